@@ -1,22 +1,35 @@
 import datetime
+from pathlib import Path
 from typing import (
     Any,
+    Callable,
+    List,
+    Literal,
+    Mapping,
     Sequence,
     Unpack,
 )
 
 from . import (
+    CacheInfo,
     ClientConfig,
+    ConnectionStats,
+    EventSource,
+    JsonPages,
     Message,
+    MessageRecords,
     Method,
     Request,
+    SentRequest,
     SocketAddr,
     StatusCode,
     Streamer,
+    Timings,
     Version,
     WebSocketRequest,
 )
 from .cookie import Cookie, Jar
+from .emulation import EmulationInfo
 from .header import HeaderMap
 from .redirect import History
 from .tls import TlsInfo
@@ -37,6 +50,23 @@ class Response:
     Get the status code of the response.
     """
 
+    ok: bool
+    r"""
+    Whether the status code is within 200-299.
+    """
+
+    elapsed: datetime.timedelta
+    r"""
+    Get the time taken from starting the request until the response headers arrived,
+    including any retries. Reading the body later does not change it.
+    """
+
+    timings: Timings
+    r"""
+    Get the breakdown of where the time before the response headers arrived was spent,
+    measured for the final attempt.
+    """
+
     version: Version
     r"""
     Get the HTTP version of the response.
@@ -52,6 +82,21 @@ class Response:
     Get the cookies of the response.
     """
 
+    set_cookies: Sequence[Cookie]
+    r"""
+    Get only the cookies set by this response's `Set-Cookie` headers, independent of
+    the client's cookie jar. A missing `Domain` or `Path` attribute is filled in with
+    the host and default path the cookie applies to.
+    """
+
+    links: dict[str, dict[str, str]]
+    r"""
+    Get the entries of the `Link` headers, like `requests`' `Response.links`. Entries are
+    keyed by their `rel` (or their URL when they have none) and hold the `url` along with
+    the other parameters, e.g. `resp.links["next"]["url"]` for paginated APIs. Malformed
+    entries are skipped.
+    """
+
     content_length: int | None
     r"""
     Get the content length of the response.
@@ -82,14 +127,173 @@ class Response:
     Get the TLS information of the response.
     """
 
-    def raise_for_status(self) -> None:
+    sent_request: SentRequest | None
+    r"""
+    Get the finalized request, if the request was made with `return_sent=True`.
+    """
+
+    decode_fallback: bool
+    r"""
+    Whether the body is the raw payload kept after decompression failed.
+    """
+
+    trailers: HeaderMap | None
+    r"""
+    Get the trailer headers of the response.
+
+    Only available once the body has been fully read (e.g. after `bytes()`, `text()` or `json()`).
+    """
+
+    content: bytes
+    r"""
+    Get the body bytes already read into memory, without reading the body again.
+
+    Raises a `RuntimeError` if the body has not been read yet (e.g. with `bytes()`, `text()`
+    or `json()`).
+    """
+
+    decoded_bytes: int | None
+    r"""
+    Get the size of the body after decompression, or `None` until the body has been read
+    into memory.
+    """
+
+    downloaded_bytes: int | None
+    r"""
+    Get the size of the body as transferred, before decompression, or `None` until the body
+    has been read into memory.
+    """
+
+    def cache_info(self) -> CacheInfo:
         r"""
-        Turn a response into an error if the server returned an error.
+        Get the caching metadata (`Age`, `Cache-Control`, `X-Cache`, ...) of the response.
         """
+        ...
+
+    def raise_for_status(self) -> "Response":
+        r"""
+        Raises `StatusError` if the status is a client (4xx) or server (5xx) error,
+        otherwise returns the response itself so the call can be chained.
+
+        The body is not consumed, so the error payload can still be read afterwards.
+        """
+        ...
 
-    def stream(self) -> Streamer:
+    def ok_or(self, default: Any) -> "Response | Any":
+        r"""
+        Returns the response itself if the status is 2xx, otherwise `default`.
+        """
+
+    def stream(
+        self,
+        max_bytes: int | None = None,
+        autodetect_compression: bool = False,
+        chunk_timeout: datetime.timedelta | None = None,
+        chunk_size: int | None = None,
+    ) -> Streamer:
         r"""
         Get the response into a `Streamer` of `bytes` from the body.
+
+        # Arguments
+
+        * `max_bytes` - Optional byte budget. The stream ends cleanly once this many body bytes
+          have been yielded, which guards against unbounded reads from untrusted sources.
+        * `autodetect_compression` - When the response declares no `Content-Encoding`, sniff
+          the body for gzip or zstd magic bytes and decompress it transparently. Off by default.
+        * `chunk_timeout` - Optional deadline for each chunk. A read raises `TimeoutError` if
+          no chunk arrives in time, which detects stalled live streams sooner than the overall
+          read timeout. The stream can still be read after a timeout.
+        * `chunk_size` - Re-buffer the body into chunks of exactly this many bytes, with a
+          shorter final chunk at the end of the body. Trailers are skipped when set.
+        """
+        ...
+
+    def iter_content(
+        self, chunk_size: int | None = None, decode_unicode: bool = False
+    ) -> Streamer:
+        r"""
+        Iterate over the response body, mirroring `requests.Response.iter_content`.
+
+        # Arguments
+
+        * `chunk_size` - Yield chunks of exactly this many bytes (the last may be shorter).
+          When `None`, chunks are yielded as they arrive from the network.
+        * `decode_unicode` - Yield `str` chunks decoded with the response charset
+          (utf-8 when unspecified) instead of `bytes`.
+        """
+        ...
+
+    def iter_lines(
+        self,
+        delimiter: bytes | None = None,
+        decode_unicode: bool = True,
+        encoding: str | None = None,
+        max_line_length: int | None = None,
+    ) -> Streamer:
+        r"""
+        Iterate over the lines of the response body, e.g. of NDJSON or event streams.
+
+        Lines spanning several network reads are reassembled before they are yielded, and a
+        final line without a delimiter is yielded when the body ends.
+
+        # Arguments
+
+        * `delimiter` - Split lines on these bytes. When `None`, lines are split on `\n`
+          and a trailing `\r` is stripped.
+        * `decode_unicode` - Yield `str` lines decoded with the response charset
+          (utf-8 when unspecified). When false, `bytes` lines are yielded.
+        * `encoding` - Decode lines with this encoding (e.g. `"latin-1"`) instead of the
+          response charset. A `ValueError` is raised for an unknown encoding.
+        * `max_line_length` - The longest line accepted, in bytes without its delimiter.
+          `BodyTooLarge` is raised once a longer line is buffered, bounding the memory a
+          body without delimiters can take. Unlimited when `None`.
+        """
+        ...
+
+    def iter_multipart(self) -> Streamer:
+        r"""
+        Iterate over the parts of a multipart body, such as a `multipart/x-mixed-replace`
+        MJPEG or server-push stream, yielding a `(headers, body)` tuple for each part.
+
+        The boundary is read from the `Content-Type` header; a `ValueError` is raised when
+        the response is not multipart or declares no boundary.
+        """
+        ...
+
+    def paginate_json(
+        self, cursor_path: str, next_url: Callable[[Any], str | None]
+    ) -> "JsonPages":
+        r"""
+        Follow cursor-based pagination, yielding the parsed JSON of each page, starting
+        with this response.
+
+        The cursor is read from each page at the dotted `cursor_path` (numeric segments
+        index arrays), and `next_url` is called with it to build the URL of the next page,
+        which is fetched with a plain GET on the same client. Iteration stops when the
+        cursor is missing, null or empty, or when `next_url` returns `None`. A page with a
+        4xx or 5xx status raises `StatusError`.
+
+        # Examples
+
+        ```python
+        resp = client.get("https://api.example.com/items")
+        for page in resp.paginate_json(
+            "meta.next_cursor",
+            lambda cursor: f"https://api.example.com/items?cursor={cursor}",
+        ):
+            print(page["items"])
+        ```
+        """
+        ...
+
+    def iter_chunks(self) -> Streamer:
+        r"""
+        Iterate over the response body as chunks arrive, yielding each one as a read-only
+        `memoryview` over the received bytes instead of copying it into a new `bytes` object.
+        Useful for hashing or forwarding large bodies. Trailers are skipped.
+
+        A view stays valid for as long as it is referenced, but it keeps the whole received
+        chunk alive; call `bytes(view)` to keep a copy and release it. Views are read-only.
         """
         ...
 
@@ -99,9 +303,62 @@ class Response:
         """
         ...
 
-    def json(self) -> Any:
+    def extract_links(
+        self,
+        base: str | None = None,
+        attrs: Mapping[str, Sequence[str]] | None = None,
+    ) -> List[str]:
+        r"""
+        Extract absolute URLs from the links of an HTML body.
+
+        Only `http` and `https` URLs are returned, deduplicated and in document order.
+
+        # Arguments
+
+        * `base` - The URL relative links are resolved against. Defaults to the document's
+          `<base href>`, or the response URL.
+        * `attrs` - Tag names mapped to the attributes searched for links. Defaults to
+          `href`/`src`/`action` on `a`, `area`, `link`, `img`, `script`, `iframe`, `frame`,
+          `embed`, `source`, `audio`, `video` and `form`.
+        """
+        ...
+
+    def follow_refresh(
+        self, meta: bool = True, wait: bool = False
+    ) -> "Response" | None:
+        r"""
+        Follow a `Refresh` redirect (e.g. `Refresh: 5; url=/next`), which some sites use
+        instead of a 3xx status, and return the response of the refreshed page.
+
+        Returns `None` when the response does not refresh to another URL.
+
+        # Arguments
+
+        * `meta` - Also look for a `<meta http-equiv="refresh">` tag when the body is HTML
+          and there is no `Refresh` header. This reads the body.
+        * `wait` - Wait out the refresh delay before requesting the target.
+
+        # Examples
+
+        ```python
+        next_resp = resp.follow_refresh()
+        ```
+        """
+        ...
+
+    def json(self, loads: Callable[[bytes], Any] | None = None) -> Any:
         r"""
         Get the JSON content of the response.
+
+        Pass `loads` to parse the body bytes with it instead of the built-in parser,
+        e.g. `orjson.loads` for speed, or `functools.partial(json.loads,
+        parse_float=Decimal)` to keep the precision of numbers.
+        """
+
+    def json_or(self, default: Any) -> Any:
+        r"""
+        Get the JSON content of the response, or `default` if the status is not 2xx or the
+        body cannot be read or parsed. Errors raised by a `validator` are not suppressed.
         """
 
     def bytes(self) -> bytes:
@@ -110,6 +367,56 @@ class Response:
         """
         ...
 
+    def raw_bytes(self) -> bytes:
+        r"""
+        Get the body exactly as received on the wire, without undoing its
+        `Content-Encoding`. Decompression happens while the body is read, so send the
+        request with `decompress=False` for this. Raises `RuntimeError` if the body was
+        decompressed.
+        """
+        ...
+
+    def save(
+        self,
+        path: str | Path,
+        digest: str | None = None,
+        algorithm: Literal["sha256", "sha512", "md5"] = "sha256",
+        chunk_size: int | None = None,
+        append: bool = False,
+    ) -> int:
+        r"""
+        Stream the response body into a file, returning the number of bytes written.
+
+        When `digest` is given, the body is hashed with `algorithm` while it is written.
+        If the hex digest does not match, the written bytes are discarded and
+        `DigestMismatch` is raised.
+
+        # Arguments
+
+        * `chunk_size` - Gather small chunks and write them in blocks of up to this many bytes.
+        * `append` - Append to the file instead of overwriting it.
+
+        # Examples
+
+        ```python
+        import wreq.blocking
+
+        resp = wreq.blocking.Client().get("https://example.com/archive.tar.gz")
+        resp.save("archive.tar.gz", digest="9f86d08...", algorithm="sha256")
+        ```
+        """
+        ...
+
+    def download(self, path: str | Path, chunk_size: int | None = None) -> int:
+        r"""
+        Download the response body into a file without buffering it in memory, returning the
+        number of bytes written.
+
+        When `chunk_size` is set, small chunks are gathered and written to the file in blocks
+        of up to that many bytes. Raises an error if the body was already consumed.
+        """
+        ...
+
     def close(self) -> None:
         r"""
         Close the response.
@@ -121,7 +428,20 @@ class Response:
 
     def __enter__(self) -> Any: ...
     def __exit__(self, _exc_type: Any, _exc_value: Any, _traceback: Any) -> None: ...
-    def __str__(self) -> str: ...
+    def __bytes__(self) -> bytes:
+        r"""
+        Get the buffered body. Raises `RuntimeError` if the body has not been read yet.
+        """
+        ...
+
+    def __str__(self) -> str:
+        r"""
+        Get the buffered body decoded with the response charset (utf-8 when unspecified).
+        Raises `RuntimeError` if the body has not been read yet.
+        """
+        ...
+
+    def __repr__(self) -> str: ...
 
 
 class WebSocket:
@@ -201,6 +521,30 @@ class WebSocket:
         * `reason` - An optional reason for closing.
         """
 
+    def records(self, timeout: datetime.timedelta | None = None) -> MessageRecords:
+        r"""
+        Iterate over received messages together with their receive metadata.
+
+        Each item is a `(seq, recv_time, message)` tuple, see `wreq.WebSocket.records`.
+
+        # Arguments
+
+        * `timeout` - An optional timeout applied to each receive.
+        """
+
+    def run(
+        self,
+        on_text: Callable[[str], Any] | None = None,
+        on_binary: Callable[[bytes], Any] | None = None,
+        on_close: Callable[[int | None, str | None], Any] | None = None,
+    ) -> None:
+        r"""
+        Run the receive loop, dispatching each message to the matching callback until closed.
+
+        See `wreq.WebSocket.run`; callbacks here must be plain functions.
+        """
+        ...
+
     def __enter__(self) -> Any: ...
     def __exit__(self, _exc_type: Any, _exc_value: Any, _traceback: Any) -> None: ...
     def __str__(self) -> str: ...
@@ -247,7 +591,9 @@ class Client:
         r"""
         Closes the client and any associated resources.
 
-        After calling this method, the client should not be used to make further requests.
+        Requests in flight are cancelled and the connection pool is released. Any request
+        made after this raises a `RuntimeError`. Leaving a `with` block closes the client
+        too.
 
         Examples:
 
@@ -265,6 +611,70 @@ class Client:
         """
         ...
 
+    def emulation_info(self) -> EmulationInfo:
+        r"""
+        Returns a summary of the emulation profile configured on the client, without
+        making a request.
+        """
+        ...
+
+    def connection_stats(self) -> ConnectionStats | None:
+        r"""
+        Returns how many requests were served by a reused pooled connection versus a new
+        connection, or `None` unless the client was created with `track_connections=True`.
+
+        A high share of new connections points at pool or keep-alive misconfiguration.
+        """
+        ...
+
+    def pipe(
+        self,
+        source: Response,
+        method: Method,
+        url: str,
+        **kwargs: Unpack[Request],
+    ) -> Response:
+        r"""
+        Sends a request whose body is streamed straight from the body of `source`, without
+        buffering it, e.g. to relay a download to another server.
+
+        The source body is consumed and sent chunked; its `Content-Type` is forwarded unless
+        the request sets one. The request cannot also set `body`, `json`, `form` or
+        `multipart`.
+
+        # Examples
+
+        ```python
+        client = wreq.blocking.Client()
+        src = client.get("https://httpbin.io/bytes/1048576")
+        resp = client.pipe(src, wreq.Method.POST, "https://httpbin.io/post")
+        ```
+        """
+        ...
+
+    def to_curl(
+        self,
+        method: Method,
+        url: str,
+        **kwargs: Unpack[Request],
+    ) -> str:
+        r"""
+        Builds the request without sending it and returns the equivalent `curl` command.
+
+        The command covers the method, the headers and body set on the request, the
+        request's (or else the client's first) proxy, and `--insecure` when certificate
+        verification is disabled. Headers the client adds when sending, such as emulation
+        or default headers, are not included. Streaming bodies cannot be rendered.
+
+        # Examples
+
+        ```python
+        client = wreq.Client()
+        print(client.to_curl(wreq.Method.POST, "https://httpbin.io/post", json={"a": 1}))
+        ```
+        """
+        ...
+
     def request(
         self,
         method: Method,
@@ -307,6 +717,23 @@ class Client:
         """
         ...
 
+    def sse(self, url: str, **kwargs: Unpack[Request]) -> "EventSource":
+        r"""
+        Opens a stream of server-sent events (`text/event-stream`), connecting on the first
+        iteration. See `wreq.Client.sse`.
+
+        # Examples
+
+        ```python
+        import wreq.blocking
+
+        client = wreq.blocking.Client()
+        for event in client.sse("https://example.com/events"):
+            print(event.event, event.id, event.data)
+        ```
+        """
+        ...
+
     def trace(
         self,
         url: str,
@@ -489,11 +916,15 @@ class Client:
 
 def delete(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -509,11 +940,15 @@ def delete(
 
 def get(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -529,11 +964,15 @@ def get(
 
 def head(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -549,11 +988,15 @@ def head(
 
 def options(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -569,11 +1012,15 @@ def options(
 
 def patch(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -589,11 +1036,15 @@ def patch(
 
 def post(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -609,11 +1060,15 @@ def post(
 
 def put(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -630,6 +1085,8 @@ def put(
 def request(
     method: Method,
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
@@ -657,11 +1114,15 @@ def request(
 
 def trace(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> "Response":
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -677,6 +1138,8 @@ def trace(
 
 def websocket(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[WebSocketRequest],
 ) -> "WebSocket":
     r"""
@@ -8,6 +8,7 @@ and provides a cookie jar for automatic cookie handling during HTTP requests.
 
 import datetime
 from enum import Enum, auto
+from pathlib import Path
 from typing import Sequence, final
 
 __all__ = ["SameSite", "Cookie", "Jar"]
@@ -166,3 +167,30 @@ class Jar:
         Remove all cookies from the jar.
         """
         ...
+
+    def save(self, path: str | Path) -> None:
+        r"""
+        Save all cookies in the jar to a JSON file, including their domain, path,
+        expiry, `Secure`, `HttpOnly` and `SameSite` attributes. Cookies set without a
+        `Domain` attribute are saved as host-only and restored for their host alone.
+
+        # Examples
+
+        ```python
+        from wreq import Client
+        from wreq.cookie import Jar
+
+        jar = Jar.load("cookies.json")
+        client = Client(cookie_provider=jar)
+        ...
+        jar.save("cookies.json")
+        ```
+        """
+        ...
+
+    @staticmethod
+    def load(path: str | Path) -> "Jar":
+        r"""
+        Load a jar from a JSON file written by `save`. Expired cookies are dropped.
+        """
+        ...
@@ -7,6 +7,7 @@ from ipaddress import IPv4Address, IPv6Address
 __all__ = [
     "LookupIpStrategy",
     "ResolverOptions",
+    "DnsOverHttps",
 ]
 
 
@@ -80,3 +81,35 @@ class ResolverOptions:
             ... ])
         """
         ...
+
+
+@final
+class DnsOverHttps:
+    """A DNS-over-HTTPS endpoint for the `dns` client option.
+
+    Args:
+        url: The `https://` URL of the endpoint, e.g. `"https://1.1.1.1/dns-query"`.
+        bootstrap: Addresses of the endpoint itself. When given, the endpoint's host name
+            is not looked up, which keeps resolution working on networks whose DNS is
+            blocked or tampered with.
+
+    Example:
+        >>> from ipaddress import IPv4Address
+        >>> doh = DnsOverHttps(
+        ...     "https://cloudflare-dns.com/dns-query",
+        ...     bootstrap=[IPv4Address("1.1.1.1"), IPv4Address("1.0.0.1")],
+        ... )
+        >>> client = wreq.Client(dns=doh)
+    """
+
+    url: str
+    """The URL of the endpoint."""
+
+    bootstrap: list[IPv4Address | IPv6Address]
+    """The bootstrap addresses of the endpoint."""
+
+    def __init__(
+        self,
+        url: str,
+        bootstrap: Sequence[str | IPv4Address | IPv6Address] | None = None,
+    ) -> None: ...
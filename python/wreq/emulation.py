@@ -9,9 +9,11 @@ authentic and less likely to be blocked by anti-bot systems.
 """
 
 from enum import Enum, auto
-from typing import ClassVar, final
+from typing import ClassVar, List, final
 
-__all__ = ["Emulation", "Profile", "Platform"]
+from .tls import AlpnProtocol, TlsVersion
+
+__all__ = ["Emulation", "EmulationInfo", "Profile", "Platform"]
 
 
 @final
@@ -184,8 +186,8 @@ class Emulation:
     Represents the configuration options for emulating a client profile and platform.
 
     The `Emulation` struct allows you to configure various aspects of profile and platform
-    emulation, including the profile, platform, and whether to enable certain features
-    like HTTP/2 or headers.
+    emulation, including the profile, platform, and which layers (TLS, HTTP/2, headers)
+    of the profile are applied.
     """
 
     # Chrome versions
@@ -330,15 +332,20 @@ class Emulation:
         platform: Platform = Platform.MacOS,
         http2: bool = True,
         headers: bool = True,
+        tls: bool = True,
     ) -> None:
         """
         Create a new emulation configuration.
 
+        Each layer of the profile (TLS, HTTP/2, headers) can be toggled independently,
+        e.g. to combine a profile's TLS fingerprint with your own headers.
+
         Args:
             profile: Whether to change the profile (browser/okhttp) information.
             platform: Whether to change the platform (Windows/macOS/Linux/Android/iOS) information.
-            http2: Whether to enable HTTP/2.
-            headers: Whether to include default headers.
+            http2: Whether to apply the profile's HTTP/2 settings.
+            headers: Whether to include the profile's default headers.
+            tls: Whether to apply the profile's TLS settings.
 
         Returns:
             A configured Emulation instance
@@ -352,6 +359,13 @@ class Emulation:
                 http2=False,
                 headers=True
             )
+
+            # Only the TLS fingerprint of Firefox, with custom headers
+            option = Emulation(
+                profile=Profile.Firefox139,
+                http2=False,
+                headers=False,
+            )
             ```
         """
         ...
@@ -368,3 +382,20 @@ class Emulation:
             ```
         """
         ...
+
+
+class EmulationInfo:
+    r"""
+    A summary of the emulation profile configured on a client, as returned by
+    `Client.emulation_info()`.
+
+    `profile` and `platform` are `None` when no emulation is configured or they are not
+    known up front (e.g. `Emulation.random()`). The TLS details reflect any `tls_options`
+    set on the client.
+    """
+
+    profile: Profile | None
+    platform: Platform | None
+    min_tls_version: TlsVersion | None
+    max_tls_version: TlsVersion | None
+    alpn_protocols: List[AlpnProtocol] | None
@@ -6,34 +6,58 @@ The exceptions are organized into logical categories based on their cause and
 severity, making it easier to handle specific types of errors appropriately.
 """
 
+from typing import Any
+
+from .redirect import History
+
 __all__ = [
+    "RnetError",
     "TlsError",
     "ConnectionError",
     "ProxyConnectionError",
     "ConnectionResetError",
     "BodyError",
+    "BodyTooLarge",
     "BuilderError",
     "DecodingError",
+    "DigestMismatch",
+    "ValidationError",
     "StatusError",
     "RequestError",
+    "HostNotAllowed",
     "RedirectError",
     "UpgradeError",
     "WebSocketError",
     "TimeoutError",
+    "ClientClosedError",
+    "ConsumedError",
+    "IoError",
+    "SerializationError",
+    "InvalidHeaderError",
+    "WebSocketClosedError",
 ]
 
+class RnetError(Exception):
+    r"""
+    The base class of every exception raised by the client.
+
+    Catch this to handle any client failure in one place, or one of its
+    subclasses below to handle a specific kind of failure.
+    """
+
+
 # ========================================
 # Network and System-Level Errors
 # ========================================
 
 
-class RustPanic(Exception):
+class RustPanic(RnetError):
     r"""
     A panic occurred in the underlying Rust code.
     """
 
 
-class TlsError(Exception):
+class TlsError(RnetError):
     r"""
     An error occurred in the TLS security layer.
 
@@ -45,7 +69,7 @@ class TlsError(Exception):
     """
 
 
-class ConnectionError(Exception):
+class ConnectionError(RnetError):
     r"""
     An error occurred while establishing a connection.
 
@@ -58,7 +82,7 @@ class ConnectionError(Exception):
     """
 
 
-class ProxyConnectionError(Exception):
+class ProxyConnectionError(RnetError):
     r"""
     An error occurred while connecting through a proxy server.
 
@@ -72,7 +96,7 @@ class ProxyConnectionError(Exception):
     """
 
 
-class ConnectionResetError(Exception):
+class ConnectionResetError(RnetError):
     r"""
     The connection was reset by the remote peer.
 
@@ -88,7 +112,7 @@ class ConnectionResetError(Exception):
 # ========================================
 
 
-class BodyError(Exception):
+class BodyError(RnetError):
     r"""
     An error occurred while processing the body of a request or response.
 
@@ -100,7 +124,16 @@ class BodyError(Exception):
     """
 
 
-class BuilderError(Exception):
+class BodyTooLarge(BodyError):
+    r"""
+    The response body is larger than the `max_body_size` limit.
+
+    Raised before reading when the `Content-Length` is over the limit, and
+    while reading once the bytes received exceed it.
+    """
+
+
+class BuilderError(RnetError):
     r"""
     An error occurred while building a request or response.
 
@@ -112,7 +145,7 @@ class BuilderError(Exception):
     """
 
 
-class DecodingError(Exception):
+class DecodingError(RnetError):
     r"""
     An error occurred while decoding a response.
 
@@ -124,17 +157,56 @@ class DecodingError(Exception):
     """
 
 
-class StatusError(Exception):
+class DigestMismatch(RnetError):
+    r"""
+    The digest of a downloaded body did not match the expected value.
+
+    This exception is raised by `Response.save` when an expected
+    digest is provided and the hash of the written body differs.
+    """
+
+
+class ValidationError(RnetError):
+    r"""
+    A response body was rejected by the request's `validator`.
+
+    This exception is raised by `Response.json` when the validator
+    callback raises. The original exception is available as `__cause__`.
+    """
+
+
+class StatusError(RnetError):
     r"""
     An error occurred while processing the status code of a response.
 
     This exception is typically raised for HTTP error status codes
-    (4xx, 5xx) when automatic error handling is enabled, or when
-    there are issues interpreting the status line.
+    (4xx, 5xx) by `Response.raise_for_status()` or when automatic error
+    handling is enabled, or when there are issues interpreting the status line.
+    """
+
+    url: str
+    r"""
+    The URL of the response.
     """
 
+    status: int
+    r"""
+    The status code of the response.
+    """
+
+    reason: str | None
+    r"""
+    The canonical reason phrase of the status code, e.g. `"Not Found"`.
+    """
 
-class RequestError(Exception):
+    response: Any | None
+    r"""
+    The failed `Response` when raised by `Response.raise_for_status()`, with its body
+    still unread, or `None` when raised by the client's `raise_for_status` option.
+    """
+
+
+class RequestError(RnetError):
     r"""
     An error occurred while making a request.
 
@@ -144,12 +216,21 @@ class RequestError(Exception):
     """
 
 
+class HostNotAllowed(RnetError):
+    r"""
+    The request target is not in the client's `allowed_hosts` allowlist, or resolves to a
+    private network address while `block_private_networks` is enabled.
+
+    Raised before any connection is made.
+    """
+
+
 # ========================================
 # HTTP Protocol and Navigation Errors
 # ========================================
 
 
-class RedirectError(Exception):
+class RedirectError(RnetError):
     r"""
     An error occurred while following a redirect.
 
@@ -159,10 +240,19 @@ class RedirectError(Exception):
     - Invalid redirect location
     - Cross-protocol redirects when not allowed
     - Redirect limit exceeded
+
+    When the request was made with a `Policy.limited()` or `Policy.custom()` redirect
+    policy, `history` holds the redirect hops followed before the error, which makes
+    redirect loops easy to spot.
     """
 
+    history: list[History]
+    r"""
+    The redirect chain followed before the error was raised.
+    """
 
-class UpgradeError(Exception):
+
+class UpgradeError(RnetError):
     r"""
     An error occurred while upgrading a connection.
 
@@ -174,7 +264,7 @@ class UpgradeError(Exception):
     """
 
 
-class WebSocketError(Exception):
+class WebSocketError(RnetError):
     r"""
     An error occurred while handling a WebSocket connection.
 
@@ -186,12 +276,20 @@ class WebSocketError(Exception):
     """
 
 
+class WebSocketClosedError(WebSocketError, RuntimeError):
+    r"""
+    The WebSocket has been disconnected.
+
+    Raised when sending on or closing a WebSocket that is no longer connected.
+    """
+
+
 # ========================================
 # Timeout Errors
 # ========================================
 
 
-class TimeoutError(Exception):
+class TimeoutError(RnetError):
     r"""
     A timeout occurred while waiting for a response.
 
@@ -204,3 +302,57 @@ class TimeoutError(Exception):
     Timeouts can often be resolved by increasing timeout values
     or retrying the request.
     """
+
+
+# ========================================
+# Client State and Usage Errors
+# ========================================
+#
+# These also derive from `RuntimeError`, which they were raised as before the
+# client had its own exceptions, so existing `except RuntimeError` handlers
+# keep catching them.
+
+
+class ClientClosedError(RnetError, RuntimeError):
+    r"""
+    The client has been closed.
+
+    Raised when sending a request through a client after `close()` or after
+    leaving its `with` block.
+    """
+
+
+class ConsumedError(RnetError, RuntimeError):
+    r"""
+    An instance was used after it had been consumed.
+
+    Raised when a value that can only be used once, such as a streamed body,
+    is used again.
+    """
+
+
+class IoError(RnetError, RuntimeError):
+    r"""
+    An I/O error occurred outside the connection itself.
+
+    This covers failures such as reading a file passed as the request body
+    or writing a cookie jar to disk.
+    """
+
+
+class SerializationError(RnetError, RuntimeError):
+    r"""
+    Data could not be serialized or parsed as JSON or form data.
+
+    Raised when a `json` or `form` request body cannot be encoded, or when a
+    saved cookie jar cannot be read back.
+    """
+
+
+class InvalidHeaderError(RnetError, RuntimeError):
+    r"""
+    A header name or value is not valid.
+
+    Raised when a header contains characters HTTP does not allow, such as
+    newlines or non-visible ASCII in its name.
+    """
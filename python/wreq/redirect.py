@@ -20,14 +20,18 @@ class Policy:
     """
 
     @staticmethod
-    def limited(max: int | None = None) -> "Policy":
+    def limited(
+        max: int | None = None, max_per_host: int | None = None
+    ) -> "Policy":
         """
         Create a Policy with a maximum number of redirects.
 
         An error will be returned if the max is reached.
 
         Args:
-            max: Maximum number of redirects to follow
+            max: Maximum number of redirects to follow (defaults to 10)
+            max_per_host: Maximum number of redirects to any single host, which catches
+                loops bouncing between URLs on the same host before `max` is reached
 
         Returns:
             Policy: A redirect policy with the specified limit
@@ -181,6 +185,9 @@ class History:
     status: int
     """Get the status code of the redirect response."""
 
+    method: str
+    """Get the method of the request the redirect response answered."""
+
     url: str
     """Get the URL of the redirect response."""
 
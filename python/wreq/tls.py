@@ -161,6 +161,22 @@ class Identity:
         """
         ...
 
+    @staticmethod
+    def from_pkcs12(data: bytes, password: str) -> "Identity":
+        """
+        Parses a DER-formatted PKCS #12 archive (`.p12` or `.pfx`), using `password` to
+        decrypt the key. Same as `from_pkcs12_der`.
+        """
+        ...
+
+    @staticmethod
+    def from_pem(cert: bytes, key: bytes) -> "Identity":
+        """
+        Parses a chain of PEM encoded X509 certificates, leaf first, and the PEM encoded
+        PKCS #8 private key of the leaf certificate. Same as `from_pkcs8_pem`.
+        """
+        ...
+
 
 @final
 class CertStore:
@@ -5,8 +5,11 @@ from pathlib import Path
 from typing import (
     Any,
     AsyncGenerator,
+    Callable,
     Mapping,
     Generator,
+    List,
+    Literal,
     NotRequired,
     Sequence,
     Mapping,
@@ -19,11 +22,11 @@ from typing import (
 from . import redirect
 from . import emulation
 from .cookie import *
-from .dns import ResolverOptions
+from .dns import DnsOverHttps, ResolverOptions
 from .emulation import *
 from .header import *
 from .http1 import Http1Options
-from .http2 import Http2Options
+from .http2 import Http2Options, StreamDependency
 from .proxy import *
 from .redirect import History
 from .tls import *
@@ -268,9 +271,54 @@ class Message:
         """
         ...
 
+    @staticmethod
+    def fragment(
+        data: bytes | str,
+        fin: bool = False,
+        opcode: Literal["continue", "text", "binary"] = "continue",
+    ) -> "Message":
+        r"""
+        Creates a raw data frame for sending a message in fragments.
+
+        Start a fragmented message with `opcode="text"` or `"binary"`, follow up with
+        `"continue"` frames, and set `fin=True` on the last one. Frames are sent as-is:
+        a wrong sequence (e.g. a continuation without a start frame) violates the
+        WebSocket protocol and will likely make the server close the connection.
+
+        # Arguments
+
+        * `data` - The payload of this fragment.
+        * `fin` - Whether this is the final fragment of the message.
+        * `opcode` - The frame opcode.
+
+        # Examples
+
+        ```python
+        await ws.send_all([
+            Message.fragment("Hello, ", opcode="text"),
+            Message.fragment("world", fin=True),
+        ])
+        ```
+        """
+        ...
+
     def __str__(self) -> str: ...
 
 
+class MessageRecords:
+    r"""
+    An iterator over received WebSocket messages with their receive metadata.
+
+    Each item is a `(seq, recv_time, message)` tuple. Iteration stops when the
+    connection is closed by the peer.
+    """
+
+    def __iter__(self) -> "MessageRecords": ...
+    def __next__(self) -> Tuple[int, datetime.timedelta, Message]: ...
+    def __aiter__(self) -> "MessageRecords": ...
+    async def __anext__(self) -> Tuple[int, datetime.timedelta, Message]: ...
+
+
 class Streamer:
     r"""
     A stream response.
@@ -305,17 +353,358 @@ class Streamer:
     """
 
     def __iter__(self) -> "Streamer": ...
-    def __next__(self) -> bytes | HeaderMap: ...
+    def __next__(
+        self,
+    ) -> bytes | memoryview | str | HeaderMap | Tuple[HeaderMap, bytes]: ...
     def __enter__(self) -> Any: ...
     def __exit__(self, _exc_type: Any, _exc_value: Any, _traceback: Any) -> None: ...
     async def __aiter__(self) -> "Streamer": ...
-    async def __anext__(self) -> bytes | HeaderMap: ...
+    async def __anext__(
+        self,
+    ) -> bytes | memoryview | str | HeaderMap | Tuple[HeaderMap, bytes]: ...
     async def __aenter__(self) -> Any: ...
     async def __aexit__(
         self, _exc_type: Any, _exc_value: Any, _traceback: Any
     ) -> None: ...
 
 
+class JsonPages:
+    r"""
+    An iterator over the pages of a cursor-paginated JSON API, created by
+    `Response.paginate_json`. Supports both `for` and `async for`.
+    """
+
+    def __iter__(self) -> "JsonPages": ...
+    def __next__(self) -> Any: ...
+    def __aiter__(self) -> "JsonPages": ...
+    async def __anext__(self) -> Any: ...
+
+
+class ServerSentEvent:
+    r"""
+    An event read from a server-sent event stream.
+    """
+
+    event: str
+    r"""
+    Get the event type, `"message"` unless the server named one with `event:`.
+    """
+
+    data: str
+    r"""
+    Get the event data, with the values of multiple `data:` lines joined by newlines.
+    """
+
+    id: str | None
+    r"""
+    Get the last event ID the server set with `id:`, which carries over to later events.
+    """
+
+    retry: datetime.timedelta | None
+    r"""
+    Get the reconnection delay the server set with `retry:` along with this event.
+    """
+
+    def __repr__(self) -> str: ...
+
+
+class EventSource:
+    r"""
+    A stream of server-sent events, created by `Client.sse`. Supports both `for` and
+    `async for`.
+
+    The stream is reconnected whenever it ends or breaks off, after the delay the server
+    last set with `retry:` (3 seconds by default), sending `Last-Event-ID` so the server can
+    resume from the last event. A partial event is dropped when the stream breaks off.
+    Iteration stops when the server answers `204 No Content`.
+    """
+
+    def __iter__(self) -> "EventSource": ...
+    def __next__(self) -> ServerSentEvent: ...
+    def __aiter__(self) -> "EventSource": ...
+    async def __anext__(self) -> ServerSentEvent: ...
+
+
+class CacheControl:
+    r"""
+    Parsed `Cache-Control` directives.
+
+    Directive names are case-insensitive and stored lowercased; quoted values are unquoted.
+    """
+
+    directives: Mapping[str, str | None]
+    r"""
+    Get all directives, mapping each name to its value (or `None` for flags).
+    """
+
+    max_age: int | None
+    r"""
+    Get the `max-age` directive in seconds.
+    """
+
+    s_maxage: int | None
+    r"""
+    Get the `s-maxage` directive in seconds.
+    """
+
+    stale_while_revalidate: int | None
+    r"""
+    Get the `stale-while-revalidate` directive in seconds.
+    """
+
+    stale_if_error: int | None
+    r"""
+    Get the `stale-if-error` directive in seconds.
+    """
+
+    no_cache: bool
+    r"""
+    Whether the `no-cache` directive is present.
+    """
+
+    no_store: bool
+    r"""
+    Whether the `no-store` directive is present.
+    """
+
+    no_transform: bool
+    r"""
+    Whether the `no-transform` directive is present.
+    """
+
+    must_revalidate: bool
+    r"""
+    Whether the `must-revalidate` directive is present.
+    """
+
+    proxy_revalidate: bool
+    r"""
+    Whether the `proxy-revalidate` directive is present.
+    """
+
+    private: bool
+    r"""
+    Whether the `private` directive is present.
+    """
+
+    public: bool
+    r"""
+    Whether the `public` directive is present.
+    """
+
+    immutable: bool
+    r"""
+    Whether the `immutable` directive is present.
+    """
+
+    def __contains__(self, name: str) -> bool: ...
+    def __str__(self) -> str: ...
+
+
+class Timings:
+    r"""
+    A breakdown of where the time before the response headers arrived was spent.
+
+    Connection setup and the TLS handshake are not reported on their own; they are
+    part of `ttfb` whenever a new connection had to be opened.
+
+    # Examples
+
+    ```python
+    import wreq
+
+    resp = await wreq.get("https://example.com")
+    print(resp.timings.dns, resp.timings.ttfb)
+    ```
+    """
+
+    dns: datetime.timedelta | None
+    r"""
+    Get the time spent resolving host names, or `None` if no lookup was made,
+    e.g. when a pooled connection was reused.
+    """
+
+    ttfb: datetime.timedelta
+    r"""
+    Get the time from sending the request until the response headers arrived.
+    """
+
+    def __repr__(self) -> str: ...
+
+
+class CacheInfo:
+    r"""
+    Structured caching metadata of a response.
+
+    # Examples
+
+    ```python
+    import wreq
+
+    resp = await wreq.get("https://example.com")
+    info = resp.cache_info()
+    print(info.age, info.cache_control.max_age, info.hit)
+    ```
+    """
+
+    age: int | None
+    r"""
+    Get the `Age` header in seconds.
+    """
+
+    cache_control: CacheControl
+    r"""
+    Get the parsed `Cache-Control` directives.
+    """
+
+    cdn_cache_control: CacheControl | None
+    r"""
+    Get the parsed `CDN-Cache-Control` directives, if present.
+    """
+
+    x_cache: str | None
+    r"""
+    Get the raw `X-Cache` header, if present.
+    """
+
+    hit: bool | None
+    r"""
+    Whether the `X-Cache` header reports a cache hit.
+    """
+
+    def __str__(self) -> str: ...
+
+
+class SentRequest:
+    r"""
+    The request as built from its options, before the client dispatches it.
+
+    Available as `Response.sent_request` when the request was made with `return_sent=True`.
+    It is not a capture of the bytes on the wire: the headers are taken before the client's
+    default and emulation headers, cookies from the jar, `Accept-Encoding` and `Host` are
+    added, and header names are lowercased with the original casing and ordering options
+    applied only when the request is written.
+    """
+
+    method: str
+    r"""
+    Get the method of the sent request.
+    """
+
+    url: str
+    r"""
+    Get the URL of the sent request.
+    """
+
+    headers: HeaderMap
+    r"""
+    Get the headers set on the request itself, with lowercased names.
+    """
+
+    def __str__(self) -> str: ...
+
+
+class ConnectionStats:
+    r"""
+    A snapshot of how a client's requests were served.
+
+    The counts are approximate, see the `track_connections` client option.
+
+    # Examples
+
+    ```python
+    import wreq
+
+    client = wreq.Client(track_connections=True)
+    await client.get("https://example.com")
+    await client.get("https://example.com")
+    print(client.connection_stats())
+    ```
+    """
+
+    requests: int
+    r"""
+    The number of responses received.
+    """
+
+    reused: int
+    r"""
+    The number of responses served by a pooled connection.
+    """
+
+    new_connections: int
+    r"""
+    The number of responses that required opening a new connection.
+    """
+
+    def __repr__(self) -> str: ...
+
+
+class Retry:
+    r"""
+    A retry policy for transient failures.
+
+    A request is retried on connection errors and on responses with one of `statuses`,
+    up to `max_attempts` attempts in total. The delay before each retry starts at
+    `backoff` and doubles every time, unless the response carries a `Retry-After`
+    header, which is honored instead. With `jitter`, each delay is randomized
+    between half and all of its value. The last response or error is returned.
+
+    When a timeout is set, it bounds all attempts together: each one only gets the time
+    left, and no retry is started that could not finish in time. Without one, a
+    `Retry-After` hint is capped at the longest backoff the policy would wait.
+
+    # Examples
+
+    ```python
+    import datetime
+    import wreq
+
+    retry = wreq.Retry(max_attempts=4, backoff=datetime.timedelta(seconds=1))
+    resp = await wreq.get("https://example.com", retry=retry)
+    ```
+    """
+
+    def __init__(
+        self,
+        max_attempts: int = 3,
+        backoff: datetime.timedelta = datetime.timedelta(milliseconds=500),
+        jitter: bool = True,
+        statuses: Sequence[int] | None = None,
+    ) -> None:
+        r"""
+        Create a new retry policy.
+
+        # Arguments
+
+        * `max_attempts` - The total number of attempts, including the first. Must be positive.
+        * `backoff` - The delay before the first retry.
+        * `jitter` - Whether to randomize delays.
+        * `statuses` - The statuses to retry, `[429, 502, 503, 504]` by default.
+        """
+        ...
+
+    max_attempts: int
+    r"""
+    The total number of attempts, including the first one.
+    """
+
+    backoff: datetime.timedelta
+    r"""
+    The delay before the first retry, doubled for each further one.
+    """
+
+    jitter: bool
+    r"""
+    Whether delays are randomized.
+    """
+
+    statuses: List[int]
+    r"""
+    The response statuses that are retried.
+    """
+
+
 class Response:
     r"""
     A response from a request.
@@ -354,6 +743,23 @@ class Response:
     Get the status code of the response.
     """
 
+    ok: bool
+    r"""
+    Whether the status code is within 200-299.
+    """
+
+    elapsed: datetime.timedelta
+    r"""
+    Get the time taken from starting the request until the response headers arrived,
+    including any retries. Reading the body later does not change it.
+    """
+
+    timings: Timings
+    r"""
+    Get the breakdown of where the time before the response headers arrived was spent,
+    measured for the final attempt.
+    """
+
     version: Version
     r"""
     Get the HTTP version of the response.
@@ -369,6 +775,21 @@ class Response:
     Get the cookies of the response.
     """
 
+    set_cookies: Sequence[Cookie]
+    r"""
+    Get only the cookies set by this response's `Set-Cookie` headers, independent of
+    the client's cookie jar. A missing `Domain` or `Path` attribute is filled in with
+    the host and default path the cookie applies to.
+    """
+
+    links: dict[str, dict[str, str]]
+    r"""
+    Get the entries of the `Link` headers, like `requests`' `Response.links`. Entries are
+    keyed by their `rel` (or their URL when they have none) and hold the `url` along with
+    the other parameters, e.g. `resp.links["next"]["url"]` for paginated APIs. Malformed
+    entries are skipped.
+    """
+
     content_length: int | None
     r"""
     Get the content length of the response.
@@ -394,14 +815,177 @@ class Response:
     Get the TLS information of the response.
     """
 
-    def raise_for_status(self) -> None:
+    sent_request: SentRequest | None
+    r"""
+    Get the finalized request, if the request was made with `return_sent=True`.
+    """
+
+    decode_fallback: bool
+    r"""
+    Whether the body is the raw payload kept after decompression failed.
+
+    Only set when the request was made with `decode_fallback=True`.
+    """
+
+    trailers: HeaderMap | None
+    r"""
+    Get the trailer headers of the response.
+
+    Only available once the body has been fully read (e.g. after `bytes()`, `text()` or `json()`).
+    """
+
+    content: bytes
+    r"""
+    Get the body bytes already read into memory, without reading the body again.
+
+    Raises a `RuntimeError` if the body has not been read yet (e.g. with `bytes()`, `text()`
+    or `json()`).
+    """
+
+    decoded_bytes: int | None
+    r"""
+    Get the size of the body after decompression, or `None` until the body has been read
+    into memory.
+    """
+
+    downloaded_bytes: int | None
+    r"""
+    Get the size of the body as transferred, before decompression, or `None` until the body
+    has been read into memory.
+    """
+
+    def cache_info(self) -> CacheInfo:
+        r"""
+        Get the caching metadata (`Age`, `Cache-Control`, `X-Cache`, ...) of the response.
+        """
+        ...
+
+    def raise_for_status(self) -> "Response":
+        r"""
+        Raises `StatusError` if the status is a client (4xx) or server (5xx) error,
+        otherwise returns the response itself so the call can be chained.
+
+        The body is not consumed, so the error payload can still be read afterwards.
+        """
+        ...
+
+    def ok_or(self, default: Any) -> "Response | Any":
         r"""
-        Turn a response into an error if the server returned an error.
+        Returns the response itself if the status is 2xx, otherwise `default`.
         """
 
-    def stream(self) -> Streamer:
+    def stream(
+        self,
+        max_bytes: int | None = None,
+        autodetect_compression: bool = False,
+        chunk_timeout: datetime.timedelta | None = None,
+        chunk_size: int | None = None,
+    ) -> Streamer:
         r"""
         Get the response into a `Streamer` of `bytes` from the body.
+
+        A body that has already been buffered is streamed from memory.
+
+        # Arguments
+
+        * `max_bytes` - Optional byte budget. The stream ends cleanly once this many body bytes
+          have been yielded, which guards against unbounded reads from untrusted sources.
+        * `autodetect_compression` - When the response declares no `Content-Encoding`, sniff
+          the body for gzip or zstd magic bytes and decompress it transparently. Off by default.
+        * `chunk_timeout` - Optional deadline for each chunk. A read raises `TimeoutError` if
+          no chunk arrives in time, which detects stalled live streams sooner than the overall
+          read timeout. The stream can still be read after a timeout.
+        * `chunk_size` - Re-buffer the body into chunks of exactly this many bytes, with a
+          shorter final chunk at the end of the body. Trailers are skipped when set.
+        """
+        ...
+
+    def iter_content(
+        self, chunk_size: int | None = None, decode_unicode: bool = False
+    ) -> Streamer:
+        r"""
+        Iterate over the response body, mirroring `requests.Response.iter_content`.
+
+        # Arguments
+
+        * `chunk_size` - Yield chunks of exactly this many bytes (the last may be shorter).
+          When `None`, chunks are yielded as they arrive from the network.
+        * `decode_unicode` - Yield `str` chunks decoded with the response charset
+          (utf-8 when unspecified) instead of `bytes`.
+        """
+        ...
+
+    def iter_lines(
+        self,
+        delimiter: bytes | None = None,
+        decode_unicode: bool = True,
+        encoding: str | None = None,
+        max_line_length: int | None = None,
+    ) -> Streamer:
+        r"""
+        Iterate over the lines of the response body, e.g. of NDJSON or event streams.
+
+        Lines spanning several network reads are reassembled before they are yielded, and a
+        final line without a delimiter is yielded when the body ends.
+
+        # Arguments
+
+        * `delimiter` - Split lines on these bytes. When `None`, lines are split on `\n`
+          and a trailing `\r` is stripped.
+        * `decode_unicode` - Yield `str` lines decoded with the response charset
+          (utf-8 when unspecified). When false, `bytes` lines are yielded.
+        * `encoding` - Decode lines with this encoding (e.g. `"latin-1"`) instead of the
+          response charset. A `ValueError` is raised for an unknown encoding.
+        * `max_line_length` - The longest line accepted, in bytes without its delimiter.
+          `BodyTooLarge` is raised once a longer line is buffered, bounding the memory a
+          body without delimiters can take. Unlimited when `None`.
+        """
+        ...
+
+    def iter_multipart(self) -> Streamer:
+        r"""
+        Iterate over the parts of a multipart body, such as a `multipart/x-mixed-replace`
+        MJPEG or server-push stream, yielding a `(headers, body)` tuple for each part.
+
+        The boundary is read from the `Content-Type` header; a `ValueError` is raised when
+        the response is not multipart or declares no boundary.
+        """
+        ...
+
+    def paginate_json(
+        self, cursor_path: str, next_url: Callable[[Any], str | None]
+    ) -> "JsonPages":
+        r"""
+        Follow cursor-based pagination, yielding the parsed JSON of each page, starting
+        with this response.
+
+        The cursor is read from each page at the dotted `cursor_path` (numeric segments
+        index arrays), and `next_url` is called with it to build the URL of the next page,
+        which is fetched with a plain GET on the same client. Iteration stops when the
+        cursor is missing, null or empty, or when `next_url` returns `None`. A page with a
+        4xx or 5xx status raises `StatusError`.
+
+        # Examples
+
+        ```python
+        resp = await client.get("https://api.example.com/items")
+        async for page in resp.paginate_json(
+            "meta.next_cursor",
+            lambda cursor: f"https://api.example.com/items?cursor={cursor}",
+        ):
+            print(page["items"])
+        ```
+        """
+        ...
+
+    def iter_chunks(self) -> Streamer:
+        r"""
+        Iterate over the response body as chunks arrive, yielding each one as a read-only
+        `memoryview` over the received bytes instead of copying it into a new `bytes` object.
+        Useful for hashing or forwarding large bodies. Trailers are skipped.
+
+        A view stays valid for as long as it is referenced, but it keeps the whole received
+        chunk alive; call `bytes(view)` to keep a copy and release it. Views are read-only.
         """
         ...
 
@@ -411,9 +995,62 @@ class Response:
         """
         ...
 
-    async def json(self) -> Any:
+    async def extract_links(
+        self,
+        base: str | None = None,
+        attrs: Mapping[str, Sequence[str]] | None = None,
+    ) -> List[str]:
+        r"""
+        Extract absolute URLs from the links of an HTML body.
+
+        Only `http` and `https` URLs are returned, deduplicated and in document order.
+
+        # Arguments
+
+        * `base` - The URL relative links are resolved against. Defaults to the document's
+          `<base href>`, or the response URL.
+        * `attrs` - Tag names mapped to the attributes searched for links. Defaults to
+          `href`/`src`/`action` on `a`, `area`, `link`, `img`, `script`, `iframe`, `frame`,
+          `embed`, `source`, `audio`, `video` and `form`.
+        """
+        ...
+
+    async def follow_refresh(
+        self, meta: bool = True, wait: bool = False
+    ) -> "Response" | None:
+        r"""
+        Follow a `Refresh` redirect (e.g. `Refresh: 5; url=/next`), which some sites use
+        instead of a 3xx status, and return the response of the refreshed page.
+
+        Returns `None` when the response does not refresh to another URL.
+
+        # Arguments
+
+        * `meta` - Also look for a `<meta http-equiv="refresh">` tag when the body is HTML
+          and there is no `Refresh` header. This reads the body.
+        * `wait` - Wait out the refresh delay before requesting the target.
+
+        # Examples
+
+        ```python
+        next_resp = await resp.follow_refresh()
+        ```
+        """
+        ...
+
+    async def json(self, loads: Callable[[bytes], Any] | None = None) -> Any:
         r"""
         Get the JSON content of the response.
+
+        Pass `loads` to parse the body bytes with it instead of the built-in parser,
+        e.g. `orjson.loads` for speed, or `functools.partial(json.loads,
+        parse_float=Decimal)` to keep the precision of numbers.
+        """
+
+    async def json_or(self, default: Any) -> Any:
+        r"""
+        Get the JSON content of the response, or `default` if the status is not 2xx or the
+        body cannot be read or parsed. Errors raised by a `validator` are not suppressed.
         """
 
     async def bytes(self) -> bytes:
@@ -422,6 +1059,65 @@ class Response:
         """
         ...
 
+    async def raw_bytes(self) -> bytes:
+        r"""
+        Get the body exactly as received on the wire, without undoing its
+        `Content-Encoding`. Decompression happens while the body is read, so send the
+        request with `decompress=False` for this. Raises `RuntimeError` if the body was
+        decompressed.
+        """
+        ...
+
+    async def save(
+        self,
+        path: str | Path,
+        digest: str | None = None,
+        algorithm: Literal["sha256", "sha512", "md5"] = "sha256",
+        chunk_size: int | None = None,
+        append: bool = False,
+    ) -> int:
+        r"""
+        Stream the response body into a file, returning the number of bytes written.
+
+        When `digest` is given, the body is hashed with `algorithm` while it is written.
+        If the hex digest does not match, the written bytes are discarded and
+        `DigestMismatch` is raised.
+
+        # Arguments
+
+        * `chunk_size` - Gather small chunks and write them in blocks of up to this many bytes.
+        * `append` - Append to the file instead of overwriting it.
+
+        # Examples
+
+        ```python
+        import wreq
+
+        resp = await wreq.get("https://example.com/archive.tar.gz")
+        await resp.save("archive.tar.gz", digest="9f86d08...", algorithm="sha256")
+        ```
+        """
+        ...
+
+    async def download(self, path: str | Path, chunk_size: int | None = None) -> int:
+        r"""
+        Download the response body into a file without buffering it in memory, returning the
+        number of bytes written.
+
+        When `chunk_size` is set, small chunks are gathered and written to the file in blocks
+        of up to that many bytes. Raises an error if the body was already consumed.
+
+        # Examples
+
+        ```python
+        import wreq
+
+        resp = await wreq.get("https://example.com/archive.tar.gz")
+        written = await resp.download("archive.tar.gz", chunk_size=64 * 1024)
+        ```
+        """
+        ...
+
     async def close(self) -> None:
         r"""
         Close the response.
@@ -435,7 +1131,20 @@ class Response:
     async def __aexit__(
         self, _exc_type: Any, _exc_value: Any, _traceback: Any
     ) -> Any: ...
-    def __str__(self) -> str: ...
+    def __bytes__(self) -> bytes:
+        r"""
+        Get the buffered body. Raises `RuntimeError` if the body has not been read yet.
+        """
+        ...
+
+    def __str__(self) -> str:
+        r"""
+        Get the buffered body decoded with the response charset (utf-8 when unspecified).
+        Raises `RuntimeError` if the body has not been read yet.
+        """
+        ...
+
+    def __repr__(self) -> str: ...
 
 
 class WebSocket:
@@ -488,19 +1197,65 @@ class WebSocket:
         Send a message to the WebSocket.
         """
 
-    async def send_all(self, messages: Sequence[Message]) -> None:
-        r"""
-        Send multiple messages to the WebSocket.
+    async def send_all(self, messages: Sequence[Message]) -> None:
+        r"""
+        Send multiple messages to the WebSocket.
+        """
+
+    async def close(
+        self,
+        code: int | None = None,
+        reason: str | None = None,
+    ) -> None:
+        r"""
+        Close the WebSocket connection.
+        """
+
+    def records(self, timeout: datetime.timedelta | None = None) -> "MessageRecords":
+        r"""
+        Iterate over received messages together with their receive metadata.
+
+        Each item is a `(seq, recv_time, message)` tuple. `seq` starts at `0` and increases
+        by one for every message read from the connection. `recv_time` is the time elapsed
+        since the connection was opened, measured on a monotonic clock in the background
+        receive task, so it is neither skewed by Python scheduling delays nor by changes to
+        the system clock.
+
+        # Examples
+
+        ```python
+        async with await wreq.websocket("wss://echo.websocket.org") as ws:
+            async for seq, recv_time, message in ws.records():
+                print(seq, recv_time, message.data)
+        ```
         """
+        ...
 
-    async def close(
+    async def run(
         self,
-        code: int | None = None,
-        reason: str | None = None,
+        on_text: Callable[[str], Any] | None = None,
+        on_binary: Callable[[bytes], Any] | None = None,
+        on_close: Callable[[int | None, str | None], Any] | None = None,
     ) -> None:
         r"""
-        Close the WebSocket connection.
+        Run the receive loop, dispatching each message to the matching callback until closed.
+
+        Callbacks may be plain functions or coroutine functions; awaitable results are
+        awaited before the next message is dispatched. `on_close` receives the close code
+        and reason, or `(None, None)` if the connection ended without a close frame.
+        An exception raised by a callback stops the loop and is propagated.
+
+        # Examples
+
+        ```python
+        async def on_text(text: str):
+            print("text:", text)
+
+        ws = await wreq.websocket("wss://echo.websocket.org")
+        await ws.run(on_text=on_text, on_close=lambda code, reason: print("closed", code))
+        ```
         """
+        ...
 
     def __aenter__(self) -> Any: ...
     def __aexit__(self, _exc_type: Any, _exc_value: Any, _traceback: Any) -> Any: ...
@@ -508,9 +1263,42 @@ class WebSocket:
 
 
 class ClientConfig(TypedDict):
+    base_url: NotRequired[str]
+    """
+    The base URL that relative request URLs are resolved against (RFC 3986), so
+    `client.get("/users")` works. Absolute request URLs replace the base entirely. Note that
+    a base path without a trailing slash is replaced by relative paths, as in a browser.
+    A malformed base raises `BuilderError` when the client is created.
+    """
+
     emulation: NotRequired[emulation.Emulation | emulation.Profile]
     """Emulation config."""
 
+    host_emulation: NotRequired[
+        Mapping[str, emulation.Emulation | emulation.Profile]
+    ]
+    """
+    Emulation config keyed by host pattern, chosen per request from the target host and
+    overriding `emulation`. Patterns are exact hosts (`example.com`) or wildcards
+    (`*.example.com`) matching any subdomain; exact matches win over wildcards.
+    A per-request `emulation` still takes precedence.
+    """
+
+    emulation_fallback: NotRequired[
+        Sequence[emulation.Emulation | emulation.Profile]
+    ]
+    """
+    Emulation configs a blocked request is re-sent with, in order, until one is not
+    blocked or the list runs out. A request is sent at most once per entry on top of the
+    first attempt. Requests with streaming bodies cannot be re-sent.
+    """
+
+    block_detector: NotRequired[Callable[[StatusCode, HeaderMap], bool]]
+    """
+    Decides whether a response was blocked for `emulation_fallback`, from its status
+    and headers. Defaults to treating `403 Forbidden` as blocked.
+    """
+
     user_agent: NotRequired[str]
     """
     Sets the `User-Agent` header to be used by this client.
@@ -521,6 +1309,23 @@ class ClientConfig(TypedDict):
     Sets the default headers for every request.
     """
 
+    default_query: NotRequired[
+        Sequence[Tuple[str, str | int | float | bool]]
+        | Mapping[str, str | int | float | bool]
+    ]
+    """
+    Query parameters added to every request, e.g. an `api_key`. They come before the
+    request's own `query`, and a key the request sets replaces all of its default values.
+    Repeated keys given as a list of pairs are kept.
+    """
+
+    default_accept: NotRequired[Literal["json", "html", "text", "xml", "*"] | str]
+    """
+    Sets the default `Accept` header for every request, unless set in `headers`.
+
+    Accepts the aliases `json`, `html`, `text`, `xml` and `*`, or any media type.
+    """
+
     orig_headers: NotRequired[Sequence[str] | OrigHeaderMap]
     """
     Sets the original headers for every request.
@@ -531,9 +1336,33 @@ class ClientConfig(TypedDict):
     Enable or disable automatic setting of the `Referer` header.
     """
 
-    redirect: NotRequired[redirect.Policy]
+    redirect: NotRequired[redirect.Policy | Callable[[str, str, int], bool]]
     """
     Set a `redirect.Policy` for this client.
+
+    A callable `(url, previous_url, status) -> bool` is also accepted and is asked before
+    each redirect is followed; returning `False` stops and returns the 3xx response,
+    and raising fails with a `RedirectError` whose `__cause__` is the raised exception.
+    The request waits for the callable at every hop, so it must be fast and must not
+    block on I/O.
+    """
+
+    max_redirects_per_host: NotRequired[int]
+    """
+    Follow redirects (up to 10 in total), but fail once a single host has been redirected
+    to more than this many times. Shortcut for
+    `redirect=redirect.Policy.limited(max_per_host=...)`; cannot be combined with `redirect`.
+    """
+
+    redirect_retries: NotRequired[int]
+    """
+    Retry a redirect hop this many times when connecting to its target fails, resuming the
+    chain from that hop instead of failing the request. This is separate from `max_retries`,
+    which re-sends the whole request. Applies to redirects followed through
+    `redirect.Policy.limited` or `redirect.Policy.custom` (including `max_redirects_per_host`),
+    and requests with streaming bodies are not retried. The resumed request carries the
+    method and body changes of every hop before it, redirect limits count the whole chain,
+    and `Response.history` lists every hop.
     """
 
     raise_for_status: NotRequired[bool]
@@ -541,6 +1370,61 @@ class ClientConfig(TypedDict):
     Enable or disable automatic raising of exceptions for HTTP status codes.
     """
 
+    max_retries: NotRequired[int]
+    """
+    Retry failed requests up to this many times. Connection errors and responses with a
+    status in `retry_on_status` are retried, honoring `Retry-After`, and the overall
+    `timeout` bounds the retries. A request's own `retry` policy replaces this one, and
+    its `max_retries`, `retry_backoff` and `retry_on_status` override the matching options.
+    """
+
+    retry_backoff: NotRequired[datetime.timedelta]
+    """
+    The delay before the first retry, doubled for each further one (default 500ms).
+    """
+
+    retry_on_status: NotRequired[Sequence[int]]
+    """
+    The response statuses that are retried (default 429, 502, 503 and 504).
+    """
+
+    retry_methods: NotRequired[Sequence[Method]]
+    """
+    The methods that are retried. Only idempotent methods (GET, HEAD, PUT, DELETE, OPTIONS
+    and TRACE) are retried by default.
+    """
+
+    track_connections: NotRequired[bool]
+    """
+    Count requests served by reused pooled connections versus new connections, reported by
+    `Client.connection_stats()`. The counts are approximate: a connection is identified by
+    its local and remote addresses, and forgotten once idle for longer than
+    `pool_idle_timeout`, so a response over a recently seen pair is counted as reuse.
+    """
+
+    max_body_size: NotRequired[int]
+    """
+    The largest response body, in bytes, that may be read, after decompression. A larger
+    `Content-Length` raises `BodyTooLarge` as soon as the response arrives, and reading
+    raises it once more bytes than that have been received, whether the body is buffered
+    or streamed. Unlimited by default.
+    """
+
+    auto_buffer_limit: NotRequired[int]
+    """
+    Read response bodies whose `Content-Length` is at most this many bytes into memory as
+    soon as the response arrives, so they can be reused by `text()`, `json()`, `stream()`
+    and friends. Bodies above the limit are never cached: the first read still works, but
+    the body cannot be read again.
+    """
+
+    offload_decode: NotRequired[bool]
+    """
+    Parse response bodies in `text()` and `json()` on a blocking thread pool rather than
+    the async runtime's worker threads, so a large parse does not stall other in-flight
+    requests. Decompression still happens while the body is read. Defaults to `False`.
+    """
+
     cookie_store: NotRequired[bool]
     """
     Enable a persistent cookie store for the client.
@@ -640,6 +1524,62 @@ class ClientConfig(TypedDict):
     Sets the maximum number of connections in the pool.
     """
 
+    allowed_hosts: NotRequired[Sequence[str]]
+    """
+    Only send requests to hosts matching one of these patterns; others raise
+    `HostNotAllowed` before connecting. Entries are exact hosts (`example.com`),
+    subdomain wildcards (`*.example.com`), IP addresses, or CIDR ranges (`10.0.0.0/8`,
+    matched against IP literal hosts only). Every redirect target is checked as well.
+    """
+
+    block_private_networks: NotRequired[bool]
+    """
+    Refuse to connect to loopback, private (RFC 1918 / RFC 4193), link-local (including the
+    `169.254.169.254` metadata endpoint), and unspecified addresses, raising `HostNotAllowed`.
+    Hostnames are checked on their resolved addresses, so DNS rebinding and redirects are
+    covered too. Addresses pinned via `dns_options` are not checked. A proxy resolves the
+    target itself, beyond this check, so combining it with `proxies` or a request's `proxy`
+    raises `ValueError`, and proxies from the environment are ignored.
+    """
+
+    raw_request: NotRequired[bool]
+    """
+    Send only the headers set on the request, for protocol testing and replaying captured
+    requests. Default headers (including emulation headers such as `Accept`) and
+    `Accept-Encoding` are not added; per-request `default_headers` and compression options
+    still opt back in. HTTP/1.1 requests must set `Host` themselves, otherwise `ValueError`
+    is raised. `Content-Length` is still sent for bodies of known size, since HTTP requires
+    either it or chunked encoding.
+    """
+
+    auto_compress_request: NotRequired[bool]
+    """
+    Compress request bodies of known size (`body` bytes, text, form or JSON, `json`, `form`
+    and buffered streams) once they reach `auto_compress_threshold`, setting
+    `Content-Encoding`. Bodies that would not shrink, streamed bodies, and requests that set
+    `Content-Encoding` themselves are sent as is.
+    """
+
+    auto_compress_threshold: NotRequired[int]
+    """
+    The smallest request body, in bytes, compressed by `auto_compress_request`.
+    Defaults to 1024.
+    """
+
+    auto_compress_algorithm: NotRequired[Literal["gzip", "zstd"]]
+    """
+    The algorithm used by `auto_compress_request`. Defaults to `"gzip"`.
+    """
+
+    single_connection: NotRequired[bool]
+    """
+    Keep at most one pooled connection, e.g. to test connection-scoped server state.
+    Requests sent one after another, each reading or closing its response first, all reuse
+    that connection. Requests are never held back: one sent while the connection is still
+    busy, e.g. with an unread response, dials a connection of its own that is closed
+    instead of pooled. Cannot be combined with `pool_max_size` greater than 1.
+    """
+
     # ======== HTTP options ========
 
     http1_only: NotRequired[bool]
@@ -650,6 +1590,8 @@ class ClientConfig(TypedDict):
     http2_only: NotRequired[bool]
     """
     Only use HTTP/2.
+
+    Mutually exclusive with `http1_only`; setting both raises `ValueError`.
     """
 
     https_only: NotRequired[bool]
@@ -669,9 +1611,10 @@ class ClientConfig(TypedDict):
 
     # ======== TLS options ========
 
-    tls_verify: NotRequired[bool | Path | CertStore]
+    tls_verify: NotRequired[bool | bytes | Path | CertStore]
     """
-    Sets whether to verify TLS certificates.
+    Sets whether to verify TLS certificates, or the root certificates to verify them
+    against: PEM or DER `bytes`, a PEM file path, or a `CertStore`.
     """
 
     tls_verify_hostname: NotRequired[bool]
@@ -681,12 +1624,22 @@ class ClientConfig(TypedDict):
 
     tls_identity: NotRequired[Identity]
     """
-    Represents a private key and X509 cert as a client certificate.
+    The client certificate presented to servers that ask for one, built with
+    `Identity.from_pkcs12` or `Identity.from_pem`. Combine it with `tls_verify` set to
+    the server's CA for mutual TLS. A certificate and key that do not belong together make
+    building the client fail.
+    """
+
+    identity: NotRequired[Identity]
+    """
+    An alias of `tls_identity`, which cannot be set along with it.
     """
 
-    tls_keylog: NotRequired[KeyLog]
+    tls_keylog: NotRequired[KeyLog | Path | str | bool]
     """
-    Key logging policy (environment or file).
+    Write TLS session keys in the NSS key log format, so tools like Wireshark can decrypt
+    captured traffic. Takes a `KeyLog`, a file path, or `True` to use the file named by the
+    `SSLKEYLOGFILE` environment variable.
     """
 
     tls_info: NotRequired[bool]
@@ -709,6 +1662,26 @@ class ClientConfig(TypedDict):
     Sets the TLS options.
     """
 
+    tls_cipher_list: NotRequired[str]
+    """
+    The cipher suites to offer, in OpenSSL cipher list format, e.g.
+    `"ECDHE-ECDSA-AES128-GCM-SHA256:ECDHE-RSA-AES128-GCM-SHA256"`.
+    Configures TLS directly, so it cannot be combined with `emulation` or `tls_options`.
+    """
+
+    tls_curves: NotRequired[str]
+    """
+    The key exchange groups to offer, colon-separated, e.g. `"X25519:P-256"`.
+    Cannot be combined with `emulation` or `tls_options`.
+    """
+
+    tls_sigalgs: NotRequired[str]
+    """
+    The signature algorithms to offer, colon-separated, e.g.
+    `"ecdsa_secp256r1_sha256:rsa_pss_rsae_sha256"`.
+    Cannot be combined with `emulation` or `tls_options`.
+    """
+
     # ======== Network options ========
 
     no_proxy: NotRequired[bool]
@@ -723,6 +1696,12 @@ class ClientConfig(TypedDict):
     The proxies to use for requests.
     """
 
+    proxy_seed: NotRequired[int]
+    """
+    Seed the random choice of a proxy when a request gives a list of them, so the
+    sequence of proxies picked is the same on every run.
+    """
+
     local_address: NotRequired[IPv4Address | IPv6Address]
     """
     Bind to a local IP Address.
@@ -761,6 +1740,42 @@ class ClientConfig(TypedDict):
 
     dns_options: NotRequired[ResolverOptions]
 
+    dns: NotRequired[Literal["system", "hickory", "ipv4", "ipv6"] | str | DnsOverHttps]
+    """
+    Where to resolve host names:
+
+    - `"hickory"` (the default) resolves with hickory, reading the system configuration.
+    - `"ipv4"` and `"ipv6"` do the same but look up only one address family.
+    - `"system"` goes through the operating system resolver (`getaddrinfo`), honoring
+      `/etc/hosts` and other NSS sources.
+    - An `https://` URL such as `"https://cloudflare-dns.com/dns-query"`, or a
+      `DnsOverHttps` with bootstrap addresses, queries that DNS-over-HTTPS endpoint.
+
+    The lookup strategy of `dns_options` applies to all but `"ipv4"` and `"ipv6"`. A
+    DNS-over-HTTPS endpoint is contacted on the first request, so an unreachable one raises
+    `ConnectionError` then. Behind an HTTP or `socks5h://` proxy, target hosts are resolved
+    by the proxy, so only the proxy's own host name goes through this resolver.
+    """
+
+    resolve: NotRequired[Mapping[str, str | IPv4Address | IPv6Address]]
+    """
+    Pin host names to IP addresses without a DNS lookup, like curl's `--resolve`, e.g.
+    `{"example.com": "127.0.0.1", "api.example.com:443": "10.0.0.5"}`. A `host:port` key
+    only applies to requests whose URL uses that port and wins over a plain `host` key.
+    The `Host` header and TLS server name still use the original host name. Pinned
+    addresses are not checked by `block_private_networks`.
+    """
+
+    dns_resolver: NotRequired[Callable[[str], Sequence[str | IPv4Address | IPv6Address]]]
+    """
+    Resolve host names with a callable instead of the built-in resolvers, e.g. for
+    split-horizon DNS, stubbing in tests or custom load balancing. It is called with the
+    host name and returns its addresses. Nothing is cached: the callable is invoked for
+    every new connection, on a worker thread. An exception it raises, or an empty result,
+    fails the request with `ConnectionError`. Cannot be combined with `dns`; `resolve` and
+    `block_private_networks` still apply.
+    """
+
     # ========= Compression options =========
 
     gzip: NotRequired[bool]
@@ -794,6 +1809,13 @@ class Request(TypedDict):
     The headers to use for the request.
     """
 
+    accept: NotRequired[Literal["json", "html", "text", "xml", "*"] | str]
+    """
+    The `Accept` header to use for the request, unless set in `headers`.
+
+    Accepts the aliases `json`, `html`, `text`, `xml` and `*`, or any media type.
+    """
+
     orig_headers: NotRequired[Sequence[str] | OrigHeaderMap]
     """
     The original headers to use for the request.
@@ -809,9 +1831,10 @@ class Request(TypedDict):
     The cookies to use for the request.
     """
 
-    proxy: NotRequired[Proxy]
+    proxy: NotRequired[Proxy | Sequence[Proxy]]
     """
-    The proxy to use for the request.
+    The proxy to use for the request. Given a list, one of them is picked uniformly at
+    random for each call, which is reproducible with the client's `proxy_seed`.
     """
 
     local_address: NotRequired[IPv4Address | IPv6Address]
@@ -848,9 +1871,16 @@ class Request(TypedDict):
     [man-7p-ip]: https://docs.oracle.com/cd/E86824_01/html/E54777/ip-7p.html
     """
 
-    timeout: NotRequired[datetime.timedelta]
+    timeout: NotRequired[
+        datetime.timedelta | Tuple[datetime.timedelta, datetime.timedelta]
+    ]
     """
     The timeout to use for the request.
+
+    A `(connect, read)` tuple applies `connect` to opening a new connection and sets
+    `read_timeout` to `read`; it cannot be combined with `read_timeout`. A client with that
+    connect timeout is derived from the same options on first use and keeps its own
+    connection pool.
     """
 
     read_timeout: NotRequired[datetime.timedelta]
@@ -863,9 +1893,54 @@ class Request(TypedDict):
     The HTTP version to use for the request.
     """
 
-    redirect: NotRequired[redirect.Policy]
+    stream_priority: NotRequired[StreamDependency]
+    """
+    The HTTP/2 stream dependency (weight, parent stream, exclusive flag) sent in the
+    HEADERS frame of this request. Only for clients without an HTTP/2 profile: when the
+    client's or host's emulation, or the client's `http2_options`, sets one, `ValueError`
+    is raised rather than replacing its SETTINGS, pseudo-header order and window sizes.
+    Cannot be combined with a per-request `emulation`.
+    """
+
+    alpn: NotRequired[Sequence[AlpnProtocol]]
+    """
+    The ALPN protocols to offer for this request, in order. Only for clients without a
+    TLS profile: when the client's or host's emulation, or the client's `tls_options`,
+    sets one, `ValueError` is raised rather than replacing the whole profile. Cannot be
+    combined with a per-request `emulation`.
+    """
+
+    alps: NotRequired[Sequence[AlpsProtocol]]
+    """
+    The ALPS protocols to offer for this request, in order. Like `alpn`, only for clients
+    without a TLS profile, and cannot be combined with a per-request `emulation`.
+    """
+
+    grease_enabled: NotRequired[bool]
+    """
+    Toggle GREASE values in the TLS handshake for this request. Like `alpn`, only for
+    clients without a TLS profile, and cannot be combined with a per-request `emulation`.
+    """
+
+    enable_ech_grease: NotRequired[bool]
     """
-    The redirect policy.
+    Toggle the ECH GREASE extension in the TLS handshake for this request. Like `alpn`,
+    only for clients without a TLS profile, and cannot be combined with a per-request
+    `emulation`.
+    """
+
+    tls_verify: NotRequired[bool]
+    """
+    Whether to verify the server certificate for this request, overriding the client's
+    `tls_verify`. A client with the other setting is derived from the same options on first
+    use and keeps its own connection pool.
+    """
+
+    redirect: NotRequired[redirect.Policy | Callable[[str, str, int], bool]]
+    """
+    The redirect policy, or a callable `(url, previous_url, status) -> bool` deciding
+    whether to follow each redirect. The callable runs synchronously before every hop,
+    so it must be fast.
     """
 
     cookie_provider: NotRequired[Jar]
@@ -873,6 +1948,13 @@ class Request(TypedDict):
     Set cookie provider for the request.
     """
 
+    persist_cookies: NotRequired[bool]
+    """
+    Also store the `cookies` of this request in the cookie provider, so later requests
+    to the same site send them too. Uses the request `cookie_provider` if given, otherwise
+    the client's cookie store. Raises `ValueError` if neither is available.
+    """
+
     gzip: NotRequired[bool]
     """
     Sets gzip as an accepted encoding.
@@ -893,6 +1975,13 @@ class Request(TypedDict):
     Sets zstd as an accepted encoding.
     """
 
+    decompress: NotRequired[bool]
+    """
+    Set to `False` to turn off decompression and keep the response body as received,
+    for `Response.raw_bytes()`. The built-in encodings are then no longer advertised;
+    set an `Accept-Encoding` header to still ask for a compressed body.
+    """
+
     auth: NotRequired[str]
     """
     The authentication to use for the request.
@@ -926,7 +2015,14 @@ class Request(TypedDict):
 
     json: NotRequired[Any]
     """
-    The JSON body to use for the request.
+    The JSON body to use for the request. Dict keys are sent in insertion order.
+    """
+
+    json_dumps: NotRequired[Callable[[Any], str | bytes]]
+    """
+    Serialize `json` with this function instead, e.g. `orjson.dumps` or a `json.dumps`
+    with a `default` hook, for values the built-in encoder does not handle. The result is
+    sent as the body with `Content-Type: application/json` unless a content type is set.
     """
 
     body: NotRequired[
@@ -943,11 +2039,83 @@ class Request(TypedDict):
     The body to use for the request.
     """
 
+    stream_mode: NotRequired[Literal["chunked", "buffered"]]
+    """
+    How a generator `body` is sent. `"chunked"` (the default) streams chunks as they are
+    produced; `"buffered"` drains the generator first and sends a `Content-Length`, for
+    servers and CDNs that reject chunked uploads.
+    """
+
     multipart: NotRequired[Multipart]
     """
     The multipart form to use for the request.
     """
 
+    return_sent: NotRequired[bool]
+    """
+    Attach the request as built to the response as `Response.sent_request`. Headers the
+    client adds on dispatch are not included, see `SentRequest`.
+    """
+
+    decode_fallback: NotRequired[bool]
+    """
+    Decode the body once it has been read instead of while it streams in, and return the
+    bytes as received if they cannot be decoded. The request is sent only once. A
+    compressed body is buffered eagerly; check `Response.decode_fallback` to see which
+    path was taken.
+    """
+
+    max_body_size: NotRequired[int]
+    """
+    The largest response body, in bytes, that may be read, overriding the client's
+    `max_body_size`.
+    """
+
+    validator: NotRequired[Callable[[Any], Any]]
+    """
+    A callable handed the parsed JSON body whenever `Response.json()` is called. If it
+    raises, `json()` raises `ValidationError` with the original exception as its cause.
+    """
+
+    retry: NotRequired["Retry"]
+    """
+    The retry policy for this request, replacing the client's policy and covering any
+    method. Requests with streaming bodies are sent once.
+
+    `max_retries`, `retry_backoff` and `retry_on_status` are shorthands that override the
+    matching fields of this policy, or of the client's policy when none is given (or of the
+    defaults, 2 retries, 500ms and 429, 502, 503 and 504, without either).
+    """
+
+    max_retries: NotRequired[int]
+    """
+    Retry the request up to this many times on connection errors and on the statuses in
+    `retry_on_status`, honoring `Retry-After`. Like `retry`, this opts the request in
+    whatever its method.
+    """
+
+    retry_backoff: NotRequired[datetime.timedelta]
+    """
+    The delay before the first retry, doubled for each further one. Only the methods the
+    client's policy covers (idempotent ones by default) are retried unless `retry` or
+    `max_retries` is given too.
+    """
+
+    retry_on_status: NotRequired[Sequence[int]]
+    """
+    The response statuses that are retried, e.g. `[429, 503]`. Only the methods the
+    client's policy covers (idempotent ones by default) are retried unless `retry` or
+    `max_retries` is given too.
+    """
+
+    resolve: NotRequired[Mapping[str, str | IPv4Address | IPv6Address]]
+    """
+    Pin host names to IP addresses for this request, like the client's `resolve` option,
+    whose entries it takes precedence over. The `Host` header and TLS server name still use
+    the original host name. Requests setting it never share pooled connections, so each
+    opens a new connection.
+    """
+
 
 class WebSocketRequest(TypedDict):
     emulation: NotRequired[emulation.Emulation | emulation.Profile]
@@ -1134,7 +2302,9 @@ class Client:
         r"""
         Closes the client and any associated resources.
 
-        After calling this method, the client should not be used to make further requests.
+        Requests in flight are cancelled and the connection pool is released. Any request
+        made after this raises a `RuntimeError`. Leaving an `async with` block closes
+        the client too.
 
         Examples:
 
@@ -1155,6 +2325,70 @@ class Client:
         """
         ...
 
+    def emulation_info(self) -> EmulationInfo:
+        r"""
+        Returns a summary of the emulation profile configured on the client, without
+        making a request.
+        """
+        ...
+
+    def connection_stats(self) -> ConnectionStats | None:
+        r"""
+        Returns how many requests were served by a reused pooled connection versus a new
+        connection, or `None` unless the client was created with `track_connections=True`.
+
+        A high share of new connections points at pool or keep-alive misconfiguration.
+        """
+        ...
+
+    async def pipe(
+        self,
+        source: Response,
+        method: Method,
+        url: str,
+        **kwargs: Unpack[Request],
+    ) -> Response:
+        r"""
+        Sends a request whose body is streamed straight from the body of `source`, without
+        buffering it, e.g. to relay a download to another server.
+
+        The source body is consumed and sent chunked; its `Content-Type` is forwarded unless
+        the request sets one. The request cannot also set `body`, `json`, `form` or
+        `multipart`.
+
+        # Examples
+
+        ```python
+        client = wreq.Client()
+        src = await client.get("https://httpbin.io/bytes/1048576")
+        resp = await client.pipe(src, wreq.Method.POST, "https://httpbin.io/post")
+        ```
+        """
+        ...
+
+    def to_curl(
+        self,
+        method: Method,
+        url: str,
+        **kwargs: Unpack[Request],
+    ) -> str:
+        r"""
+        Builds the request without sending it and returns the equivalent `curl` command.
+
+        The command covers the method, the headers and body set on the request, the
+        request's (or else the client's first) proxy, and `--insecure` when certificate
+        verification is disabled. Headers the client adds when sending, such as emulation
+        or default headers, are not included. Streaming bodies cannot be rendered.
+
+        # Examples
+
+        ```python
+        client = wreq.Client()
+        print(client.to_curl(wreq.Method.POST, "https://httpbin.io/post", json={"a": 1}))
+        ```
+        """
+        ...
+
     async def request(
         self,
         method: Method,
@@ -1208,6 +2442,35 @@ class Client:
         """
         ...
 
+    def sse(
+        self,
+        url: str,
+        **kwargs: Unpack[Request],
+    ) -> EventSource:
+        r"""
+        Opens a stream of server-sent events (`text/event-stream`), connecting on the first
+        iteration.
+
+        `Accept: text/event-stream` and `Cache-Control: no-cache` are sent unless the
+        request sets them. An error status or a failure to connect the first time raises;
+        connection failures while reconnecting are retried after the reconnection delay.
+
+        # Examples
+
+        ```python
+        import wreq
+        import asyncio
+
+        async def main():
+            client = wreq.Client()
+            async for event in client.sse("https://example.com/events"):
+                print(event.event, event.id, event.data)
+
+        asyncio.run(main())
+        ```
+        """
+        ...
+
     async def trace(
         self,
         url: str,
@@ -1416,11 +2679,15 @@ class Client:
 
 async def delete(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1440,11 +2707,15 @@ async def delete(
 
 async def get(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1464,11 +2735,15 @@ async def get(
 
 async def head(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1487,11 +2762,15 @@ async def head(
 
 async def options(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1510,11 +2789,15 @@ async def options(
 
 async def patch(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1534,11 +2817,15 @@ async def patch(
 
 async def post(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1558,11 +2845,15 @@ async def post(
 
 async def put(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1583,6 +2874,8 @@ async def put(
 async def request(
     method: Method,
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
@@ -1614,11 +2907,15 @@ async def request(
 
 async def trace(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[Request],
 ) -> Response:
     r"""
     Shortcut method to quickly make a request.
 
+    Pass `client=` to reuse an existing client's pool and emulation instead of a default client.
+
     # Examples
 
     ```python
@@ -1637,6 +2934,8 @@ async def trace(
 
 async def websocket(
     url: str,
+    *,
+    client: Client | None = None,
     **kwargs: Unpack[WebSocketRequest],
 ) -> WebSocket:
     r"""
@@ -24,6 +24,11 @@ use wreq::header::{HeaderName, HeaderValue, OrigHeaderName};
 /// [`PyBuffer`] enables zero-copy conversion of Rust [`Bytes`] to Python bytes.
 pub struct PyBuffer(BufferView);
 
+/// [`PyMemoryView`] exposes Rust [`Bytes`] to Python as a read-only `memoryview` without copying.
+///
+/// The view keeps the underlying buffer alive for as long as it is referenced.
+pub struct PyMemoryView(BufferView);
+
 #[pyclass(frozen, skip_from_py_object)]
 struct BufferView(Bytes);
 
@@ -44,6 +49,30 @@ impl<'a> IntoPyObject<'a> for PyBuffer {
     }
 }
 
+// ===== PyMemoryView =====
+
+impl<'a> IntoPyObject<'a> for PyMemoryView {
+    type Target = PyAny;
+    type Output = Bound<'a, Self::Target>;
+    type Error = PyErr;
+
+    #[inline(always)]
+    fn into_pyobject(self, py: Python<'a>) -> Result<Self::Output, Self::Error> {
+        let buffer = self.0.into_pyobject(py)?;
+        #[allow(unsafe_code)]
+        unsafe {
+            Bound::from_owned_ptr_or_err(py, ffi::PyMemoryView_FromObject(buffer.as_ptr()))
+        }
+    }
+}
+
+impl From<Bytes> for PyMemoryView {
+    #[inline]
+    fn from(value: Bytes) -> Self {
+        PyMemoryView(BufferView(value))
+    }
+}
+
 impl From<Vec<u8>> for PyBuffer {
     #[inline]
     fn from(value: Vec<u8>) -> Self {
@@ -3,38 +3,53 @@ pub mod nogil;
 pub mod req;
 pub mod resp;
 
+mod curl;
+pub mod guard;
 mod param;
 mod query;
+pub mod retry;
+pub mod stats;
 
 use std::{
+    borrow::Cow,
+    collections::HashMap,
     net::{IpAddr, Ipv4Addr, Ipv6Addr},
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
-use pyo3::{IntoPyObjectExt, coroutine::CancelHandle, prelude::*, pybacked::PyBackedStr};
+use arc_swap::ArcSwapOption;
+use pyo3::{
+    IntoPyObjectExt, coroutine::CancelHandle, exceptions::PyValueError, prelude::*,
+    pybacked::PyBackedStr, types::PyDict,
+};
 use req::{Request, WebSocketRequest};
 use tokio_util::sync::CancellationToken;
 use wreq::tls::trust::CertStore;
 
 use self::{
+    body::{ContentCoding, RequestCompression},
+    guard::AllowedHosts,
     nogil::NoGIL,
-    req::{execute_request, execute_websocket_request},
-    resp::{BlockingResponse, BlockingWebSocket, Response, WebSocket},
+    query::Query,
+    req::{execute_request, execute_websocket_request, render_curl},
+    resp::{BlockingResponse, BlockingWebSocket, EventSource, Response, WebSocket, wire::Codings},
+    retry::Retry,
+    stats::{ConnectionStats, ConnectionTracker},
 };
 use crate::{
     cookie::Jar,
-    dns::{HickoryDnsResolver, LookupIpStrategy, ResolverOptions},
-    emulate::EmulationLike,
+    dns::{DnsMode, HickoryDnsResolver, HostOverrides, LookupIpStrategy, ResolverOptions},
+    emulate::{EmulationFallback, EmulationInfo, EmulationLike, HostEmulation},
     error::Error,
     extractor::Extractor,
-    header::{HeaderMap, OrigHeaderMap},
+    header::{Accept, HeaderMap, OrigHeaderMap},
     http::Method,
     http1::Http1Options,
     http2::Http2Options,
     proxy::Proxy,
     redirect,
-    tls::{Identity, KeyLog, TlsOptions, TlsVerify, TlsVersion},
+    tls::{Identity, KeyLogLike, TlsOptions, TlsVerify, TlsVersion},
 };
 
 /// A IP socket address.
@@ -60,20 +75,64 @@ impl_print_str!(Display, SocketAddr);
 /// A builder for `Client`.
 #[derive(Default)]
 struct Builder {
+    /// The base URL that relative request URLs are resolved against.
+    base_url: Option<PyBackedStr>,
     /// The Emulation settings for the client.
     emulation: Option<EmulationLike>,
+    /// The Emulation settings keyed by host pattern, overriding `emulation` per request.
+    host_emulation: Option<HostEmulation>,
+    /// The Emulation settings a blocked request is re-sent with, in order.
+    emulation_fallback: Option<Vec<EmulationLike>>,
+    /// Decides whether a response was blocked, from its status and headers.
+    block_detector: Option<Py<PyAny>>,
+    /// The hosts requests are allowed to be sent to.
+    allowed_hosts: Option<AllowedHosts>,
+    /// Whether to refuse connecting to loopback, private and link-local addresses.
+    block_private_networks: Option<bool>,
+    /// Whether to send only the headers set on the request itself.
+    raw_request: Option<bool>,
+    /// The largest response body, in bytes, that is buffered up front and kept for reuse.
+    auto_buffer_limit: Option<u64>,
+    /// The largest response body, in bytes, that may be read.
+    max_body_size: Option<u64>,
+    /// Whether to parse response bodies on the blocking thread pool.
+    offload_decode: Option<bool>,
+    /// Whether to compress request bodies of known size.
+    auto_compress_request: Option<bool>,
+    /// The smallest request body, in bytes, that is compressed.
+    auto_compress_threshold: Option<usize>,
+    /// The algorithm used to compress request bodies.
+    auto_compress_algorithm: Option<ContentCoding>,
     /// The user agent to use for the client.
     user_agent: Option<PyBackedStr>,
     /// The headers to use for the client.
     headers: Option<HeaderMap>,
+    /// The query parameters added to every request.
+    default_query: Option<Query>,
+    /// The default `Accept` header, unless set explicitly in `headers`.
+    default_accept: Option<Accept>,
     /// The original headers to use for the client.
     orig_headers: Option<OrigHeaderMap>,
     /// Whether to use referer.
     referer: Option<bool>,
     /// Whether to redirect policy.
     redirect: Option<redirect::Policy>,
+    /// The maximum number of redirects to a single host, following up to 10 redirects in total.
+    max_redirects_per_host: Option<usize>,
+    /// The number of times a redirect hop is retried after a connection failure.
+    redirect_retries: Option<u32>,
     /// Whether to raise for status.
     raise_for_status: Option<bool>,
+    /// The number of times a failed idempotent request is retried.
+    max_retries: Option<u32>,
+    /// The delay before the first retry, doubled for each further one.
+    retry_backoff: Option<Duration>,
+    /// The response statuses that are retried.
+    retry_on_status: Option<Vec<u16>>,
+    /// The methods that are retried, instead of the idempotent ones.
+    retry_methods: Option<Vec<Method>>,
+    /// Whether to count requests served by reused versus new connections.
+    track_connections: Option<bool>,
 
     // ========= Cookie options =========
     /// Whether to use cookie store.
@@ -110,6 +169,8 @@ struct Builder {
     pool_max_idle_per_host: Option<usize>,
     /// Sets the maximum number of connections in the pool.
     pool_max_size: Option<usize>,
+    /// Route every request through a single pooled connection.
+    single_connection: Option<bool>,
 
     // ========= Protocol options =========
     /// Whether to use the HTTP/1 protocol only.
@@ -128,10 +189,12 @@ struct Builder {
     tls_verify: Option<TlsVerify>,
     /// Whether to verify the hostname in the SSL certificate.
     tls_verify_hostname: Option<bool>,
-    /// Represents a private key and X509 cert as a client certificate.
+    /// The client certificate for mutual TLS, presented alongside `tls_verify`.
     tls_identity: Option<Identity>,
-    /// Key logging policy for TLS session keys.
-    tls_keylog: Option<KeyLog>,
+    /// An alias of `tls_identity`.
+    identity: Option<Identity>,
+    /// Key logging policy given as a `KeyLog`, a file path, or `True` for `SSLKEYLOGFILE`.
+    tls_keylog: Option<KeyLogLike>,
     /// Add TLS information as `TlsInfo` extension to responses.
     tls_info: Option<bool>,
     /// The minimum TLS version to use for the client.
@@ -140,12 +203,20 @@ struct Builder {
     tls_max_version: Option<TlsVersion>,
     /// Sets the TLS options for the client.
     tls_options: Option<TlsOptions>,
+    /// The cipher suites to offer, in OpenSSL cipher list format.
+    tls_cipher_list: Option<String>,
+    /// The key exchange groups to offer, colon-separated.
+    tls_curves: Option<String>,
+    /// The signature algorithms to offer, colon-separated.
+    tls_sigalgs: Option<String>,
 
     // ========= Network options =========
     /// Whether to disable the proxy for the client.
     no_proxy: Option<bool>,
     /// The proxies to use for the client.
     proxies: Option<Vec<Proxy>>,
+    /// The seed for picking a request's proxy from a pool.
+    proxy_seed: Option<u64>,
     /// Bind to a local IP Address.
     local_address: Option<IpAddr>,
     /// Bind to local IP Addresses (IPv4, IPv6).
@@ -155,6 +226,12 @@ struct Builder {
 
     // ========= DNS options =========
     dns_options: Option<ResolverOptions>,
+    /// Where to resolve host names: the operating system, hickory or a DNS-over-HTTPS endpoint.
+    dns: Option<DnsMode>,
+    /// The addresses pinned for host names, optionally scoped to a port.
+    resolve: Option<HostOverrides>,
+    /// A callable returning the addresses of a host name, replacing the built-in resolvers.
+    dns_resolver: Option<Py<PyAny>>,
 
     // ========= Compression options =========
     /// Sets gzip as an accepted encoding.
@@ -172,13 +249,35 @@ impl FromPyObject<'_, '_> for Builder {
 
     fn extract(ob: Borrowed<PyAny>) -> PyResult<Self> {
         let mut builder = Self::default();
+        extract_option!(ob, builder, base_url);
         extract_option!(ob, builder, emulation);
+        extract_option!(ob, builder, host_emulation);
+        extract_option!(ob, builder, emulation_fallback);
+        extract_option!(ob, builder, block_detector);
+        extract_option!(ob, builder, allowed_hosts);
+        extract_option!(ob, builder, block_private_networks);
+        extract_option!(ob, builder, raw_request);
+        extract_option!(ob, builder, auto_buffer_limit);
+        extract_option!(ob, builder, max_body_size);
+        extract_option!(ob, builder, offload_decode);
+        extract_option!(ob, builder, auto_compress_request);
+        extract_option!(ob, builder, auto_compress_threshold);
+        extract_option!(ob, builder, auto_compress_algorithm);
         extract_option!(ob, builder, user_agent);
         extract_option!(ob, builder, headers);
+        extract_option!(ob, builder, default_query);
+        extract_option!(ob, builder, default_accept);
         extract_option!(ob, builder, orig_headers);
         extract_option!(ob, builder, referer);
         extract_option!(ob, builder, redirect);
+        extract_option!(ob, builder, max_redirects_per_host);
+        extract_option!(ob, builder, redirect_retries);
         extract_option!(ob, builder, raise_for_status);
+        extract_option!(ob, builder, max_retries);
+        extract_option!(ob, builder, retry_backoff);
+        extract_option!(ob, builder, retry_on_status);
+        extract_option!(ob, builder, retry_methods);
+        extract_option!(ob, builder, track_connections);
 
         extract_option!(ob, builder, cookie_store);
         extract_option!(ob, builder, cookie_provider);
@@ -197,9 +296,11 @@ impl FromPyObject<'_, '_> for Builder {
         extract_option!(ob, builder, pool_idle_timeout);
         extract_option!(ob, builder, pool_max_idle_per_host);
         extract_option!(ob, builder, pool_max_size);
+        extract_option!(ob, builder, single_connection);
 
         extract_option!(ob, builder, no_proxy);
         extract_option!(ob, builder, proxies);
+        extract_option!(ob, builder, proxy_seed);
         extract_option!(ob, builder, local_address);
         extract_option!(ob, builder, local_addresses);
         extract_option!(ob, builder, interface);
@@ -213,13 +314,20 @@ impl FromPyObject<'_, '_> for Builder {
         extract_option!(ob, builder, tls_verify);
         extract_option!(ob, builder, tls_verify_hostname);
         extract_option!(ob, builder, tls_identity);
+        extract_option!(ob, builder, identity);
         extract_option!(ob, builder, tls_keylog);
         extract_option!(ob, builder, tls_info);
         extract_option!(ob, builder, tls_min_version);
         extract_option!(ob, builder, tls_max_version);
         extract_option!(ob, builder, tls_options);
+        extract_option!(ob, builder, tls_cipher_list);
+        extract_option!(ob, builder, tls_curves);
+        extract_option!(ob, builder, tls_sigalgs);
 
         extract_option!(ob, builder, dns_options);
+        extract_option!(ob, builder, dns);
+        extract_option!(ob, builder, resolve);
+        extract_option!(ob, builder, dns_resolver);
 
         extract_option!(ob, builder, gzip);
         extract_option!(ob, builder, brotli);
@@ -233,9 +341,45 @@ impl FromPyObject<'_, '_> for Builder {
 #[derive(Default, Clone)]
 #[pyclass(subclass, frozen, skip_from_py_object)]
 pub struct Client {
-    inner: wreq::Client,
+    inner: Handle,
     cancel: CancellationToken,
     raise_for_status: bool,
+    host_emulation: Option<Arc<HostEmulation>>,
+    emulation_fallback: Option<Arc<EmulationFallback>>,
+    allowed_hosts: Option<Arc<AllowedHosts>>,
+    /// The base URL that relative request URLs are resolved against.
+    base_url: Option<Arc<url::Url>>,
+    /// The query parameters added to every request.
+    default_query: Option<Arc<Query>>,
+    block_private_networks: bool,
+    raw_request: bool,
+    /// The content codings response bodies are decoded from.
+    codings: Codings,
+    /// Whether the default headers or the emulation set `Accept-Encoding` themselves.
+    accept_encoding: bool,
+    auto_buffer_limit: Option<u64>,
+    max_body_size: Option<u64>,
+    offload_decode: bool,
+    emulation_info: EmulationInfo,
+    /// Whether the client sends a TLS profile, from its emulation or its TLS options.
+    tls_profile: bool,
+    /// Whether the client sends an HTTP/2 profile, from its emulation or its HTTP/2 options.
+    http2_profile: bool,
+    tls_insecure: bool,
+    proxy: Option<Proxy>,
+    /// Picks a request's proxy when it gives a pool of them.
+    proxy_rng: Arc<Mutex<fastrand::Rng>>,
+    request_compression: Option<Arc<RequestCompression>>,
+    connections: Option<Arc<ConnectionTracker>>,
+    retry: Option<Retry>,
+    /// The number of times a redirect hop is retried after a connection failure.
+    redirect_retries: u32,
+    /// The timeout for a whole request, bounding its retries too.
+    timeout: Option<Duration>,
+    /// The options the client was created with, used to derive variants of it.
+    config: Option<Arc<Py<PyDict>>>,
+    /// The clients derived for per-request options, each built on first use.
+    variants: Arc<Mutex<HashMap<Variant, Handle>>>,
 
     /// Get the cookie jar of the client.
     #[pyo3(get)]
@@ -247,22 +391,123 @@ pub struct Client {
 #[pyclass(name = "Client", subclass, frozen, skip_from_py_object)]
 pub struct BlockingClient(Client);
 
+/// The underlying client, shared by every clone of a [`Client`] and dropped when it is closed.
+#[derive(Clone)]
+struct Handle(Arc<ArcSwapOption<wreq::Client>>);
+
+/// The per-request options that are fixed per underlying client, so a request setting any
+/// of them is sent with a client derived for it.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash)]
+struct Variant {
+    /// Whether to verify certificates, if it differs from the client.
+    tls_verify: Option<bool>,
+    /// Whether to keep no idle connections.
+    unpooled: bool,
+    /// The connect timeout, if the request sets one.
+    connect_timeout: Option<Duration>,
+}
+
+// ====== Handle =====
+
+impl Handle {
+    fn new(client: wreq::Client) -> Handle {
+        Handle(Arc::new(ArcSwapOption::from_pointee(client)))
+    }
+
+    /// Get the underlying client, failing once it has been closed.
+    fn get(&self) -> Result<Arc<wreq::Client>, Error> {
+        self.0.load_full().ok_or(Error::ClientClosed)
+    }
+
+    fn close(&self) {
+        self.0.store(None);
+    }
+}
+
+impl Default for Handle {
+    fn default() -> Handle {
+        Handle::new(wreq::Client::default())
+    }
+}
+
 // ====== Client =====
 
-#[pymethods]
 impl Client {
-    /// Creates a new Client instance.
-    #[new]
-    #[pyo3(signature = (**kwds))]
-    fn new(py: Python, kwds: Option<Builder>) -> PyResult<Client> {
+    /// Build a client from the given options.
+    fn build(py: Python, kwds: Option<Builder>) -> PyResult<Client> {
         py.detach(|| {
             // Create the client builder.
             let mut builder = wreq::Client::builder();
             let mut cookie_jar: Option<Jar> = None;
             let mut raise_for_status = false;
+            let mut host_emulation = None;
+            let mut emulation_fallback = None;
+            let mut allowed_hosts = None;
+            let mut base_url = None;
+            let mut default_query = None;
+            let mut block_private_networks = false;
+            let mut raw_request = false;
+            let mut codings = Codings {
+                gzip: true,
+                brotli: true,
+                deflate: true,
+                zstd: true,
+            };
+            let mut accept_encoding = false;
+            let mut auto_buffer_limit = None;
+            let mut max_body_size = None;
+            let mut offload_decode = false;
+            let mut emulation_info = EmulationInfo::default();
+            let mut tls_profile = false;
+            let mut http2_profile = false;
+            let mut tls_insecure = false;
+            let mut proxy = None;
+            let mut proxy_rng = Arc::default();
+            let mut request_compression = None;
+            let mut connections = None;
+            let mut retry = None;
+            let mut redirect_retries = 0;
+            let mut timeout = None;
 
             if let Some(mut config) = kwds {
                 // Emulation options.
+                let direct_tls = config.tls_cipher_list.is_some()
+                    || config.tls_curves.is_some()
+                    || config.tls_sigalgs.is_some();
+                if direct_tls && (config.emulation.is_some() || config.tls_options.is_some()) {
+                    return Err(PyValueError::new_err(
+                        "`tls_cipher_list`, `tls_curves` and `tls_sigalgs` cannot be combined \
+                        with `emulation` or `tls_options`",
+                    ));
+                }
+
+                // Base URL options.
+                if let Some(url) = config.base_url.take() {
+                    let url = url::Url::parse(&url).map_err(Error::Url)?;
+                    if url.cannot_be_a_base() {
+                        return Err(
+                            Error::Url(url::ParseError::RelativeUrlWithCannotBeABaseBase).into(),
+                        );
+                    }
+                    if config.https_only.unwrap_or(false) && url.scheme() != "https" {
+                        return Err(PyValueError::new_err(
+                            "`base_url` must use https when `https_only` is set",
+                        ));
+                    }
+                    base_url = Some(Arc::new(url));
+                }
+                default_query = config.default_query.take().map(Arc::new);
+                emulation_info =
+                    EmulationInfo::new(config.emulation.as_ref(), config.tls_options.as_ref());
+                tls_profile = direct_tls
+                    || config.tls_options.is_some()
+                    || config.emulation.as_ref().is_some_and(EmulationLike::has_tls);
+                http2_profile = config.http2_options.is_some()
+                    || config.emulation.as_ref().is_some_and(EmulationLike::has_http2);
+                accept_encoding = config
+                    .emulation
+                    .as_ref()
+                    .is_some_and(EmulationLike::sets_accept_encoding);
                 apply_option!(set_if_some, builder, config.emulation, emulation);
 
                 // User agent options.
@@ -275,6 +520,13 @@ impl Client {
                 );
 
                 // Default headers options.
+                if let Some(accept) = config.default_accept.take() {
+                    accept.apply_default(&mut config.headers);
+                }
+                accept_encoding |= config
+                    .headers
+                    .as_ref()
+                    .is_some_and(|headers| headers.0.contains_key(http::header::ACCEPT_ENCODING));
                 apply_option!(set_if_some_inner, builder, config.headers, default_headers);
                 apply_option!(
                     set_if_some_inner,
@@ -285,7 +537,17 @@ impl Client {
 
                 // Allow redirects options.
                 apply_option!(set_if_some, builder, config.referer, referer);
+                if let Some(max_per_host) = config.max_redirects_per_host.take() {
+                    if config.redirect.is_some() {
+                        return Err(PyValueError::new_err(
+                            "`max_redirects_per_host` cannot be combined with `redirect`, \
+                             use `redirect.Policy.limited(max_per_host=...)` instead",
+                        ));
+                    }
+                    config.redirect = Some(redirect::Policy::limited(None, Some(max_per_host)));
+                }
                 apply_option!(set_if_some_inner, builder, config.redirect, redirect);
+                redirect_retries = config.redirect_retries.unwrap_or(0);
 
                 // Cookie options.
                 if let Some(jar) = config.cookie_provider.take() {
@@ -329,6 +591,7 @@ impl Client {
                 );
 
                 // Timeout options.
+                timeout = config.timeout;
                 apply_option!(set_if_some, builder, config.timeout, timeout);
                 apply_option!(
                     set_if_some,
@@ -339,6 +602,9 @@ impl Client {
                 apply_option!(set_if_some, builder, config.read_timeout, read_timeout);
 
                 // Pool options.
+                if config.track_connections.unwrap_or(false) {
+                    connections = Some(Arc::new(ConnectionTracker::new(config.pool_idle_timeout)));
+                }
                 apply_option!(
                     set_if_some,
                     builder,
@@ -351,9 +617,25 @@ impl Client {
                     config.pool_max_idle_per_host,
                     pool_max_idle_per_host
                 );
+                if config.single_connection.unwrap_or_default() {
+                    if config.pool_max_size.is_some_and(|size| size > 1) {
+                        return Err(PyValueError::new_err(
+                            "`single_connection` cannot be combined with `pool_max_size` > 1",
+                        ));
+                    }
+                    // Requests are not held back: one sent while the pooled connection is busy
+                    // dials its own, which is closed instead of pooled once it is done.
+                    config.pool_max_size = Some(1);
+                    builder = builder.pool_max_idle_per_host(1);
+                }
                 apply_option!(set_if_some, builder, config.pool_max_size, pool_max_size);
 
                 // Protocol options.
+                if config.http1_only.unwrap_or_default() && config.http2_only.unwrap_or_default() {
+                    return Err(PyValueError::new_err(
+                        "`http1_only` and `http2_only` are mutually exclusive",
+                    ));
+                }
                 apply_option!(set_if_true, builder, config.http1_only, http1_only, false);
                 apply_option!(set_if_true, builder, config.http2_only, http2_only, false);
                 apply_option!(set_if_some, builder, config.https_only, https_only);
@@ -392,17 +674,38 @@ impl Client {
                     config.tls_verify_hostname,
                     tls_verify_hostname
                 );
-                apply_option!(
-                    set_if_some_inner,
-                    builder,
-                    config.tls_identity,
-                    tls_identity
-                );
-                apply_option!(set_if_some_inner, builder, config.tls_keylog, tls_keylog);
+                if let Some(identity) = config.identity.take() {
+                    if config.tls_identity.is_some() {
+                        return Err(PyValueError::new_err(
+                            "`identity` and `tls_identity` cannot both be set",
+                        ));
+                    }
+                    config.tls_identity = Some(identity);
+                }
+                if let Some(keylog) = config.tls_keylog.take().and_then(KeyLogLike::into_inner) {
+                    builder = builder.tls_keylog(keylog);
+                }
                 apply_option!(set_if_some_inner, builder, config.tls_options, tls_options);
+                if direct_tls {
+                    let mut tls_options = wreq::tls::TlsOptions::builder();
+                    apply_option!(
+                        set_if_some,
+                        tls_options,
+                        config.tls_cipher_list,
+                        cipher_list
+                    );
+                    apply_option!(set_if_some, tls_options, config.tls_curves, curves_list);
+                    apply_option!(set_if_some, tls_options, config.tls_sigalgs, sigalgs_list);
+                    builder = builder.tls_options(tls_options.build());
+                }
+                tls_insecure = matches!(config.tls_verify, Some(TlsVerify::Verification(false)));
                 if let Some(verify) = config.tls_verify.take() {
                     builder = match verify {
                         TlsVerify::Verification(verify) => builder.tls_cert_verification(verify),
+                        TlsVerify::CertificateBytes(certs) => {
+                            let store = TlsVerify::cert_store(&certs).map_err(Error::Library)?;
+                            builder.tls_cert_store(store)
+                        }
                         TlsVerify::CertificatePath(path_buf) => {
                             let pem_data = std::fs::read(path_buf)?;
                             let store =
@@ -414,10 +717,32 @@ impl Client {
                         }
                     }
                 }
+                apply_option!(
+                    set_if_some_inner,
+                    builder,
+                    config.tls_identity,
+                    tls_identity
+                );
 
-                // Network options.
+                // Network options. A proxy resolves the target itself, out of reach of the
+                // private network check, so none is used when that check is on.
+                if config.block_private_networks == Some(true) {
+                    if config.proxies.is_some() {
+                        return Err(PyValueError::new_err(
+                            "`proxies` cannot be combined with `block_private_networks`",
+                        ));
+                    }
+                    config.no_proxy = Some(true);
+                }
+                proxy = config
+                    .proxies
+                    .as_ref()
+                    .and_then(|proxies| proxies.first().cloned());
                 apply_option!(set_if_some_iter_inner, builder, config.proxies, proxy);
                 apply_option!(set_if_true, builder, config.no_proxy, no_proxy, false);
+                if let Some(seed) = config.proxy_seed.take() {
+                    proxy_rng = Arc::new(Mutex::new(fastrand::Rng::with_seed(seed)));
+                }
                 apply_option!(set_if_some, builder, config.local_address, local_address);
                 apply_option!(
                     set_if_some_tuple_inner,
@@ -438,44 +763,236 @@ impl Client {
                 apply_option!(set_if_some, builder, config.interface, interface);
 
                 // DNS options.
+                block_private_networks = config.block_private_networks.unwrap_or(false);
                 builder = {
-                    let dns_resolver = if let Some(options) = config.dns_options.take() {
+                    let mut strategy = LookupIpStrategy::default();
+                    if let Some(options) = config.dns_options.take() {
                         for (domain, addrs) in options.resolve_to_addrs {
                             builder = builder.resolve_to_addrs(domain.as_ref().to_string(), addrs);
                         }
-                        HickoryDnsResolver::new(options.lookup_ip_strategy)
-                    } else {
-                        HickoryDnsResolver::new(LookupIpStrategy::default())
+                        strategy = options.lookup_ip_strategy;
+                    }
+                    let dns_resolver = match (config.dns.take(), config.dns_resolver.take()) {
+                        (Some(_), Some(_)) => {
+                            return Err(PyValueError::new_err(
+                                "`dns` and `dns_resolver` cannot be combined",
+                            ));
+                        }
+                        (None, Some(callback)) => HickoryDnsResolver::callback(callback),
+                        (Some(DnsMode::Https(doh)), None) => {
+                            HickoryDnsResolver::over_https(doh, strategy)
+                        }
+                        (Some(DnsMode::System), None) => HickoryDnsResolver::system(strategy),
+                        (Some(DnsMode::Ipv4), None) => {
+                            HickoryDnsResolver::new(LookupIpStrategy::IPV4_ONLY)
+                        }
+                        (Some(DnsMode::Ipv6), None) => {
+                            HickoryDnsResolver::new(LookupIpStrategy::IPV6_ONLY)
+                        }
+                        (Some(DnsMode::Hickory), None) | (None, None) => {
+                            HickoryDnsResolver::new(strategy)
+                        }
                     };
+                    let dns_resolver = dns_resolver
+                        .block_private_networks(block_private_networks)
+                        .overrides(config.resolve.take());
                     builder.dns_resolver(Arc::new(dns_resolver))
                 };
 
-                // Compression options.
-                apply_option!(set_if_some, builder, config.gzip, gzip);
-                apply_option!(set_if_some, builder, config.brotli, brotli);
-                apply_option!(set_if_some, builder, config.deflate, deflate);
-                apply_option!(set_if_some, builder, config.zstd, zstd);
+                // Compression options. Requests turn off automatic decompression and bodies
+                // are decoded once their size on the wire has been counted.
+                codings = Codings {
+                    gzip: config.gzip.unwrap_or(true),
+                    brotli: config.brotli.unwrap_or(true),
+                    deflate: config.deflate.unwrap_or(true),
+                    zstd: config.zstd.unwrap_or(true),
+                };
 
                 raise_for_status = config.raise_for_status.unwrap_or(false);
+                if let Some(max_retries) = config.max_retries.take() {
+                    retry = Some(Retry::for_client(
+                        max_retries,
+                        config
+                            .retry_backoff
+                            .take()
+                            .unwrap_or(Duration::from_millis(500)),
+                        config.retry_on_status.take(),
+                        config
+                            .retry_methods
+                            .take()
+                            .map(|methods| methods.into_iter().map(Method::into_ffi).collect()),
+                    ));
+                }
+                host_emulation = config.host_emulation.take().map(Arc::new);
+                emulation_fallback = config
+                    .emulation_fallback
+                    .take()
+                    .filter(|profiles| !profiles.is_empty())
+                    .map(|profiles| {
+                        EmulationFallback::new(profiles, config.block_detector.take())
+                    })
+                    .map(Arc::new);
+                allowed_hosts = config.allowed_hosts.take().map(Arc::new);
+                raw_request = config.raw_request.unwrap_or(false);
+                auto_buffer_limit = config.auto_buffer_limit.take();
+                max_body_size = config.max_body_size.take();
+                offload_decode = config.offload_decode.unwrap_or(false);
+                if config.auto_compress_request.unwrap_or(false) {
+                    request_compression = Some(Arc::new(RequestCompression {
+                        coding: config
+                            .auto_compress_algorithm
+                            .take()
+                            .unwrap_or(ContentCoding::Gzip),
+                        threshold: config.auto_compress_threshold.take().unwrap_or(1024),
+                    }));
+                }
             }
 
             builder
                 .build()
                 .map(|inner| Client {
-                    inner,
+                    inner: Handle::new(inner),
                     cancel: CancellationToken::new(),
                     cookie_jar,
                     raise_for_status,
+                    host_emulation,
+                    emulation_fallback,
+                    allowed_hosts,
+                    base_url,
+                    default_query,
+                    block_private_networks,
+                    raw_request,
+                    codings,
+                    accept_encoding,
+                    auto_buffer_limit,
+                    max_body_size,
+                    offload_decode,
+                    emulation_info,
+                    tls_profile,
+                    http2_profile,
+                    tls_insecure,
+                    proxy,
+                    proxy_rng,
+                    request_compression,
+                    connections,
+                    retry,
+                    redirect_retries,
+                    timeout,
+                    config: None,
+                    variants: Arc::default(),
                 })
                 .map_err(Error::Library)
                 .map_err(Into::into)
         })
     }
 
+    /// Resolve a request URL against the client's base URL, following RFC 3986.
+    ///
+    /// Absolute URLs replace the base entirely.
+    pub fn resolve_url<'a>(&self, url: &'a str) -> Result<Cow<'a, str>, Error> {
+        match &self.base_url {
+            Some(base) => base
+                .join(url)
+                .map(|url| Cow::Owned(url.into()))
+                .map_err(Error::Url),
+            None => Ok(Cow::Borrowed(url)),
+        }
+    }
+
+    /// Derive a client with the given per-request options, sharing everything else.
+    ///
+    /// The underlying client is rebuilt from the original options once per variant and
+    /// reused, so each variant keeps its own connection pool.
+    fn variant(&self, variant: Variant) -> PyResult<Client> {
+        if self.cancel.is_cancelled() {
+            return Err(Error::ClientClosed.into());
+        }
+        let variant = Variant {
+            tls_verify: variant
+                .tls_verify
+                .filter(|&verify| verify == self.tls_insecure),
+            ..variant
+        };
+        if variant == Variant::default() {
+            return Ok(self.clone());
+        }
+
+        let cached = self.variants.lock().unwrap().get(&variant).cloned();
+        let inner = match cached {
+            Some(inner) => inner,
+            None => {
+                let inner = Python::attach(|py| {
+                    let mut config = match &self.config {
+                        Some(config) => config.bind(py).extract::<Builder>()?,
+                        None => Builder::default(),
+                    };
+                    if let Some(verify) = variant.tls_verify {
+                        config.tls_verify = Some(TlsVerify::Verification(verify));
+                    }
+                    if variant.unpooled {
+                        config.single_connection = None;
+                        config.pool_max_idle_per_host = Some(0);
+                    }
+                    if let Some(connect_timeout) = variant.connect_timeout {
+                        config.connect_timeout = Some(connect_timeout);
+                    }
+                    config.cookie_provider = self.cookie_jar.clone();
+                    Client::build(py, Some(config)).map(|client| client.inner)
+                })?;
+                self.variants
+                    .lock()
+                    .unwrap()
+                    .entry(variant)
+                    .or_insert(inner)
+                    .clone()
+            }
+        };
+        Ok(Client {
+            inner,
+            tls_insecure: variant
+                .tls_verify
+                .map_or(self.tls_insecure, |verify| !verify),
+            ..self.clone()
+        })
+    }
+}
+
+#[pymethods]
+impl Client {
+    /// Creates a new Client instance.
+    #[new]
+    #[pyo3(signature = (**kwds))]
+    fn new(py: Python, kwds: Option<Bound<PyDict>>) -> PyResult<Client> {
+        let config = kwds.as_ref().map(|kwds| kwds.extract()).transpose()?;
+        let mut client = Client::build(py, config)?;
+        client.config = kwds.map(|kwds| Arc::new(kwds.unbind()));
+        Ok(client)
+    }
+
     /// Close the client, preventing any new requests.
+    ///
+    /// Requests in flight are cancelled and the connection pool is released once they
+    /// have unwound.
     #[inline]
     pub fn close(&self) {
         self.cancel.cancel();
+        self.inner.close();
+        for inner in self.variants.lock().unwrap().values() {
+            inner.close();
+        }
+    }
+
+    /// Get a summary of the emulation profile configured on the client.
+    #[inline]
+    pub fn emulation_info(&self) -> EmulationInfo {
+        self.emulation_info.clone()
+    }
+
+    /// Get how many requests were served by reused versus new connections, if the client
+    /// was created with `track_connections`.
+    #[inline]
+    pub fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.connections.as_ref().map(|tracker| tracker.snapshot())
     }
 
     /// Make a GET request to the given URL.
@@ -592,6 +1109,40 @@ impl Client {
         .await
     }
 
+    /// Send a request whose body is streamed straight from another response.
+    #[pyo3(signature = (source, method, url, **kwds))]
+    pub async fn pipe(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+        source: Py<Response>,
+        method: Method,
+        url: PyBackedStr,
+        kwds: Option<Request>,
+    ) -> PyResult<Response> {
+        let (body, content_type) = source.get().pipe_body()?;
+        let request = kwds
+            .unwrap_or_default()
+            .with_piped_body(body, content_type)?;
+        NoGIL::new_with_token(
+            execute_request(self.clone(), method, url, Some(request)),
+            cancel,
+            self.cancel.clone(),
+        )
+        .await
+    }
+
+    /// Build the request without sending it and render it as an equivalent `curl` command.
+    #[pyo3(signature = (method, url, **kwds))]
+    pub fn to_curl(
+        &self,
+        py: Python,
+        method: Method,
+        url: PyBackedStr,
+        kwds: Option<Request>,
+    ) -> PyResult<String> {
+        nogil::block_on(py, render_curl(self.clone(), method, url, kwds))
+    }
+
     /// Make a WebSocket request to the given URL.
     #[inline]
     #[pyo3(signature = (url, **kwds))]
@@ -608,6 +1159,12 @@ impl Client {
         )
         .await
     }
+
+    /// Open a stream of server-sent events from the given URL, reconnecting when it ends.
+    #[pyo3(signature = (url, **kwds))]
+    pub fn sse(&self, url: PyBackedStr, kwds: Option<Bound<PyDict>>) -> PyResult<EventSource> {
+        EventSource::new(self.clone(), url.to_string(), kwds)
+    }
 }
 
 #[pymethods]
@@ -631,7 +1188,7 @@ impl BlockingClient {
     #[new]
     #[inline]
     #[pyo3(signature = (**kwds))]
-    fn new(py: Python, kwds: Option<Builder>) -> PyResult<BlockingClient> {
+    fn new(py: Python, kwds: Option<Bound<PyDict>>) -> PyResult<BlockingClient> {
         Client::new(py, kwds).map(BlockingClient)
     }
 
@@ -648,6 +1205,19 @@ impl BlockingClient {
         self.0.close();
     }
 
+    /// Get a summary of the emulation profile configured on the client.
+    #[inline]
+    pub fn emulation_info(&self) -> EmulationInfo {
+        self.0.emulation_info()
+    }
+
+    /// Get how many requests were served by reused versus new connections, if the client
+    /// was created with `track_connections`.
+    #[inline]
+    pub fn connection_stats(&self) -> Option<ConnectionStats> {
+        self.0.connection_stats()
+    }
+
     /// Make a GET request to the specified URL.
     #[inline(always)]
     #[pyo3(signature = (url, **kwds))]
@@ -753,11 +1323,41 @@ impl BlockingClient {
         url: PyBackedStr,
         kwds: Option<Request>,
     ) -> PyResult<BlockingResponse> {
-        py.detach(|| {
-            pyo3_async_runtimes::tokio::get_runtime()
-                .block_on(execute_request(self.0.clone(), method, url, kwds))
-                .map(Into::into)
-        })
+        nogil::block_on(py, execute_request(self.0.clone(), method, url, kwds)).map(Into::into)
+    }
+
+    /// Send a request whose body is streamed straight from another response.
+    #[pyo3(signature = (source, method, url, **kwds))]
+    pub fn pipe(
+        &self,
+        py: Python,
+        source: PyRef<BlockingResponse>,
+        method: Method,
+        url: PyBackedStr,
+        kwds: Option<Request>,
+    ) -> PyResult<BlockingResponse> {
+        let (body, content_type) = source.pipe_body()?;
+        let request = kwds
+            .unwrap_or_default()
+            .with_piped_body(body, content_type)?;
+        nogil::block_on(
+            py,
+            execute_request(self.0.clone(), method, url, Some(request)),
+        )
+        .map(Into::into)
+    }
+
+    /// Build the request without sending it and render it as an equivalent `curl` command.
+    #[inline]
+    #[pyo3(signature = (method, url, **kwds))]
+    pub fn to_curl(
+        &self,
+        py: Python,
+        method: Method,
+        url: PyBackedStr,
+        kwds: Option<Request>,
+    ) -> PyResult<String> {
+        self.0.to_curl(py, method, url, kwds)
     }
 
     /// Make a WebSocket request to the specified URL.
@@ -768,11 +1368,14 @@ impl BlockingClient {
         url: PyBackedStr,
         kwds: Option<WebSocketRequest>,
     ) -> PyResult<BlockingWebSocket> {
-        py.detach(|| {
-            pyo3_async_runtimes::tokio::get_runtime()
-                .block_on(execute_websocket_request(self.0.clone(), url, kwds))
-                .map(Into::into)
-        })
+        nogil::block_on(py, execute_websocket_request(self.0.clone(), url, kwds)).map(Into::into)
+    }
+
+    /// Open a stream of server-sent events from the specified URL.
+    #[inline]
+    #[pyo3(signature = (url, **kwds))]
+    pub fn sse(&self, url: PyBackedStr, kwds: Option<Bound<PyDict>>) -> PyResult<EventSource> {
+        self.0.sse(url, kwds)
     }
 }
 
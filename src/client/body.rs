@@ -2,20 +2,26 @@
 
 mod form;
 mod json;
+mod mixed;
 pub mod multipart;
 mod stream;
 
+use async_compression::tokio::write::{GzipEncoder, ZstdEncoder};
 use bytes::Bytes;
+use http::HeaderValue;
 use pyo3::{
     FromPyObject, PyResult,
+    exceptions::PyValueError,
     prelude::*,
     pybacked::{PyBackedBytes, PyBackedStr},
 };
+use tokio::io::AsyncWriteExt;
 
 pub use self::{
     form::Form,
     json::Json,
-    stream::{PyStream, Streamer},
+    mixed::Parts,
+    stream::{PyStream, Streamer, charset},
 };
 
 /// Represents the body of an HTTP request.
@@ -28,6 +34,115 @@ pub enum Body {
     Stream(PyStream),
 }
 
+/// How a streaming request body is sent.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum StreamMode {
+    /// Send chunks as they are produced, using `Transfer-Encoding: chunked` on HTTP/1.1.
+    Chunked,
+    /// Drain the stream first and send the body with a `Content-Length`.
+    Buffered,
+}
+
+/// The algorithm used to compress request bodies.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ContentCoding {
+    Gzip,
+    Zstd,
+}
+
+/// Compresses request bodies of known size once they reach a threshold.
+pub struct RequestCompression {
+    pub coding: ContentCoding,
+    pub threshold: usize,
+}
+
+impl FromPyObject<'_, '_> for StreamMode {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let mode = ob.extract::<PyBackedStr>()?;
+        match &*mode {
+            "chunked" => Ok(StreamMode::Chunked),
+            "buffered" => Ok(StreamMode::Buffered),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid stream mode: {}, expected one of chunked, buffered",
+                &*mode
+            ))),
+        }
+    }
+}
+
+impl FromPyObject<'_, '_> for ContentCoding {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let coding = ob.extract::<PyBackedStr>()?;
+        match &*coding {
+            "gzip" => Ok(ContentCoding::Gzip),
+            "zstd" => Ok(ContentCoding::Zstd),
+            _ => Err(PyValueError::new_err(format!(
+                "Invalid compression algorithm: {}, expected one of gzip, zstd",
+                &*coding
+            ))),
+        }
+    }
+}
+
+impl ContentCoding {
+    /// The `Content-Encoding` header value for this algorithm.
+    pub fn header_value(self) -> HeaderValue {
+        match self {
+            ContentCoding::Gzip => HeaderValue::from_static("gzip"),
+            ContentCoding::Zstd => HeaderValue::from_static("zstd"),
+        }
+    }
+}
+
+impl RequestCompression {
+    /// Compress the body if it is at least `threshold` bytes long.
+    ///
+    /// Returns `None` for smaller bodies and for bodies that do not shrink, which are sent as is.
+    pub async fn compress(&self, body: &[u8]) -> Result<Option<Bytes>, crate::Error> {
+        if body.len() < self.threshold {
+            return Ok(None);
+        }
+        let compressed = match self.coding {
+            ContentCoding::Gzip => {
+                let mut encoder = GzipEncoder::new(Vec::new());
+                encoder.write_all(body).await?;
+                encoder.shutdown().await?;
+                encoder.into_inner()
+            }
+            ContentCoding::Zstd => {
+                let mut encoder = ZstdEncoder::new(Vec::new());
+                encoder.write_all(body).await?;
+                encoder.shutdown().await?;
+                encoder.into_inner()
+            }
+        };
+        Ok((compressed.len() < body.len()).then(|| Bytes::from(compressed)))
+    }
+}
+
+impl Body {
+    /// Serialize a body of known size, handing streaming bodies back unchanged.
+    pub fn into_bytes(self) -> PyResult<Result<Bytes, Body>> {
+        match self {
+            Body::Form(form) => serde_urlencoded::to_string(form)
+                .map(|form| Ok(Bytes::from(form)))
+                .map_err(crate::Error::Form)
+                .map_err(Into::into),
+            Body::Json(json) => serde_json::to_vec(&json)
+                .map(|json| Ok(Bytes::from(json)))
+                .map_err(crate::Error::Json)
+                .map_err(Into::into),
+            Body::Text(s) => Ok(Ok(Bytes::from_owner(s))),
+            Body::Bytes(bytes) => Ok(Ok(Bytes::from_owner(bytes))),
+            body @ Body::Stream(_) => Ok(Err(body)),
+        }
+    }
+}
+
 impl TryFrom<Body> for wreq::Body {
     type Error = PyErr;
 
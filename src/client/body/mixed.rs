@@ -0,0 +1,153 @@
+//! Incremental parser for `multipart/x-mixed-replace` (and other multipart) response bodies.
+
+use bytes::{Buf, Bytes, BytesMut};
+use http::{HeaderMap, HeaderName, HeaderValue};
+use memchr::memmem;
+
+/// Splits a multipart body into parts as bytes arrive.
+pub struct Parts {
+    /// The delimiter line, `--` followed by the boundary.
+    delimiter: Vec<u8>,
+    /// The delimiter as it appears between parts, preceded by a line break.
+    separator: Vec<u8>,
+    buffer: BytesMut,
+    state: State,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// Skipping the preamble before the first delimiter.
+    Preamble,
+    /// Right after a delimiter, before the end of its line.
+    Delimiter,
+    /// Inside a part, waiting for the next delimiter.
+    Part,
+    /// The closing delimiter has been seen.
+    Done,
+}
+
+impl Parts {
+    /// Create a parser for the boundary declared in the given `Content-Type`, if any.
+    pub fn new(headers: &http::HeaderMap) -> Option<Parts> {
+        let boundary = boundary(headers)?;
+        let delimiter = [b"--", boundary.as_bytes()].concat();
+        let separator = [b"\r\n", delimiter.as_slice()].concat();
+        Some(Parts {
+            delimiter,
+            separator,
+            buffer: BytesMut::new(),
+            state: State::Preamble,
+        })
+    }
+
+    /// Feed body bytes into the parser.
+    #[inline]
+    pub fn extend(&mut self, bytes: &[u8]) {
+        if self.state != State::Done {
+            self.buffer.extend_from_slice(bytes);
+        }
+    }
+
+    /// Take the next complete part, if one has been buffered.
+    pub fn next_part(&mut self) -> Option<(HeaderMap, Bytes)> {
+        loop {
+            match self.state {
+                State::Preamble => match memmem::find(&self.buffer, &self.delimiter) {
+                    Some(pos) => {
+                        self.buffer.advance(pos + self.delimiter.len());
+                        self.state = State::Delimiter;
+                    }
+                    None => {
+                        // Keep just enough bytes to match a delimiter split across chunks.
+                        let keep = self.delimiter.len() - 1;
+                        if self.buffer.len() > keep {
+                            self.buffer.advance(self.buffer.len() - keep);
+                        }
+                        return None;
+                    }
+                },
+                State::Delimiter => {
+                    if self.buffer.len() < 2 {
+                        return None;
+                    }
+                    if self.buffer.starts_with(b"--") {
+                        self.state = State::Done;
+                        self.buffer.clear();
+                        return None;
+                    }
+                    // Skip any transport padding up to the end of the delimiter line.
+                    let pos = memmem::find(&self.buffer, b"\r\n")?;
+                    self.buffer.advance(pos + 2);
+                    self.state = State::Part;
+                }
+                State::Part => {
+                    let pos = memmem::find(&self.buffer, &self.separator)?;
+                    let part = self.buffer.split_to(pos).freeze();
+                    self.buffer.advance(self.separator.len());
+                    self.state = State::Delimiter;
+                    return Some(split_headers(part));
+                }
+                State::Done => return None,
+            }
+        }
+    }
+
+    /// Take whatever is left of an unterminated part once the body ends.
+    pub fn finish(&mut self) -> Option<(HeaderMap, Bytes)> {
+        let state = std::mem::replace(&mut self.state, State::Done);
+        if state == State::Part && !self.buffer.is_empty() {
+            return Some(split_headers(self.buffer.split().freeze()));
+        }
+        None
+    }
+}
+
+/// Read the `boundary` parameter of a multipart `Content-Type`.
+fn boundary(headers: &http::HeaderMap) -> Option<String> {
+    let content_type = headers.get(http::header::CONTENT_TYPE)?.to_str().ok()?;
+    let mut params = content_type.split(';');
+    if !params
+        .next()?
+        .trim()
+        .to_ascii_lowercase()
+        .starts_with("multipart/")
+    {
+        return None;
+    }
+    params.find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("boundary")
+            .then(|| value.trim().trim_matches('"').to_owned())
+            .filter(|boundary| !boundary.is_empty())
+    })
+}
+
+/// Split a raw part into its headers and body.
+fn split_headers(mut part: Bytes) -> (HeaderMap, Bytes) {
+    let mut headers = HeaderMap::new();
+    let end = if part.starts_with(b"\r\n") {
+        Some(0)
+    } else {
+        memmem::find(&part, b"\r\n\r\n").map(|pos| pos + 2)
+    };
+    let Some(end) = end else {
+        return (headers, part);
+    };
+
+    let head = part.split_to(end);
+    part.advance(2);
+    for line in head.split(|&byte| byte == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let Some(colon) = line.iter().position(|&byte| byte == b':') else {
+            continue;
+        };
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(&line[..colon]),
+            HeaderValue::from_bytes(line[colon + 1..].trim_ascii()),
+        ) {
+            headers.append(name, value);
+        }
+    }
+    (headers, part)
+}
@@ -1,12 +1,21 @@
 use std::{
+    io,
     pin::Pin,
     sync::Arc,
     task::{Context, Poll},
+    time::Duration,
 };
 
-use bytes::Bytes;
-use futures_util::{FutureExt, Stream, StreamExt, stream::BoxStream};
+use async_compression::tokio::bufread::{GzipDecoder, ZstdDecoder};
+use bytes::{Bytes, BytesMut};
+use encoding_rs::{Decoder, Encoding, UTF_8};
+use futures_util::{
+    FutureExt, Stream, StreamExt, TryStreamExt, future,
+    stream::{self, BoxStream},
+};
+use http::response::Response as HttpResponse;
 use http_body_util::BodyExt;
+use memchr::memmem;
 use pyo3::{
     coroutine::CancelHandle,
     intern,
@@ -14,11 +23,24 @@ use pyo3::{
     pybacked::{PyBackedBytes, PyBackedStr},
 };
 use tokio::{sync::Mutex, task::JoinHandle};
+use tokio_util::io::{ReaderStream, StreamReader};
 
-use crate::{buffer::PyBuffer, client::nogil::NoGIL, error::Error, header::HeaderMap};
+use super::mixed::Parts;
+use crate::{
+    buffer::{PyBuffer, PyMemoryView},
+    client::nogil::{self, NoGIL},
+    error::Error,
+    header::HeaderMap,
+};
 
 type Pending = Option<JoinHandle<Option<PyResult<PyBytesLike>>>>;
 
+/// The gzip magic bytes.
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+
+/// The zstd frame magic bytes.
+const ZSTD_MAGIC: &[u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+
 /// Python stream source.
 enum PyStreamSource {
     Sync(Arc<Py<PyAny>>),
@@ -36,7 +58,10 @@ pub enum PyBytesLike {
 #[derive(IntoPyObject)]
 pub enum Frame {
     Bytes(PyBuffer),
+    View(PyMemoryView),
+    Text(String),
     Trailers(HeaderMap),
+    Part(HeaderMap, PyBuffer),
 }
 
 /// A Python stream wrapper.
@@ -48,7 +73,44 @@ pub struct PyStream {
 /// A bytes stream response.
 #[derive(Clone)]
 #[pyclass(subclass, frozen, skip_from_py_object)]
-pub struct Streamer(Arc<Mutex<Option<wreq::Response>>>);
+pub struct Streamer(Arc<Mutex<Option<Source>>>);
+
+/// The response being streamed, with the remaining byte budget if one was configured.
+struct Source {
+    resp: wreq::Response,
+    remaining: Option<u64>,
+    content: Option<Content>,
+    /// Multipart parsing state used by `iter_multipart`.
+    parts: Option<Parts>,
+    /// Line splitting state used by `iter_lines`.
+    lines: Option<Lines>,
+    /// Yield chunks as `memoryview`s over the received bytes, used by `iter_chunks`.
+    views: bool,
+    /// Sniff the body for undeclared compression before the first read.
+    autodetect: bool,
+    /// The deadline for each individual chunk to arrive.
+    chunk_timeout: Option<Duration>,
+}
+
+/// Re-chunking and decoding state used by `iter_content`.
+struct Content {
+    chunk_size: Option<usize>,
+    buffer: BytesMut,
+    decoder: Option<Decoder>,
+}
+
+/// Line splitting and decoding state used by `iter_lines`.
+struct Lines {
+    /// The line delimiter, or `None` to split on `\n` and strip a trailing `\r`.
+    delimiter: Option<Vec<u8>>,
+    buffer: BytesMut,
+    /// How far the buffer has been searched for a delimiter.
+    searched: usize,
+    /// The encoding lines are decoded with, or `None` to yield `bytes`.
+    encoding: Option<&'static Encoding>,
+    /// The longest line accepted, in bytes and without its delimiter.
+    max_length: Option<usize>,
+}
 
 // ===== impl PyStream =====
 
@@ -66,39 +128,406 @@ impl From<PyStreamSource> for PyStream {
 
 impl Streamer {
     /// Create a new [`Streamer`] instance.
+    ///
+    /// When `max_bytes` is set, the stream ends once that many body bytes have been yielded.
+    /// When `autodetect` is set and the response declares no `Content-Encoding`, gzip or zstd
+    /// bodies are recognized by their magic bytes and decompressed transparently. When
+    /// `chunk_timeout` is set, each read fails with a timeout error if no chunk arrives in time.
+    /// When `chunk_size` is set, chunks are re-buffered into blocks of exactly that many bytes,
+    /// except for a shorter final one, and trailers are skipped.
     #[inline]
-    pub fn new(resp: wreq::Response) -> Streamer {
-        Streamer(Arc::new(Mutex::new(Some(resp))))
+    pub fn new(
+        resp: wreq::Response,
+        max_bytes: Option<u64>,
+        autodetect: bool,
+        chunk_timeout: Option<Duration>,
+        chunk_size: Option<usize>,
+    ) -> Streamer {
+        let autodetect = autodetect && !resp.headers().contains_key(http::header::CONTENT_ENCODING);
+        Streamer(Arc::new(Mutex::new(Some(Source {
+            resp,
+            remaining: max_bytes,
+            content: chunk_size.map(|chunk_size| Content {
+                chunk_size: Some(chunk_size),
+                buffer: BytesMut::new(),
+                decoder: None,
+            }),
+            parts: None,
+            lines: None,
+            views: false,
+            autodetect,
+            chunk_timeout,
+        }))))
+    }
+
+    /// Create a [`Streamer`] with `requests`-style `iter_content` semantics.
+    ///
+    /// Yields fixed-size chunks when `chunk_size` is set, and `str` chunks decoded with the
+    /// response charset (defaulting to utf-8) when `decode_unicode` is true. Trailers are skipped.
+    pub fn content(
+        resp: wreq::Response,
+        chunk_size: Option<usize>,
+        decode_unicode: bool,
+    ) -> Streamer {
+        let decoder = decode_unicode.then(|| charset(resp.headers()).new_decoder());
+        Streamer(Arc::new(Mutex::new(Some(Source {
+            resp,
+            remaining: None,
+            content: Some(Content {
+                chunk_size,
+                buffer: BytesMut::new(),
+                decoder,
+            }),
+            parts: None,
+            lines: None,
+            views: false,
+            autodetect: false,
+            chunk_timeout: None,
+        }))))
+    }
+
+    /// Create a [`Streamer`] yielding the lines of the body, split on `delimiter` or on line
+    /// breaks when unset.
+    ///
+    /// Lines are decoded with `encoding`, or the response charset (defaulting to utf-8) when
+    /// unset, if `decode_unicode` is true. A line split across chunks is yielded once
+    /// complete, and a final line without a delimiter is yielded when the body ends. Trailers
+    /// are skipped. A line longer than `max_length` bytes fails the read once that many bytes
+    /// are buffered without a delimiter.
+    pub fn lines(
+        resp: wreq::Response,
+        delimiter: Option<Vec<u8>>,
+        decode_unicode: bool,
+        encoding: Option<&'static Encoding>,
+        max_length: Option<usize>,
+    ) -> Streamer {
+        let encoding =
+            decode_unicode.then(|| encoding.unwrap_or_else(|| charset(resp.headers())));
+        Streamer(Arc::new(Mutex::new(Some(Source {
+            resp,
+            remaining: None,
+            content: None,
+            parts: None,
+            lines: Some(Lines {
+                delimiter,
+                buffer: BytesMut::new(),
+                searched: 0,
+                encoding,
+                max_length,
+            }),
+            views: false,
+            autodetect: false,
+            chunk_timeout: None,
+        }))))
+    }
+
+    /// Create a [`Streamer`] yielding the `(headers, body)` parts of a multipart response.
+    ///
+    /// Trailers are skipped, and a part left unterminated when the body ends is still yielded.
+    pub fn parts(resp: wreq::Response, parts: Parts) -> Streamer {
+        Streamer(Arc::new(Mutex::new(Some(Source {
+            resp,
+            remaining: None,
+            content: None,
+            parts: Some(parts),
+            lines: None,
+            views: false,
+            autodetect: false,
+            chunk_timeout: None,
+        }))))
+    }
+
+    /// Create a [`Streamer`] yielding each chunk as a read-only `memoryview`, without copying
+    /// it into a new `bytes` object. Trailers are skipped.
+    pub fn views(resp: wreq::Response) -> Streamer {
+        Streamer(Arc::new(Mutex::new(Some(Source {
+            resp,
+            remaining: None,
+            content: None,
+            parts: None,
+            lines: None,
+            views: true,
+            autodetect: false,
+            chunk_timeout: None,
+        }))))
     }
 
     async fn next(self, error: fn() -> Error) -> PyResult<Frame> {
-        let frame = self
-            .0
-            .lock()
-            .await
-            .as_mut()
-            .ok_or_else(error)?
-            .frame()
-            .await
-            .ok_or_else(error)?
-            .map_err(Error::Library)?
-            .into_data()
-            .map_err(|frame| frame.into_trailers());
-
-        match frame {
-            Ok(bytes) => Ok(Frame::Bytes(PyBuffer::from(bytes))),
-            Err(Ok(trailers)) => Ok(Frame::Trailers(HeaderMap(trailers))),
-            Err(Err(frame)) => {
-                // This branch should be unreachable, as `http_body::Frame` can only be `Data` or
-                // `Trailers`. The `debug_assert!` will help catch any future
-                // changes that violate this assumption.
-                debug_assert!(false, "Unexpected frame type: {:?}", frame);
-                Err(error().into())
+        let mut guard = self.0.lock().await;
+
+        if guard.as_ref().is_some_and(|source| source.autodetect) {
+            let source = guard.take().ok_or_else(error)?;
+            *guard = Some(source.autodetect().await?);
+        }
+
+        let source = guard.as_mut().ok_or_else(error)?;
+
+        loop {
+            // A full part is already buffered.
+            if let Some((headers, body)) = source.parts.as_mut().and_then(Parts::next_part) {
+                return Ok(Frame::Part(HeaderMap(headers), PyBuffer::from(body)));
+            }
+
+            // A full line is already buffered.
+            if let Some(line) = source
+                .lines
+                .as_mut()
+                .map(Lines::next_line)
+                .transpose()?
+                .flatten()
+            {
+                return Ok(line);
+            }
+
+            // A full chunk is already buffered.
+            if let Some(content) = source.content.as_mut() {
+                if let Some(chunk) = content.split_chunk() {
+                    match content.emit(chunk, false) {
+                        Some(frame) => return Ok(frame),
+                        None => continue,
+                    }
+                }
+            }
+
+            // The byte budget is exhausted, flush the buffered tail and then drop the response
+            // to release the connection.
+            if source.remaining == Some(0) {
+                if let Some(mut content) = source.content.take() {
+                    let rest = content.buffer.split().freeze();
+                    if let Some(frame) = content.emit(rest, true) {
+                        return Ok(frame);
+                    }
+                }
+                guard.take();
+                return Err(error().into());
+            }
+
+            // A stalled chunk fails this read only, the stream can still be polled again.
+            let frame = match source.chunk_timeout {
+                Some(timeout) => tokio::time::timeout(timeout, source.resp.frame())
+                    .await
+                    .map_err(Error::from)?,
+                None => source.resp.frame().await,
+            };
+            let frame = match frame {
+                Some(frame) => frame.map_err(Error::Library)?,
+                None => {
+                    // End of body, flush whatever is left in the buffer and decoder exactly once.
+                    if let Some((headers, body)) =
+                        source.parts.take().as_mut().and_then(Parts::finish)
+                    {
+                        return Ok(Frame::Part(HeaderMap(headers), PyBuffer::from(body)));
+                    }
+                    if let Some(mut content) = source.content.take() {
+                        let rest = content.buffer.split().freeze();
+                        if let Some(frame) = content.emit(rest, true) {
+                            return Ok(frame);
+                        }
+                    }
+                    if let Some(line) = source
+                        .lines
+                        .take()
+                        .as_mut()
+                        .map(Lines::finish)
+                        .transpose()?
+                        .flatten()
+                    {
+                        return Ok(line);
+                    }
+                    return Err(error().into());
+                }
+            };
+
+            match frame.into_data().map_err(|frame| frame.into_trailers()) {
+                Ok(mut bytes) => {
+                    if let Some(remaining) = source.remaining.as_mut() {
+                        bytes.truncate(bytes.len().min(*remaining as usize));
+                        *remaining -= bytes.len() as u64;
+                    }
+                    if let Some(parts) = source.parts.as_mut() {
+                        parts.extend(&bytes);
+                        continue;
+                    }
+                    if let Some(lines) = source.lines.as_mut() {
+                        lines.buffer.extend_from_slice(&bytes);
+                        continue;
+                    }
+                    match source.content.as_mut() {
+                        Some(content) if content.chunk_size.is_some() => {
+                            content.buffer.extend_from_slice(&bytes);
+                        }
+                        Some(content) => {
+                            if let Some(frame) = content.emit(bytes, false) {
+                                return Ok(frame);
+                            }
+                        }
+                        None if source.views => {
+                            if !bytes.is_empty() {
+                                return Ok(Frame::View(PyMemoryView::from(bytes)));
+                            }
+                        }
+                        None => return Ok(Frame::Bytes(PyBuffer::from(bytes))),
+                    }
+                }
+                Err(Ok(trailers)) => {
+                    if source.content.is_none()
+                        && source.parts.is_none()
+                        && source.lines.is_none()
+                        && !source.views
+                    {
+                        return Ok(Frame::Trailers(HeaderMap(trailers)));
+                    }
+                }
+                Err(Err(frame)) => {
+                    // This branch should be unreachable, as `http_body::Frame` can only be `Data` or
+                    // `Trailers`. The `debug_assert!` will help catch any future
+                    // changes that violate this assumption.
+                    debug_assert!(false, "Unexpected frame type: {:?}", frame);
+                    return Err(error().into());
+                }
             }
         }
     }
 }
 
+// ===== impl Source =====
+
+impl Source {
+    /// Sniff the first body bytes and wrap the body in a gzip or zstd decoder if they match.
+    ///
+    /// The sniffed bytes are always replayed, so uncompressed bodies are left untouched.
+    async fn autodetect(mut self) -> Result<Source, Error> {
+        self.autodetect = false;
+
+        let mut head = BytesMut::new();
+        while head.len() < ZSTD_MAGIC.len() {
+            match self.resp.frame().await {
+                Some(frame) => {
+                    if let Ok(data) = frame.map_err(Error::Library)?.into_data() {
+                        head.extend_from_slice(&data);
+                    }
+                }
+                None => break,
+            }
+        }
+
+        let head = head.freeze();
+        let (parts, body) = HttpResponse::from(self.resp).into_parts();
+        let data = stream::once(future::ok(head.clone())).chain(body.into_data_stream());
+        let body = if head.starts_with(GZIP_MAGIC) {
+            let reader = StreamReader::new(data.map_err(io::Error::other));
+            wreq::Body::wrap_stream(ReaderStream::new(GzipDecoder::new(reader)))
+        } else if head.starts_with(ZSTD_MAGIC) {
+            let reader = StreamReader::new(data.map_err(io::Error::other));
+            wreq::Body::wrap_stream(ReaderStream::new(ZstdDecoder::new(reader)))
+        } else {
+            wreq::Body::wrap_stream(data)
+        };
+
+        self.resp = wreq::Response::from(HttpResponse::from_parts(parts, body));
+        Ok(self)
+    }
+}
+
+// ===== impl Content =====
+
+impl Content {
+    /// Split off the next full chunk, if enough bytes are buffered.
+    fn split_chunk(&mut self) -> Option<Bytes> {
+        let size = self.chunk_size?;
+        (self.buffer.len() >= size).then(|| self.buffer.split_to(size).freeze())
+    }
+
+    /// Turn a chunk into a frame, decoding it when unicode decoding is enabled.
+    ///
+    /// Returns `None` when there is nothing to yield yet, e.g. an empty chunk or a partial
+    /// multi-byte sequence held back by the decoder.
+    fn emit(&mut self, bytes: Bytes, last: bool) -> Option<Frame> {
+        match self.decoder.as_mut() {
+            Some(decoder) => {
+                let capacity = decoder
+                    .max_utf8_buffer_length(bytes.len())
+                    .unwrap_or(bytes.len());
+                let mut text = String::with_capacity(capacity);
+                let _ = decoder.decode_to_string(&bytes, &mut text, last);
+                (!text.is_empty()).then_some(Frame::Text(text))
+            }
+            None => (!bytes.is_empty()).then(|| Frame::Bytes(PyBuffer::from(bytes))),
+        }
+    }
+}
+
+// ===== impl Lines =====
+
+impl Lines {
+    /// Split off the next complete line, if its delimiter has been buffered.
+    fn next_line(&mut self) -> Result<Option<Frame>, Error> {
+        let delimiter = self.delimiter.as_deref().unwrap_or(b"\n");
+        // Resume where the last search stopped, keeping a delimiter split across chunks.
+        let start = self.searched.saturating_sub(delimiter.len() - 1);
+        let Some(pos) = memmem::find(&self.buffer[start..], delimiter) else {
+            self.searched = self.buffer.len();
+            // The tail may hold the start of a delimiter, which is not part of the line.
+            self.check(self.buffer.len().saturating_sub(delimiter.len() - 1))?;
+            return Ok(None);
+        };
+        let pos = start + pos;
+        let mut line = self.buffer.split_to(pos + delimiter.len());
+        line.truncate(pos);
+        if self.delimiter.is_none() && line.ends_with(b"\r") {
+            line.truncate(pos - 1);
+        }
+        self.searched = 0;
+        self.check(line.len())?;
+        Ok(Some(self.emit(line.freeze())))
+    }
+
+    /// Take the final line left without a delimiter when the body ends.
+    fn finish(&mut self) -> Result<Option<Frame>, Error> {
+        if self.buffer.is_empty() {
+            return Ok(None);
+        }
+        self.check(self.buffer.len())?;
+        let line = self.buffer.split().freeze();
+        Ok(Some(self.emit(line)))
+    }
+
+    /// Fail once a line grows past the maximum length.
+    fn check(&self, len: usize) -> Result<(), Error> {
+        match self.max_length {
+            Some(max) if len > max => Err(Error::LineTooLong(max)),
+            _ => Ok(()),
+        }
+    }
+
+    fn emit(&self, line: Bytes) -> Frame {
+        match self.encoding {
+            Some(encoding) => {
+                let (text, _) = encoding.decode_without_bom_handling(&line);
+                Frame::Text(text.into_owned())
+            }
+            None => Frame::Bytes(PyBuffer::from(line)),
+        }
+    }
+}
+
+/// Resolve the response encoding from the `Content-Type` charset, defaulting to utf-8.
+pub fn charset(headers: &http::HeaderMap) -> &'static Encoding {
+    headers
+        .get(http::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value.split(';').skip(1).find_map(|param| {
+                let (name, value) = param.split_once('=')?;
+                name.trim()
+                    .eq_ignore_ascii_case("charset")
+                    .then(|| value.trim().trim_matches('"'))
+            })
+        })
+        .and_then(|label| Encoding::for_label(label.as_bytes()))
+        .unwrap_or(UTF_8)
+}
+
 #[pymethods]
 impl Streamer {
     #[inline]
@@ -108,10 +537,7 @@ impl Streamer {
 
     #[inline]
     fn __next__(&self, py: Python) -> PyResult<Frame> {
-        py.detach(|| {
-            pyo3_async_runtimes::tokio::get_runtime()
-                .block_on(self.clone().next(|| Error::StopIteration))
-        })
+        nogil::block_on(py, self.clone().next(|| Error::StopIteration))
     }
 
     #[inline]
@@ -186,6 +612,18 @@ impl From<PyBytesLike> for Bytes {
 
 // ===== impl PyStream =====
 
+impl PyStream {
+    /// Drain the stream into a single buffer.
+    pub async fn collect_bytes(self) -> PyResult<Bytes> {
+        self.try_fold(BytesMut::new(), |mut buf, chunk| {
+            buf.extend_from_slice(&Bytes::from(chunk));
+            future::ok(buf)
+        })
+        .await
+        .map(BytesMut::freeze)
+    }
+}
+
 impl FromPyObject<'_, '_> for PyStream {
     type Error = PyErr;
 
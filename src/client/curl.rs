@@ -0,0 +1,87 @@
+//! Rendering requests as equivalent `curl` commands.
+//!
+//! Only what is set on the request itself is rendered. Headers added by the client when the
+//! request is sent, such as emulation or default headers, are not part of the command.
+
+use std::fmt::Write;
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::proxy::{Proxy, ProxyTarget};
+
+/// Render the request as a `curl` command line.
+pub fn render(request: &wreq::Request, proxy: Option<&Proxy>, insecure: bool) -> PyResult<String> {
+    let mut command = String::from("curl");
+
+    if request.method() != http::Method::GET {
+        let _ = write!(command, " -X {}", request.method());
+    }
+
+    for (name, value) in request.headers() {
+        let mut header = name.as_str().as_bytes().to_vec();
+        header.extend_from_slice(b": ");
+        header.extend_from_slice(value.as_bytes());
+        let _ = write!(command, " -H {}", quote(&header));
+    }
+
+    if let Some(body) = request.body() {
+        let bytes = body.as_bytes().ok_or_else(|| {
+            PyValueError::new_err(
+                "Streaming request bodies cannot be rendered as a curl command, \
+                 use `stream_mode=\"buffered\"` or pass the body as bytes",
+            )
+        })?;
+        let _ = write!(command, " --data-binary {}", quote(bytes));
+    }
+
+    match proxy.map(|proxy| &proxy.1) {
+        Some(ProxyTarget::Url { url, basic_auth }) => {
+            let _ = write!(command, " --proxy {}", quote(url.as_bytes()));
+            if let Some((username, password)) = basic_auth {
+                let credentials = format!("{username}:{password}");
+                let _ = write!(command, " --proxy-user {}", quote(credentials.as_bytes()));
+            }
+        }
+        Some(ProxyTarget::Unix(path)) => {
+            let _ = write!(command, " --unix-socket {}", quote(path.as_bytes()));
+        }
+        None => {}
+    }
+
+    if insecure {
+        command.push_str(" --insecure");
+    }
+
+    let _ = write!(command, " {}", quote(request.uri().to_string().as_bytes()));
+    Ok(command)
+}
+
+/// Quote bytes as a single POSIX shell word.
+///
+/// Printable text is single-quoted; anything else falls back to ANSI-C `$'...'` quoting so
+/// binary bodies survive copy and paste.
+fn quote(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) if !text.chars().any(char::is_control) => {
+            format!("'{}'", text.replace('\'', r"'\''"))
+        }
+        _ => {
+            let mut quoted = String::from("$'");
+            for &byte in bytes {
+                match byte {
+                    b'\\' => quoted.push_str(r"\\"),
+                    b'\'' => quoted.push_str(r"\'"),
+                    b'\n' => quoted.push_str(r"\n"),
+                    b'\r' => quoted.push_str(r"\r"),
+                    b'\t' => quoted.push_str(r"\t"),
+                    0x20..=0x7e => quoted.push(byte as char),
+                    _ => {
+                        let _ = write!(quoted, "\\x{byte:02x}");
+                    }
+                }
+            }
+            quoted.push('\'');
+            quoted
+        }
+    }
+}
@@ -0,0 +1,291 @@
+//! Outbound request guards.
+//!
+//! Checks applied to the target of a request before any connection is made, so applications
+//! fetching user-supplied URLs can restrict where the client is allowed to go.
+
+use std::{
+    fmt,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::Arc,
+};
+
+use pyo3::{exceptions::PyValueError, prelude::*, pybacked::PyBackedStr};
+
+use crate::error::Error;
+
+/// A list of hosts a client may send requests to.
+///
+/// Each entry is an exact host (`example.com`), a wildcard matching any subdomain
+/// (`*.example.com`), an IP address, or a CIDR range (`10.0.0.0/8`) matching IP literal hosts.
+pub struct AllowedHosts(Vec<HostPattern>);
+
+enum HostPattern {
+    Exact(String),
+    Wildcard(String),
+    Network(IpAddr, u8),
+}
+
+/// The error returned when a request would connect to a private network address.
+#[derive(Debug)]
+pub struct PrivateNetwork(pub IpAddr);
+
+/// The error returned when a redirect leads to a host outside the allowlist.
+#[derive(Debug)]
+pub struct DisallowedHost(pub String);
+
+/// The guards of the request currently in flight, applied to every redirect hop.
+#[derive(Clone)]
+struct Guards {
+    block_private_networks: bool,
+    allowed_hosts: Option<Arc<AllowedHosts>>,
+}
+
+tokio::task_local! {
+    /// The guards of the request currently in flight.
+    static GUARDS: Guards;
+}
+
+// ===== impl AllowedHosts =====
+
+impl AllowedHosts {
+    /// Check that the host of the given URL is allowed.
+    ///
+    /// URLs without a host are let through, so the request fails with its usual error.
+    pub fn check(&self, url: &str) -> Result<(), Error> {
+        match url.parse::<http::Uri>() {
+            Ok(uri) => self
+                .check_uri(&uri)
+                .map_err(|DisallowedHost(host)| Error::HostNotAllowed(host)),
+            Err(_) => Ok(()),
+        }
+    }
+
+    /// Check that the host of the given URI is allowed.
+    fn check_uri(&self, uri: &http::Uri) -> Result<(), DisallowedHost> {
+        let Some(host) = uri.host() else {
+            return Ok(());
+        };
+
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        let ip = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>()
+            .ok();
+
+        let allowed = self.0.iter().any(|pattern| match pattern {
+            HostPattern::Exact(exact) => *exact == host,
+            HostPattern::Wildcard(suffix) => host
+                .strip_suffix(suffix.as_str())
+                .is_some_and(|rest| rest.ends_with('.')),
+            HostPattern::Network(network, prefix) => {
+                ip.is_some_and(|ip| contains(*network, *prefix, ip))
+            }
+        });
+
+        if allowed {
+            Ok(())
+        } else {
+            Err(DisallowedHost(host))
+        }
+    }
+}
+
+impl FromPyObject<'_, '_> for AllowedHosts {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<PyAny>) -> PyResult<Self> {
+        ob.extract::<Vec<PyBackedStr>>()?
+            .iter()
+            .map(|pattern| HostPattern::parse(pattern))
+            .collect::<PyResult<_>>()
+            .map(AllowedHosts)
+    }
+}
+
+// ===== impl HostPattern =====
+
+impl HostPattern {
+    fn parse(pattern: &str) -> PyResult<Self> {
+        let pattern = pattern.trim().trim_end_matches('.').to_ascii_lowercase();
+
+        if let Some((network, prefix)) = pattern.split_once('/') {
+            let network = network
+                .parse::<IpAddr>()
+                .map_err(|_| PyValueError::new_err(format!("Invalid CIDR range: {pattern}")))?;
+            let max = if network.is_ipv4() { 32 } else { 128 };
+            return match prefix.parse::<u8>() {
+                Ok(prefix) if prefix <= max => Ok(HostPattern::Network(network, prefix)),
+                _ => Err(PyValueError::new_err(format!(
+                    "Invalid CIDR prefix length: {pattern}"
+                ))),
+            };
+        }
+
+        let trimmed = pattern.trim_start_matches('[').trim_end_matches(']');
+        if let Ok(ip) = trimmed.parse::<IpAddr>() {
+            let prefix = if ip.is_ipv4() { 32 } else { 128 };
+            return Ok(HostPattern::Network(ip, prefix));
+        }
+
+        match pattern.strip_prefix("*.") {
+            Some(suffix) => Ok(HostPattern::Wildcard(suffix.to_owned())),
+            None => Ok(HostPattern::Exact(pattern)),
+        }
+    }
+}
+
+// ===== impl PrivateNetwork =====
+
+impl fmt::Display for PrivateNetwork {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "private network address is not allowed: {}", self.0)
+    }
+}
+
+impl std::error::Error for PrivateNetwork {}
+
+// ===== impl DisallowedHost =====
+
+impl fmt::Display for DisallowedHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "host is not allowed: {}", self.0)
+    }
+}
+
+impl std::error::Error for DisallowedHost {}
+
+// ===== private networks =====
+
+/// Whether `ip` is a loopback, private (RFC 1918 / RFC 4193), link-local, shared, or
+/// unspecified address. Link-local covers cloud metadata endpoints such as `169.254.169.254`.
+///
+/// IPv6 addresses that carry an IPv4 address for a translator to reach (IPv4-mapped, NAT64
+/// and 6to4) are judged by that IPv4 address.
+pub fn is_private(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_private_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_private_v4(ip),
+            None => is_private_v6(ip),
+        },
+    }
+}
+
+fn is_private_v4(ip: Ipv4Addr) -> bool {
+    // "This network" (`0.0.0.0/8`), which some stacks route to the local host.
+    ip.octets()[0] == 0
+        || ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || contains(IpAddr::V4(Ipv4Addr::new(100, 64, 0, 0)), 10, IpAddr::V4(ip))
+}
+
+fn is_private_v6(ip: Ipv6Addr) -> bool {
+    if let Some(ip) = translated_v4(ip) {
+        return is_private_v4(ip);
+    }
+    let segments = ip.segments();
+    ip.is_loopback()
+        || ip.is_unspecified()
+        || (segments[0] & 0xfe00) == 0xfc00
+        || (segments[0] & 0xffc0) == 0xfe80
+        // Local-use NAT64 (`64:ff9b:1::/48`), whose IPv4 embedding is up to the operator.
+        || segments[..3] == [0x64, 0xff9b, 1]
+}
+
+/// The IPv4 address a well-known NAT64 (`64:ff9b::/96`) or 6to4 (`2002::/16`) address
+/// reaches once translated.
+fn translated_v4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    let [_, _, a, b, c, d, ..] = ip.octets();
+    match ip.segments() {
+        [0x64, 0xff9b, 0, 0, 0, 0, ..] => Some(Ipv4Addr::from_bits(ip.to_bits() as u32)),
+        [0x2002, ..] => Some(Ipv4Addr::new(a, b, c, d)),
+        _ => None,
+    }
+}
+
+/// Refuse the URL if its host is an IP literal in a private network.
+///
+/// Hostnames are checked by the DNS resolver once they are resolved, which also covers
+/// redirect targets and DNS rebinding.
+pub fn check_private(url: &str) -> Result<(), Error> {
+    let ip = url
+        .parse::<http::Uri>()
+        .ok()
+        .and_then(|uri| literal_ip(&uri));
+    match ip {
+        Some(ip) if is_private(ip) => Err(Error::PrivateNetwork(ip)),
+        _ => Ok(()),
+    }
+}
+
+/// Refuse a redirect hop the request in flight may not follow: one to a host outside its
+/// allowlist, or to a private IP literal if it blocks private networks.
+pub fn check_redirect(uri: &http::Uri) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let Ok(guards) = GUARDS.try_with(Guards::clone) else {
+        return Ok(());
+    };
+    if let Some(allowed_hosts) = &guards.allowed_hosts {
+        allowed_hosts.check_uri(uri)?;
+    }
+    match literal_ip(uri) {
+        Some(ip) if guards.block_private_networks && is_private(ip) => {
+            Err(PrivateNetwork(ip).into())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Drive a request future, refusing redirects to hosts outside `allowed_hosts` and, when
+/// `block_private_networks` is set, to private IP literals.
+pub async fn scope<F: Future>(
+    block_private_networks: bool,
+    allowed_hosts: Option<Arc<AllowedHosts>>,
+    fut: F,
+) -> F::Output {
+    let guards = Guards {
+        block_private_networks,
+        allowed_hosts,
+    };
+    GUARDS.scope(guards, fut).await
+}
+
+/// Find the guard that refused the request behind the given error, if any.
+pub fn refusal(err: &(dyn std::error::Error + 'static)) -> Option<Error> {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(PrivateNetwork(ip)) = err.downcast_ref::<PrivateNetwork>() {
+            return Some(Error::PrivateNetwork(*ip));
+        }
+        if let Some(DisallowedHost(host)) = err.downcast_ref::<DisallowedHost>() {
+            return Some(Error::HostNotAllowed(host.clone()));
+        }
+        source = err.source();
+    }
+    None
+}
+
+fn literal_ip(uri: &http::Uri) -> Option<IpAddr> {
+    uri.host()?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+        .ok()
+}
+
+/// Whether `ip` lies in the network `network/prefix`.
+fn contains(network: IpAddr, prefix: u8, ip: IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix as u32).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix as u32).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use pin_project_lite::pin_project;
@@ -10,9 +11,12 @@ use pyo3::{
     exceptions::{PyRuntimeError, asyncio::CancelledError},
     prelude::*,
 };
-use tokio::task::JoinHandle;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
 use tokio_util::sync::CancellationToken;
 
+/// How often a blocking call wakes up to check for pending Python signals.
+const SIGNAL_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
 pin_project! {
     /// A future that allows Python threads to run while it is being polled or executed.
     /// It also handles cancellation and spawns the task in tokio runtime.
@@ -80,3 +84,28 @@ where
         })
     }
 }
+
+/// Block the current thread on a future without holding the GIL.
+///
+/// Unlike a plain `block_on`, the future is raced against a periodic check of Python signals,
+/// so a blocking call can be interrupted with Ctrl-C. When a signal handler raises (e.g.
+/// `KeyboardInterrupt`), the future is dropped, which aborts the in-flight operation.
+pub fn block_on<Fut, T>(py: Python, fut: Fut) -> PyResult<T>
+where
+    Fut: Future<Output = PyResult<T>> + Send,
+    T: Send,
+{
+    py.detach(|| {
+        pyo3_async_runtimes::tokio::get_runtime().block_on(async {
+            tokio::pin!(fut);
+            let mut interval = tokio::time::interval(SIGNAL_CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            loop {
+                tokio::select! {
+                    result = &mut fut => return result,
+                    _ = interval.tick() => Python::attach(|py| py.check_signals())?,
+                }
+            }
+        })
+    })
+}
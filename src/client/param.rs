@@ -71,6 +71,22 @@ pub enum ParamValue {
     String(PyBackedStr),
 }
 
+impl Params {
+    /// The key-value pairs, in order.
+    pub fn pairs(&self) -> Vec<(&str, &ParamValue)> {
+        match self {
+            Params::Map(map) => map
+                .iter()
+                .map(|(key, value)| (key.as_ref(), value))
+                .collect(),
+            Params::List(vec) => vec
+                .iter()
+                .map(|(key, value)| (key.as_ref(), value))
+                .collect(),
+        }
+    }
+}
+
 impl Serialize for ParamValue {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
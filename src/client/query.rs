@@ -1,2 +1,18 @@
+use super::param::ParamValue;
+
 /// Alias for query parameters.
 pub type Query = super::param::Params;
+
+/// The pairs of the client's default query that a request's own query does not override.
+///
+/// A key set by the request replaces all of its default values, while repeated keys are
+/// otherwise kept as they are on either side.
+pub fn defaults<'a>(defaults: &'a Query, query: Option<&Query>) -> Vec<(&'a str, &'a ParamValue)> {
+    defaults
+        .pairs()
+        .into_iter()
+        .filter(|(key, _)| {
+            query.is_none_or(|query| query.pairs().iter().all(|(other, _)| other != key))
+        })
+        .collect()
+}
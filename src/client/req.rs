@@ -1,27 +1,40 @@
 use std::{
     net::{IpAddr, Ipv4Addr, Ipv6Addr},
-    time::Duration,
+    sync::{Arc, Mutex, atomic::AtomicU64},
+    time::{Duration, Instant},
 };
 
+use bytes::Bytes;
 use futures_util::TryFutureExt;
-use http::header::COOKIE;
-use pyo3::{PyResult, prelude::*, pybacked::PyBackedStr};
+use http::{
+    HeaderName, HeaderValue,
+    header::{ACCEPT_ENCODING, CONTENT_ENCODING, CONTENT_TYPE, COOKIE, HOST},
+};
+use pyo3::{PyResult, exceptions::PyValueError, prelude::*, pybacked::PyBackedStr};
 
 use crate::{
     client::{
-        Client,
-        body::{Body, Form, Json, multipart::Multipart},
-        query::Query,
-        resp::{Response, WebSocket},
+        Client, Variant,
+        body::{Body, Form, Json, StreamMode, multipart::Multipart},
+        curl, guard,
+        query::{self, Query},
+        resp::{
+            Response, Timings, WebSocket, limit,
+            wire::{self, Codings},
+        },
+        retry::Retry,
     },
     cookie::{Cookies, Jar},
+    dns::{self, HostOverrides},
     emulate::EmulationLike,
     error::Error,
     extractor::Extractor,
-    header::{HeaderMap, OrigHeaderMap},
+    header::{Accept, HeaderMap, OrigHeaderMap},
     http::{Method, Version},
+    http2::StreamDependency,
     proxy::Proxy,
     redirect,
+    tls::{AlpnProtocol, AlpsProtocol},
 };
 
 /// The parameters for a request.
@@ -31,8 +44,8 @@ pub struct Request {
     /// The Emulation settings for the request.
     emulation: Option<EmulationLike>,
 
-    /// The proxy to use for the request.
-    proxy: Option<Proxy>,
+    /// The proxy to use for the request, or a pool to pick one from.
+    proxy: Option<ProxyChoice>,
 
     /// Bind to a local IP Address.
     local_address: Option<IpAddr>,
@@ -43,8 +56,8 @@ pub struct Request {
     /// Bind to an interface by `SO_BINDTODEVICE`.
     interface: Option<String>,
 
-    /// The timeout to use for the request.
-    timeout: Option<Duration>,
+    /// The timeout to use for the request, or a `(connect, read)` tuple.
+    timeout: Option<Timeout>,
 
     /// The read timeout to use for the request.
     read_timeout: Option<Duration>,
@@ -52,9 +65,30 @@ pub struct Request {
     /// The HTTP version to use for the request.
     version: Option<Version>,
 
+    /// The HTTP/2 stream dependency sent in the HEADERS frame of the request.
+    stream_priority: Option<StreamDependency>,
+
+    /// The ALPN protocols offered for the request, in order.
+    alpn: Option<Vec<AlpnProtocol>>,
+
+    /// The ALPS protocols offered for the request, in order.
+    alps: Option<Vec<AlpsProtocol>>,
+
+    /// Whether to send GREASE values in the TLS handshake of the request.
+    grease_enabled: Option<bool>,
+
+    /// Whether to send an ECH GREASE extension in the TLS handshake of the request.
+    enable_ech_grease: Option<bool>,
+
+    /// Whether to verify the server certificate, overriding the client's `tls_verify`.
+    tls_verify: Option<bool>,
+
     /// The headers to use for the request.
     headers: Option<HeaderMap>,
 
+    /// The `Accept` header to use for the request, unless set explicitly in `headers`.
+    accept: Option<Accept>,
+
     /// The original headers to use for the request.
     orig_headers: Option<OrigHeaderMap>,
 
@@ -70,6 +104,9 @@ pub struct Request {
     /// The cookie provider to use for the request.
     cookie_provider: Option<Jar>,
 
+    /// Whether to store the request cookies in the cookie provider for later requests.
+    persist_cookies: Option<bool>,
+
     /// Sets gzip as an accepted encoding.
     gzip: Option<bool>,
 
@@ -82,6 +119,9 @@ pub struct Request {
     /// Sets zstd as an accepted encoding.
     zstd: Option<bool>,
 
+    /// Whether to decompress the response body, `False` keeping it as received.
+    decompress: Option<bool>,
+
     /// The authentication to use for the request.
     auth: Option<PyBackedStr>,
 
@@ -105,6 +145,39 @@ pub struct Request {
 
     /// The body to use for the request.
     body: Option<Body>,
+
+    /// The streaming body piped from another response, with its `Content-Type`.
+    piped: Option<(wreq::Body, Option<HeaderValue>)>,
+
+    /// Whether a streaming body is sent chunked or buffered with a `Content-Length`.
+    stream_mode: Option<StreamMode>,
+
+    /// Whether to attach the finalized request to the response.
+    return_sent: Option<bool>,
+
+    /// Whether to fall back to the raw body when automatic decompression fails.
+    decode_fallback: Option<bool>,
+
+    /// The largest response body, in bytes, that may be read.
+    max_body_size: Option<u64>,
+
+    /// A callable handed the parsed JSON body, which may raise to reject the response.
+    validator: Option<Py<PyAny>>,
+
+    /// The retry policy for transient failures.
+    retry: Option<Retry>,
+
+    /// The number of times the request is retried.
+    max_retries: Option<u32>,
+
+    /// The delay before the first retry, doubled for each further one.
+    retry_backoff: Option<Duration>,
+
+    /// The response statuses that are retried.
+    retry_on_status: Option<Vec<u16>>,
+
+    /// The addresses pinned for host names, taking precedence over the client's.
+    resolve: Option<HostOverrides>,
 }
 
 /// The parameters for a WebSocket request.
@@ -209,6 +282,56 @@ pub struct WebSocketRequest {
     accept_unmasked_frames: Option<bool>,
 }
 
+/// A request timeout, either for the whole request or split into `(connect, read)` phases.
+#[derive(FromPyObject)]
+pub enum Timeout {
+    Total(Duration),
+    Split(Duration, Duration),
+}
+
+/// A request's proxy, either a single one or a pool to pick one from for each call.
+#[derive(FromPyObject)]
+pub enum ProxyChoice {
+    One(Proxy),
+    Pool(Vec<Proxy>),
+}
+
+/// A request ready to be sent, along with the options that apply once it is sent.
+struct Prepared {
+    /// The client the request is sent with.
+    client: Client,
+    builder: wreq::RequestBuilder,
+    return_sent: bool,
+    decode_fallback: bool,
+    /// Whether automatic decompression is turned off.
+    undecoded: bool,
+    /// The codings the body is decoded from once counted, unless it is kept undecoded.
+    codings: Option<Codings>,
+    max_body_size: Option<u64>,
+    validator: Option<Arc<Py<PyAny>>>,
+    /// The time the whole request, retries included, may take.
+    timeout: Option<Duration>,
+    /// The port the request is sent to, for port-scoped host overrides.
+    port: Option<u16>,
+    /// The addresses pinned for host names by the request.
+    overrides: Option<Arc<HostOverrides>>,
+    proxy: Option<Proxy>,
+    retry: Option<Retry>,
+}
+
+/// The request as built from its options, before the client dispatches it.
+///
+/// The headers are those of the built request only: the client adds its default and
+/// emulation headers, cookies and `Host` on dispatch, and applies header casing and ordering
+/// as it writes the request.
+#[derive(Clone)]
+#[pyclass(subclass, str, frozen, skip_from_py_object)]
+pub struct SentRequest {
+    method: http::Method,
+    uri: http::Uri,
+    headers: http::HeaderMap,
+}
+
 // ===== impl Request =====
 
 impl FromPyObject<'_, '_> for Request {
@@ -226,30 +349,169 @@ impl FromPyObject<'_, '_> for Request {
         extract_option!(ob, request, read_timeout);
 
         extract_option!(ob, request, version);
+        extract_option!(ob, request, stream_priority);
+        extract_option!(ob, request, alpn);
+        extract_option!(ob, request, alps);
+        extract_option!(ob, request, grease_enabled);
+        extract_option!(ob, request, enable_ech_grease);
+        extract_option!(ob, request, tls_verify);
         extract_option!(ob, request, headers);
+        extract_option!(ob, request, accept);
         extract_option!(ob, request, orig_headers);
         extract_option!(ob, request, default_headers);
         extract_option!(ob, request, cookies);
         extract_option!(ob, request, redirect);
         extract_option!(ob, request, cookie_provider);
+        extract_option!(ob, request, persist_cookies);
         extract_option!(ob, request, auth);
         extract_option!(ob, request, bearer_auth);
         extract_option!(ob, request, basic_auth);
         extract_option!(ob, request, query);
         extract_option!(ob, request, form);
-        extract_option!(ob, request, json);
         extract_option!(ob, request, body);
+        // A custom serializer turns `json` into the body in Python, the Rust path is kept
+        // for everything else.
+        let json_dumps = ob
+            .get_item(pyo3::intern!(ob.py(), "json_dumps"))
+            .ok()
+            .filter(|dumps| !dumps.is_none());
+        match json_dumps {
+            Some(dumps) => {
+                if let Ok(json) = ob.get_item(pyo3::intern!(ob.py(), "json")) {
+                    if request.body.is_some() {
+                        return Err(PyValueError::new_err(
+                            "`json` and `body` cannot both be set",
+                        ));
+                    }
+                    request.body = Some(dumps.call1((json,))?.extract()?);
+                    request = request.with_default_header(
+                        CONTENT_TYPE,
+                        HeaderValue::from_static("application/json"),
+                    );
+                }
+            }
+            None => extract_option!(ob, request, json),
+        }
+        extract_option!(ob, request, stream_mode);
         extract_option!(ob, request, multipart);
 
         extract_option!(ob, request, gzip);
         extract_option!(ob, request, brotli);
         extract_option!(ob, request, deflate);
         extract_option!(ob, request, zstd);
+        extract_option!(ob, request, decompress);
+
+        extract_option!(ob, request, return_sent);
+        extract_option!(ob, request, retry);
+        extract_option!(ob, request, max_retries);
+        extract_option!(ob, request, retry_backoff);
+        extract_option!(ob, request, retry_on_status);
+        extract_option!(ob, request, resolve);
+        extract_option!(ob, request, decode_fallback);
+        extract_option!(ob, request, max_body_size);
+        extract_option!(ob, request, validator);
 
         Ok(request)
     }
 }
 
+impl Request {
+    /// Send a streaming body piped from another response, defaulting to its `Content-Type`.
+    pub fn with_piped_body(
+        mut self,
+        body: wreq::Body,
+        content_type: Option<HeaderValue>,
+    ) -> PyResult<Request> {
+        if self.body.is_some()
+            || self.json.is_some()
+            || self.form.is_some()
+            || self.multipart.is_some()
+        {
+            return Err(PyValueError::new_err(
+                "A piped request cannot also set `body`, `json`, `form` or `multipart`",
+            ));
+        }
+        self.piped = Some((body, content_type));
+        Ok(self)
+    }
+
+    /// Set a header, replacing the value the request gives it.
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Request {
+        self.headers
+            .get_or_insert_with(|| HeaderMap(http::HeaderMap::with_capacity(1)))
+            .0
+            .insert(name, value);
+        self
+    }
+
+    /// Set a header unless the request gives it a value already.
+    pub fn with_default_header(mut self, name: HeaderName, value: HeaderValue) -> Request {
+        self.headers
+            .get_or_insert_with(|| HeaderMap(http::HeaderMap::with_capacity(1)))
+            .0
+            .entry(name)
+            .or_insert(value);
+        self
+    }
+}
+
+// ===== impl SentRequest =====
+
+impl From<&wreq::Request> for SentRequest {
+    fn from(request: &wreq::Request) -> Self {
+        SentRequest {
+            method: request.method().clone(),
+            uri: request.uri().clone(),
+            headers: request.headers().clone(),
+        }
+    }
+}
+
+#[pymethods]
+impl SentRequest {
+    /// Get the method of the sent request.
+    #[getter]
+    fn method(&self) -> &str {
+        self.method.as_str()
+    }
+
+    /// Get the URL of the sent request.
+    #[getter]
+    fn url(&self) -> String {
+        self.uri.to_string()
+    }
+
+    /// Get the headers set on the request itself, with lowercased names.
+    #[getter]
+    fn headers(&self) -> HeaderMap {
+        HeaderMap(self.headers.clone())
+    }
+}
+
+impl std::fmt::Display for SentRequest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<SentRequest({} {})>", self.method, self.uri)
+    }
+}
+
+// ===== impl ProxyChoice =====
+
+impl ProxyChoice {
+    /// Pick the proxy for one call, drawing uniformly from a pool with the client's generator.
+    fn pick(self, rng: &Mutex<fastrand::Rng>) -> PyResult<Proxy> {
+        match self {
+            ProxyChoice::One(proxy) => Ok(proxy),
+            ProxyChoice::Pool(mut pool) => {
+                if pool.is_empty() {
+                    return Err(PyValueError::new_err("`proxy` must not be an empty list"));
+                }
+                let index = rng.lock().map_or(0, |mut rng| rng.usize(..pool.len()));
+                Ok(pool.swap_remove(index))
+            }
+        }
+    }
+}
+
 // ===== impl WebSocketRequest =====
 
 impl FromPyObject<'_, '_> for WebSocketRequest {
@@ -284,19 +546,188 @@ impl FromPyObject<'_, '_> for WebSocketRequest {
     }
 }
 
-pub async fn execute_request<U>(
-    client: Client,
+/// Build a request from the given options, checking its target against the client's guards.
+async fn prepare_request(
+    client: &Client,
     method: Method,
-    url: U,
+    url: &str,
     request: Option<Request>,
-) -> PyResult<Response>
-where
-    U: AsRef<str>,
-{
+) -> PyResult<Prepared> {
+    let url = &*client.resolve_url(url)?;
+
+    // Refuse hosts outside the allowlist before anything is sent.
+    if let Some(allowed_hosts) = &client.allowed_hosts {
+        allowed_hosts.check(url)?;
+    }
+    if client.block_private_networks {
+        guard::check_private(url)?;
+        // A proxy resolves the target itself, out of reach of the resolver's check.
+        if request.as_ref().is_some_and(|request| request.proxy.is_some()) {
+            return Err(PyValueError::new_err(
+                "`proxy` cannot be combined with `block_private_networks`",
+            ));
+        }
+    }
+
+    // Certificate verification and connect timeouts are fixed per client, so send with a
+    // derived one when overridden. Pinned addresses are only looked up for new connections,
+    // so requests pinning any are kept out of the connection pool.
+    let client = &match &request {
+        Some(request) => client.variant(Variant {
+            tls_verify: request.tls_verify,
+            unpooled: request.resolve.is_some(),
+            connect_timeout: match request.timeout {
+                Some(Timeout::Split(connect, _)) => Some(connect),
+                _ => None,
+            },
+        })?,
+        None => client.clone(),
+    };
+
+    let mut retry = client.retry.clone();
+    let mut timeout = client.timeout;
+
     // Create the request builder.
-    let mut builder = client.inner.request(method.into_ffi(), url.as_ref());
+    let mut builder = client.inner.get()?.request(method.into_ffi(), url);
+
+    // The client's default query goes first, minus the keys the request sets itself.
+    if let Some(default_query) = &client.default_query {
+        let own = request.as_ref().and_then(|request| request.query.as_ref());
+        builder = builder.query(&query::defaults(default_query, own));
+    }
+
+    // Host-specific emulation, overridden by any per-request emulation below.
+    let host_emulation = host_emulation(client, url);
+    let mut accept_encoding = client.accept_encoding
+        || host_emulation
+            .as_ref()
+            .is_some_and(EmulationLike::sets_accept_encoding);
+    let tls_profile =
+        client.tls_profile || host_emulation.as_ref().is_some_and(EmulationLike::has_tls);
+    let http2_profile =
+        client.http2_profile || host_emulation.as_ref().is_some_and(EmulationLike::has_http2);
+    if let Some(emulation) = host_emulation {
+        builder = builder.emulation(emulation);
+    }
+
+    // Raw mode: leave the header set to the caller, unless the request opts back in below.
+    if client.raw_request {
+        check_raw_host(request.as_ref())?;
+        builder = builder
+            .default_headers(false)
+            .gzip(false)
+            .brotli(false)
+            .deflate(false)
+            .zstd(false);
+    }
+    let mut return_sent = false;
+    let mut decode_fallback = false;
+    let mut undecoded = client.raw_request;
+    let mut codings = client.codings;
+    let mut max_body_size = client.max_body_size;
+    let mut validator = None;
+    let mut proxy = None;
+    let mut overrides = None;
 
     if let Some(mut request) = request {
+        return_sent = request.return_sent.unwrap_or_default();
+        retry = Retry::for_request(
+            client.retry.as_ref(),
+            request.retry.take(),
+            request.max_retries.take(),
+            request.retry_backoff.take(),
+            request.retry_on_status.take(),
+        );
+        // Falling back needs the body as received, so the client leaves it undecoded and it
+        // is decoded once read. A body that is undecoded anyway needs none of this.
+        decode_fallback = request.decode_fallback.unwrap_or_default()
+            && !undecoded
+            && request.decompress != Some(false);
+        if decode_fallback {
+            request = request.with_default_header(
+                ACCEPT_ENCODING,
+                HeaderValue::from_static("gzip, deflate, br, zstd"),
+            );
+        }
+        accept_encoding |= request
+            .emulation
+            .as_ref()
+            .is_some_and(EmulationLike::sets_accept_encoding)
+            || request
+                .headers
+                .as_ref()
+                .is_some_and(|headers| headers.0.contains_key(ACCEPT_ENCODING));
+        max_body_size = request.max_body_size.or(max_body_size);
+        validator = request.validator.take().map(Arc::new);
+        overrides = request.resolve.take().map(Arc::new);
+        // HTTP/2 stream priority, protocol negotiation and GREASE options.
+        let tls_overrides = request.alpn.is_some()
+            || request.alps.is_some()
+            || request.grease_enabled.is_some()
+            || request.enable_ech_grease.is_some();
+        if request.stream_priority.is_some() || tls_overrides {
+            if request.emulation.is_some() {
+                return Err(PyValueError::new_err(
+                    "`stream_priority`, `alpn`, `alps`, `grease_enabled` and `enable_ech_grease` \
+                     cannot be combined with a per-request `emulation`",
+                ));
+            }
+            // The options are sent as a profile of their own, which would replace the rest
+            // of the fingerprint in use rather than change one field of it.
+            if request.stream_priority.is_some() && http2_profile {
+                return Err(PyValueError::new_err(
+                    "`stream_priority` cannot be set per request when the client or host \
+                     emulation sends an HTTP/2 profile, set `headers_stream_dependency` in the \
+                     client's `http2_options`",
+                ));
+            }
+            if tls_overrides && tls_profile {
+                return Err(PyValueError::new_err(
+                    "`alpn`, `alps`, `grease_enabled` and `enable_ech_grease` cannot be set per \
+                     request when the client or host emulation sends a TLS profile, set them \
+                     in the client's `tls_options`",
+                ));
+            }
+            let mut emulation = wreq::Emulation::builder();
+            if let Some(dependency) = request.stream_priority.take() {
+                let http2_options = wreq::http2::Http2Options::builder()
+                    .headers_stream_dependency(dependency.0)
+                    .build();
+                emulation = emulation.http2_options(http2_options);
+            }
+            if tls_overrides {
+                let mut tls_options = wreq::tls::TlsOptions::builder();
+                apply_option!(
+                    set_if_some_map,
+                    tls_options,
+                    request.alpn,
+                    alpn_protocols,
+                    |v: Vec<_>| v.into_iter().map(AlpnProtocol::into_ffi)
+                );
+                apply_option!(
+                    set_if_some_map,
+                    tls_options,
+                    request.alps,
+                    alps_protocols,
+                    |v: Vec<_>| v.into_iter().map(AlpsProtocol::into_ffi)
+                );
+                apply_option!(
+                    set_if_some,
+                    tls_options,
+                    request.grease_enabled,
+                    grease_enabled
+                );
+                apply_option!(
+                    set_if_some,
+                    tls_options,
+                    request.enable_ech_grease,
+                    enable_ech_grease
+                );
+                emulation = emulation.tls_options(tls_options.build());
+            }
+            builder = builder.emulation(emulation.build());
+        }
+
         // Emulation options.
         apply_option!(set_if_some, builder, request.emulation, emulation);
 
@@ -310,11 +741,33 @@ where
         );
 
         // Timeout options.
-        apply_option!(set_if_some, builder, request.timeout, timeout);
+        match request.timeout.take() {
+            Some(Timeout::Total(total)) => {
+                timeout = Some(total);
+                builder = builder.timeout(total);
+            }
+            Some(Timeout::Split(_, read)) => {
+                if request.read_timeout.is_some() {
+                    return Err(PyValueError::new_err(
+                        "`read_timeout` cannot be combined with a `(connect, read)` timeout",
+                    ));
+                }
+                // The connect timeout is applied by the client derived for it above.
+                request.read_timeout = Some(read);
+            }
+            None => {}
+        }
         apply_option!(set_if_some, builder, request.read_timeout, read_timeout);
 
         // Network options.
-        apply_option!(set_if_some_inner, builder, request.proxy, proxy);
+        proxy = request
+            .proxy
+            .take()
+            .map(|proxy| proxy.pick(&client.proxy_rng))
+            .transpose()?;
+        if let Some(proxy) = &proxy {
+            builder = builder.proxy(proxy.0.clone());
+        }
         apply_option!(set_if_some, builder, request.local_address, local_address);
         apply_option!(
             set_if_some_tuple_inner,
@@ -338,6 +791,17 @@ where
         apply_option!(set_if_some, builder, request.interface, interface);
 
         // Headers options.
+        if let Some(accept) = request.accept.take() {
+            accept.apply_default(&mut request.headers);
+        }
+        let content_type = request
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.0.get(CONTENT_TYPE).cloned());
+        let encoded = request
+            .headers
+            .as_ref()
+            .is_some_and(|headers| headers.0.contains_key(CONTENT_ENCODING));
         apply_option!(set_if_some_inner, builder, request.headers, headers);
         apply_option!(
             set_if_some_inner,
@@ -353,6 +817,21 @@ where
         );
 
         // Cookies options.
+        if request.persist_cookies.unwrap_or_default() {
+            if let Some(cookies) = &request.cookies {
+                let jar = request
+                    .cookie_provider
+                    .as_ref()
+                    .or(client.cookie_jar.as_ref())
+                    .ok_or_else(|| {
+                        PyValueError::new_err(
+                            "persist_cookies requires a cookie store, \
+                             set `cookie_store=True` or pass a `cookie_provider`",
+                        )
+                    })?;
+                persist_cookies(jar, cookies, url);
+            }
+        }
         apply_option!(
             set_if_some_iter_inner_with_key,
             builder,
@@ -382,15 +861,47 @@ where
         apply_option!(set_if_some_inner, builder, request.redirect, redirect);
 
         // Compression options.
-        apply_option!(set_if_some, builder, request.gzip, gzip);
-        apply_option!(set_if_some, builder, request.brotli, brotli);
-        apply_option!(set_if_some, builder, request.deflate, deflate);
-        apply_option!(set_if_some, builder, request.zstd, zstd);
+        codings = Codings {
+            gzip: request.gzip.unwrap_or(codings.gzip),
+            brotli: request.brotli.unwrap_or(codings.brotli),
+            deflate: request.deflate.unwrap_or(codings.deflate),
+            zstd: request.zstd.unwrap_or(codings.zstd),
+        };
+        if request.decompress == Some(false) {
+            undecoded = true;
+        }
 
         // Query options.
         apply_option!(set_if_some_ref, builder, request.query, query);
 
         // Body options.
+        if client.request_compression.is_some() && !encoded {
+            // Serialize bodies of known size here so they can be compressed.
+            let sized = match (
+                request.body.take(),
+                request.json.take(),
+                request.form.take(),
+            ) {
+                (Some(Body::Stream(stream)), ..) => {
+                    request.body = Some(Body::Stream(stream));
+                    None
+                }
+                (Some(body), ..) => Some((body, None)),
+                (None, Some(json), _) => Some((Body::Json(json), Some("application/json"))),
+                (None, None, Some(form)) => {
+                    Some((Body::Form(form), Some("application/x-www-form-urlencoded")))
+                }
+                (None, None, None) => None,
+            };
+            if let Some((body, default_type)) = sized {
+                if let Ok(bytes) = body.into_bytes()? {
+                    builder = compress_body(client, builder, bytes).await?;
+                }
+                if let Some(default_type) = default_type.filter(|_| content_type.is_none()) {
+                    builder = builder.header(CONTENT_TYPE, HeaderValue::from_static(default_type));
+                }
+            }
+        }
         apply_option!(set_if_some_ref, builder, request.form, form);
         apply_option!(set_if_some_ref, builder, request.json, json);
         apply_option!(
@@ -399,6 +910,19 @@ where
             request.multipart.and_then(|form| form.form),
             multipart
         );
+        if request.stream_mode == Some(StreamMode::Buffered) {
+            // Drain streaming bodies up front so they are sent with a `Content-Length`.
+            if let Some(Body::Stream(stream)) =
+                request.body.take_if(|body| matches!(body, Body::Stream(_)))
+            {
+                let bytes = stream.collect_bytes().await?;
+                builder = if encoded {
+                    builder.body(bytes)
+                } else {
+                    compress_body(client, builder, bytes).await?
+                };
+            }
+        }
         apply_option!(
             set_if_some_map_try,
             builder,
@@ -406,22 +930,250 @@ where
             body,
             wreq::Body::try_from
         );
+        if let Some((body, piped_type)) = request.piped.take() {
+            builder = builder.body(body);
+            if let Some(piped_type) = piped_type.filter(|_| content_type.is_none()) {
+                builder = builder.header(CONTENT_TYPE, piped_type);
+            }
+        }
+
+        // An explicit `Content-Type` header always wins over the one inferred from the body.
+        if let Some(content_type) = content_type {
+            let mut headers = http::HeaderMap::with_capacity(1);
+            headers.insert(CONTENT_TYPE, content_type);
+            builder = builder.headers(headers);
+        }
     }
 
-    // Send request.
-    builder
-        .send()
-        .await
-        .and_then(|r| {
-            if client.raise_for_status {
-                r.error_for_status()
-            } else {
-                Ok(r)
+    // Bodies are decoded here once counted, so the client leaves them as received and the
+    // `Accept-Encoding` header it would send is sent in its place.
+    builder = builder.gzip(false).brotli(false).deflate(false).zstd(false);
+    let decoded = !undecoded && !decode_fallback && !codings.is_empty();
+    if decoded && !accept_encoding {
+        if let Some(value) = codings.accept_encoding() {
+            builder = builder.header(ACCEPT_ENCODING, value);
+        }
+    }
+
+    Ok(Prepared {
+        port: url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.port_or_known_default()),
+        client: client.clone(),
+        builder,
+        return_sent,
+        decode_fallback,
+        undecoded,
+        codings: decoded.then_some(codings),
+        max_body_size,
+        validator,
+        timeout,
+        overrides,
+        proxy,
+        // A policy only covers the methods it was configured for.
+        retry: retry.filter(|retry| retry.retries_method(&method.into_ffi())),
+    })
+}
+
+pub async fn execute_request<U>(
+    client: Client,
+    method: Method,
+    url: U,
+    request: Option<Request>,
+) -> PyResult<Response>
+where
+    U: AsRef<str>,
+{
+    let start = Instant::now();
+    let Prepared {
+        client,
+        mut builder,
+        return_sent,
+        decode_fallback,
+        undecoded,
+        codings,
+        max_body_size,
+        validator,
+        timeout,
+        port,
+        overrides,
+        retry,
+        ..
+    } = prepare_request(&client, method, url.as_ref(), request).await?;
+
+    // Re-send blocked requests with the client's fallback emulations, one after another.
+    let mut fallbacks = 0;
+    let (response, sent, timings) = loop {
+        let rotate = client.emulation_fallback.as_deref().and_then(|fallback| {
+            Some((fallback, fallback.get(fallbacks)?, builder.try_clone()?))
+        });
+
+        // Send the request, retrying transient failures while the policy allows it. Requests
+        // with streaming bodies cannot be replayed and are sent once.
+        let mut attempts = 1;
+        let (response, sent, timings) = loop {
+            let replay = retry
+                .as_ref()
+                .filter(|retry| retry.allows(attempts))
+                .and_then(|retry| Some((retry, builder.try_clone()?)));
+            // Each attempt only gets what is left of the request's overall timeout.
+            if let Some(timeout) = timeout {
+                builder = builder.timeout(timeout.saturating_sub(start.elapsed()));
             }
-        })
-        .map(Response::new)
-        .map_err(Error::Library)
-        .map_err(Into::into)
+            let result = dns::target(
+                port,
+                overrides.clone(),
+                send(&client, method, builder, return_sent),
+            )
+            .await;
+            let Some((retry, replay)) = replay else {
+                break result?;
+            };
+            let delay = match &result {
+                Ok((response, ..)) if retry.retries_status(response.status()) => {
+                    retry.delay(attempts, Some(response.headers()), timeout.is_some())
+                }
+                Err(err) if Retry::retries_error(err) => {
+                    retry.delay(attempts, None, timeout.is_some())
+                }
+                _ => break result?,
+            };
+            // Give up once waiting would overrun the request's overall timeout.
+            if timeout.is_some_and(|timeout| start.elapsed() + delay >= timeout) {
+                break result?;
+            }
+            // Release the connection before waiting.
+            drop(result);
+            tokio::time::sleep(delay).await;
+            builder = replay;
+            attempts += 1;
+        };
+
+        let Some((fallback, emulation, replay)) = rotate else {
+            break (response, sent, timings);
+        };
+        if !fallback.is_blocked(&response).await? {
+            break (response, sent, timings);
+        }
+        drop(response);
+        builder = replay.emulation(emulation);
+        fallbacks += 1;
+    };
+    if client.raise_for_status {
+        Error::for_status(response.uri(), response.status())?;
+    }
+    let wire_bytes = Arc::new(AtomicU64::new(0));
+    let response = wire::count(response, codings, wire_bytes.clone());
+    let response = match max_body_size {
+        Some(max_body_size) => limit::cap(response, max_body_size)?,
+        None => response,
+    };
+    let response = Response::new(response)
+        .with_method(method.into_ffi())
+        .with_elapsed(start.elapsed())
+        .with_timings(timings)
+        .with_sent_request(sent)
+        .with_undecoded(undecoded)
+        .with_wire_bytes(wire_bytes)
+        .with_validator(validator)
+        .with_buffer_limit(client.auto_buffer_limit)
+        .with_offload_decode(client.offload_decode)
+        .with_client(client.clone());
+
+    // The body arrives undecoded when falling back is allowed, so it is decoded here and
+    // kept as received if decoding fails.
+    let response = if decode_fallback {
+        response.decode_or_raw().await?
+    } else {
+        response
+    };
+
+    // Read small bodies up front so they can be reused without holding the connection.
+    response.prefetch().await?;
+
+    Ok(response)
+}
+
+/// Send the request once, recording redirect hops so redirect errors can report the chain.
+///
+/// The request is finalized up front when the caller wants it attached to the response.
+async fn send(
+    client: &Client,
+    method: Method,
+    builder: wreq::RequestBuilder,
+    return_sent: bool,
+) -> Result<(wreq::Response, Option<SentRequest>, Timings), Error> {
+    let sent_at = Instant::now();
+    let replay = (client.redirect_retries > 0)
+        .then(|| builder.try_clone())
+        .flatten();
+    let (sent, pending) = if return_sent {
+        let request = builder.build().map_err(Error::Library)?;
+        let sent = SentRequest::from(&request);
+        (Some(sent), client.inner.get()?.execute(request))
+    } else {
+        (None, builder.send())
+    };
+
+    let pending = async {
+        let method = method.into_ffi();
+        let mut result = redirect::track(&method, Vec::new(), pending).await;
+
+        // A connection failing while a redirect is followed is retried on that hop alone,
+        // resuming the chain from there with the hops followed so far.
+        let mut retries = client.redirect_retries;
+        let mut earlier = Vec::new();
+        while let (Err((err, hops)), Some(replay)) = (&result, &replay) {
+            if hops.is_empty() || retries == 0 || !Retry::retries_error(err) {
+                break;
+            }
+            let Some(replay) = replay.try_clone() else {
+                break;
+            };
+            let mut request = replay.build().map_err(Error::Library)?;
+            redirect::retarget(&mut request, hops)?;
+            retries -= 1;
+            earlier = hops.clone();
+            let pending = client.inner.get()?.execute(request);
+            result = redirect::track(&method, earlier.clone(), pending).await;
+        }
+        result
+            .map(|mut response| {
+                if !earlier.is_empty() {
+                    response.extensions_mut().insert(redirect::Resumed(earlier));
+                }
+                response
+            })
+            .map_err(|(err, _)| err)
+    };
+    let pending = dns::timed(guard::scope(
+        client.block_private_networks,
+        client.allowed_hosts.clone(),
+        pending,
+    ));
+    let (response, dns) = pending.await;
+    let timings = Timings::new(dns, sent_at.elapsed());
+    let response = response?;
+    if let Some(connections) = &client.connections {
+        connections.record(&response);
+    }
+    Ok((response, sent, timings))
+}
+
+/// Build the request without sending it and render it as an equivalent `curl` command.
+pub async fn render_curl<U>(
+    client: Client,
+    method: Method,
+    url: U,
+    request: Option<Request>,
+) -> PyResult<String>
+where
+    U: AsRef<str>,
+{
+    let prepared = prepare_request(&client, method, url.as_ref(), request).await?;
+    let request = prepared.builder.build().map_err(Error::Library)?;
+    let proxy = prepared.proxy.or_else(|| prepared.client.proxy.clone());
+    curl::render(&request, proxy.as_ref(), prepared.client.tls_insecure)
 }
 
 pub async fn execute_websocket_request<U>(
@@ -432,8 +1184,35 @@ pub async fn execute_websocket_request<U>(
 where
     U: AsRef<str>,
 {
+    let url = &*client.resolve_url(url.as_ref())?;
+
+    // Refuse hosts outside the allowlist before anything is sent.
+    if let Some(allowed_hosts) = &client.allowed_hosts {
+        allowed_hosts.check(url)?;
+    }
+    if client.block_private_networks {
+        guard::check_private(url)?;
+        // A proxy resolves the target itself, out of reach of the resolver's check.
+        if request.as_ref().is_some_and(|request| request.proxy.is_some()) {
+            return Err(PyValueError::new_err(
+                "`proxy` cannot be combined with `block_private_networks`",
+            ));
+        }
+    }
+
     // Create the WebSocket builder.
-    let mut builder = client.inner.websocket(url.as_ref());
+    let mut builder = client.inner.get()?.websocket(url);
+
+    // The client's default query goes first, minus the keys the request sets itself.
+    if let Some(default_query) = &client.default_query {
+        let own = request.as_ref().and_then(|request| request.query.as_ref());
+        builder = builder.query(&query::defaults(default_query, own));
+    }
+
+    // Host-specific emulation, overridden by any per-request emulation below.
+    if let Some(emulation) = host_emulation(&client, url) {
+        builder = builder.emulation(emulation);
+    }
 
     if let Some(mut request) = request {
         // Emulation options.
@@ -552,3 +1331,62 @@ where
         .map_err(Error::Library)
         .map_err(Into::into)
 }
+
+/// Set a body of known size, compressed when the client compresses large request bodies.
+async fn compress_body(
+    client: &Client,
+    builder: wreq::RequestBuilder,
+    bytes: Bytes,
+) -> PyResult<wreq::RequestBuilder> {
+    let Some(compression) = client.request_compression.as_deref() else {
+        return Ok(builder.body(bytes));
+    };
+    match compression.compress(&bytes).await? {
+        Some(compressed) => Ok(builder
+            .header(CONTENT_ENCODING, compression.coding.header_value())
+            .body(compressed)),
+        None => Ok(builder.body(bytes)),
+    }
+}
+
+/// In raw mode `Host` is not filled in, so an HTTP/1.1 request must carry its own.
+fn check_raw_host(request: Option<&Request>) -> PyResult<()> {
+    let version = request.and_then(|request| request.version.as_ref());
+    if matches!(
+        version,
+        Some(Version::HTTP_09 | Version::HTTP_10 | Version::HTTP_2 | Version::HTTP_3)
+    ) {
+        return Ok(());
+    }
+    let has_host = request
+        .and_then(|request| request.headers.as_ref())
+        .is_some_and(|headers| headers.0.contains_key(HOST));
+    if !has_host {
+        return Err(PyValueError::new_err(
+            "raw_request requires an explicit `Host` header for HTTP/1.1 requests, \
+             or set `version` to HTTP/2 or HTTP/3",
+        ));
+    }
+    Ok(())
+}
+
+/// Store each `name=value` pair of the request cookies in the jar for the request URL.
+fn persist_cookies(jar: &Jar, cookies: &Cookies, url: &str) {
+    for value in &cookies.0 {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        for pair in value.split(';').map(str::trim) {
+            if pair.contains('=') {
+                jar.0.add(pair, url);
+            }
+        }
+    }
+}
+
+/// Look up the client's host-specific emulation for the target URL.
+fn host_emulation(client: &Client, url: &str) -> Option<EmulationLike> {
+    let host_emulation = client.host_emulation.as_ref()?;
+    let uri = url.parse::<http::Uri>().ok()?;
+    host_emulation.get(uri.host()?)
+}
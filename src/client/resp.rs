@@ -1,8 +1,20 @@
+mod cache;
+mod digest;
 mod ext;
 mod http;
+pub mod limit;
+mod links;
+mod pages;
+mod sse;
+mod timing;
+pub mod wire;
 mod ws;
 
 pub use self::{
+    cache::{CacheControl, CacheInfo},
     http::{BlockingResponse, Response},
-    ws::{BlockingWebSocket, WebSocket, msg::Message},
+    pages::JsonPages,
+    sse::{EventSource, ServerSentEvent},
+    timing::Timings,
+    ws::{BlockingWebSocket, MessageRecords, WebSocket, msg::Message},
 };
@@ -0,0 +1,279 @@
+use http::{HeaderMap, HeaderName, header};
+use indexmap::IndexMap;
+use pyo3::prelude::*;
+
+/// The `CDN-Cache-Control` header name, as described in RFC 9213.
+const CDN_CACHE_CONTROL: HeaderName = HeaderName::from_static("cdn-cache-control");
+
+/// The non-standard `X-Cache` header name, commonly set by CDNs and caching proxies.
+const X_CACHE: HeaderName = HeaderName::from_static("x-cache");
+
+/// Parsed `Cache-Control` directives.
+///
+/// Directive names are case-insensitive and stored lowercased; quoted values are unquoted.
+#[derive(Clone, Default)]
+#[pyclass(subclass, str, frozen, skip_from_py_object)]
+pub struct CacheControl(IndexMap<String, Option<String>>);
+
+/// Structured caching metadata of a response.
+#[derive(Clone)]
+#[pyclass(subclass, str, frozen, skip_from_py_object)]
+pub struct CacheInfo {
+    age: Option<u64>,
+    cache_control: CacheControl,
+    cdn_cache_control: Option<CacheControl>,
+    x_cache: Option<String>,
+}
+
+// ===== impl CacheControl =====
+
+impl CacheControl {
+    /// Parse all values of the given header into a single set of directives.
+    ///
+    /// Returns `None` if the header is absent.
+    fn parse(headers: &HeaderMap, name: &HeaderName) -> Option<Self> {
+        let mut directives = IndexMap::new();
+        let mut found = false;
+        for value in headers.get_all(name) {
+            found = true;
+            if let Ok(value) = value.to_str() {
+                for directive in split_directives(value) {
+                    let (name, value) = match directive.split_once('=') {
+                        Some((name, value)) => (name, Some(unquote(value.trim()))),
+                        None => (directive, None),
+                    };
+                    let name = name.trim().to_ascii_lowercase();
+                    if !name.is_empty() {
+                        directives.insert(name, value);
+                    }
+                }
+            }
+        }
+        found.then_some(CacheControl(directives))
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    fn seconds(&self, name: &str) -> Option<u64> {
+        self.0.get(name)?.as_deref()?.parse().ok()
+    }
+}
+
+#[pymethods]
+impl CacheControl {
+    /// Get all directives, mapping each name to its value (or `None` for flags).
+    #[getter]
+    fn directives(&self) -> IndexMap<String, Option<String>> {
+        self.0.clone()
+    }
+
+    /// Get the `max-age` directive in seconds.
+    #[getter]
+    fn max_age(&self) -> Option<u64> {
+        self.seconds("max-age")
+    }
+
+    /// Get the `s-maxage` directive in seconds.
+    #[getter]
+    fn s_maxage(&self) -> Option<u64> {
+        self.seconds("s-maxage")
+    }
+
+    /// Get the `stale-while-revalidate` directive in seconds.
+    #[getter]
+    fn stale_while_revalidate(&self) -> Option<u64> {
+        self.seconds("stale-while-revalidate")
+    }
+
+    /// Get the `stale-if-error` directive in seconds.
+    #[getter]
+    fn stale_if_error(&self) -> Option<u64> {
+        self.seconds("stale-if-error")
+    }
+
+    /// Whether the `no-cache` directive is present.
+    #[getter]
+    fn no_cache(&self) -> bool {
+        self.has("no-cache")
+    }
+
+    /// Whether the `no-store` directive is present.
+    #[getter]
+    fn no_store(&self) -> bool {
+        self.has("no-store")
+    }
+
+    /// Whether the `no-transform` directive is present.
+    #[getter]
+    fn no_transform(&self) -> bool {
+        self.has("no-transform")
+    }
+
+    /// Whether the `must-revalidate` directive is present.
+    #[getter]
+    fn must_revalidate(&self) -> bool {
+        self.has("must-revalidate")
+    }
+
+    /// Whether the `proxy-revalidate` directive is present.
+    #[getter]
+    fn proxy_revalidate(&self) -> bool {
+        self.has("proxy-revalidate")
+    }
+
+    /// Whether the `private` directive is present.
+    #[getter]
+    fn private(&self) -> bool {
+        self.has("private")
+    }
+
+    /// Whether the `public` directive is present.
+    #[getter]
+    fn public(&self) -> bool {
+        self.has("public")
+    }
+
+    /// Whether the `immutable` directive is present.
+    #[getter]
+    fn immutable(&self) -> bool {
+        self.has("immutable")
+    }
+
+    fn __contains__(&self, name: &str) -> bool {
+        self.has(&name.to_ascii_lowercase())
+    }
+}
+
+impl std::fmt::Display for CacheControl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (index, (name, value)) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            match value {
+                Some(value) => write!(f, "{name}={value}")?,
+                None => f.write_str(name)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+// ===== impl CacheInfo =====
+
+impl CacheInfo {
+    /// Extract caching metadata from response headers.
+    pub fn new(headers: &HeaderMap) -> Self {
+        CacheInfo {
+            age: headers
+                .get(header::AGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok()),
+            cache_control: CacheControl::parse(headers, &header::CACHE_CONTROL).unwrap_or_default(),
+            cdn_cache_control: CacheControl::parse(headers, &CDN_CACHE_CONTROL),
+            x_cache: headers
+                .get(X_CACHE)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned),
+        }
+    }
+}
+
+#[pymethods]
+impl CacheInfo {
+    /// Get the `Age` header in seconds.
+    #[getter]
+    fn age(&self) -> Option<u64> {
+        self.age
+    }
+
+    /// Get the parsed `Cache-Control` directives.
+    #[getter]
+    fn cache_control(&self) -> CacheControl {
+        self.cache_control.clone()
+    }
+
+    /// Get the parsed `CDN-Cache-Control` directives, if present.
+    #[getter]
+    fn cdn_cache_control(&self) -> Option<CacheControl> {
+        self.cdn_cache_control.clone()
+    }
+
+    /// Get the raw `X-Cache` header, if present.
+    #[getter]
+    fn x_cache(&self) -> Option<String> {
+        self.x_cache.clone()
+    }
+
+    /// Whether the `X-Cache` header reports a cache hit.
+    #[getter]
+    fn hit(&self) -> Option<bool> {
+        self.x_cache
+            .as_deref()
+            .map(|value| value.to_ascii_uppercase().contains("HIT"))
+    }
+}
+
+impl std::fmt::Display for CacheInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "<CacheInfo cache_control=\"{}\"", self.cache_control)?;
+        if let Some(age) = self.age {
+            write!(f, " age={age}")?;
+        }
+        if let Some(x_cache) = &self.x_cache {
+            write!(f, " x_cache=\"{x_cache}\"")?;
+        }
+        f.write_str(">")
+    }
+}
+
+/// Split a header value on commas that are not inside a quoted string.
+fn split_directives(value: &str) -> impl Iterator<Item = &str> {
+    let mut start = 0;
+    let mut quoted = false;
+    let mut escaped = false;
+    let mut parts = Vec::new();
+    for (index, ch) in value.char_indices() {
+        match ch {
+            _ if escaped => escaped = false,
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            ',' if !quoted => {
+                parts.push(&value[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+        .into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+}
+
+/// Remove surrounding quotes and backslash escapes from a quoted-string value.
+fn unquote(value: &str) -> String {
+    match value
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+    {
+        Some(inner) => {
+            let mut out = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(ch) = chars.next() {
+                if ch == '\\' {
+                    if let Some(next) = chars.next() {
+                        out.push(next);
+                    }
+                } else {
+                    out.push(ch);
+                }
+            }
+            out
+        }
+        None => value.to_owned(),
+    }
+}
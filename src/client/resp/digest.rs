@@ -0,0 +1,117 @@
+use std::fmt::Write;
+
+use md5::Md5;
+use pyo3::{exceptions::PyValueError, prelude::*, pybacked::PyBackedStr};
+use sha2::{Digest, Sha256, Sha512};
+
+use crate::error::Error;
+
+/// A digest algorithm supported for body integrity checks.
+#[derive(Clone, Copy)]
+pub enum Algorithm {
+    Sha256,
+    Sha512,
+    Md5,
+}
+
+/// An incremental hasher fed with body chunks as they are streamed.
+pub enum Hasher {
+    Sha256(Sha256),
+    Sha512(Sha512),
+    Md5(Md5),
+}
+
+/// An expected body digest, checked against the hash of the streamed body.
+pub struct Expected {
+    hasher: Hasher,
+    hex: String,
+}
+
+// ===== impl Algorithm =====
+
+impl FromPyObject<'_, '_> for Algorithm {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<'_, '_, PyAny>) -> PyResult<Self> {
+        let name = ob.extract::<PyBackedStr>()?;
+        match name.to_ascii_lowercase().as_str() {
+            "sha256" => Ok(Algorithm::Sha256),
+            "sha512" => Ok(Algorithm::Sha512),
+            "md5" => Ok(Algorithm::Md5),
+            _ => Err(PyValueError::new_err(format!(
+                "Unsupported digest algorithm: {}, expected one of sha256, sha512, md5",
+                &*name
+            ))),
+        }
+    }
+}
+
+// ===== impl Hasher =====
+
+impl Hasher {
+    /// Create a new [`Hasher`] for the given [`Algorithm`].
+    pub fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            Algorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            Algorithm::Md5 => Hasher::Md5(Md5::new()),
+        }
+    }
+
+    /// Feed a chunk of data into the hasher.
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+            Hasher::Md5(hasher) => hasher.update(data),
+        }
+    }
+
+    /// Consume the hasher and return the lowercase hex encoded digest.
+    pub fn finalize(self) -> String {
+        let digest = match self {
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Md5(hasher) => hasher.finalize().to_vec(),
+        };
+        digest
+            .iter()
+            .fold(String::with_capacity(digest.len() * 2), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            })
+    }
+}
+
+// ===== impl Expected =====
+
+impl Expected {
+    /// Create a new [`Expected`] digest from an algorithm and a hex encoded value.
+    pub fn new(algorithm: Algorithm, hex: &str) -> Self {
+        Expected {
+            hasher: Hasher::new(algorithm),
+            hex: hex.trim().to_ascii_lowercase(),
+        }
+    }
+
+    /// Feed a chunk of the body into the underlying hasher.
+    #[inline]
+    pub fn update(&mut self, data: &[u8]) {
+        self.hasher.update(data);
+    }
+
+    /// Finish hashing and compare against the expected value.
+    ///
+    /// Returns [`Error::DigestMismatch`] when the digests differ.
+    pub fn verify(self) -> Result<(), Error> {
+        let actual = self.hasher.finalize();
+        if actual == self.hex {
+            Ok(())
+        } else {
+            Err(Error::DigestMismatch {
+                expected: self.hex,
+                actual,
+            })
+        }
+    }
+}
@@ -1,6 +1,16 @@
+use std::{io, path::PathBuf};
+
+use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder, ZlibDecoder, ZstdDecoder};
 use bytes::Bytes;
+use http::header::CONTENT_ENCODING;
+use http_body_util::BodyExt;
 use pyo3::pybacked::PyBackedStr;
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncReadExt, AsyncWriteExt, BufWriter},
+};
 
+use super::digest::Expected;
 use crate::error::Error;
 
 /// Extension trait for [`wreq::Response`] that provides convenient methods for consuming response
@@ -17,6 +27,26 @@ pub trait ResponseExt {
 
     /// This method consumes the response and returns all body data as a [`Bytes`] buffer.
     async fn bytes(self) -> Result<Bytes, Error>;
+
+    /// Reads a body received without automatic decompression and decodes it by its
+    /// `Content-Encoding`.
+    ///
+    /// A body that cannot be decoded is handed back as received in the inner `Err`.
+    async fn decompress(self) -> Result<Result<Bytes, Bytes>, Error>;
+
+    /// Streams the body into the file at `path`, returning the number of bytes written.
+    ///
+    /// The file is truncated first, or appended to when `append` is set. When an [`Expected`]
+    /// digest is given, each chunk is hashed as it is written and the written bytes are
+    /// discarded if the final digest does not match. When `chunk_size` is given, small chunks
+    /// are gathered and written to the file in blocks of up to that many bytes.
+    async fn save(
+        self,
+        path: PathBuf,
+        expected: Option<Expected>,
+        chunk_size: Option<usize>,
+        append: bool,
+    ) -> Result<u64, Error>;
 }
 
 impl ResponseExt for wreq::Response {
@@ -38,4 +68,97 @@ impl ResponseExt for wreq::Response {
     async fn bytes(self) -> Result<Bytes, Error> {
         self.bytes().await.map_err(Error::Library)
     }
+
+    async fn decompress(self) -> Result<Result<Bytes, Bytes>, Error> {
+        let encodings = content_codings(self.headers());
+        let raw = self.bytes().await.map_err(Error::Library)?;
+
+        // Codings are listed in the order they were applied, so undo them from the last.
+        let mut body = raw.clone();
+        for encoding in encodings.iter().rev() {
+            match decode(encoding, &body).await {
+                Ok(decoded) => body = decoded,
+                Err(_) => return Ok(Err(raw)),
+            }
+        }
+        Ok(Ok(body))
+    }
+
+    async fn save(
+        mut self,
+        path: PathBuf,
+        mut expected: Option<Expected>,
+        chunk_size: Option<usize>,
+        append: bool,
+    ) -> Result<u64, Error> {
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .append(append)
+            .truncate(!append)
+            .open(&path)
+            .await?;
+        let start = file.metadata().await?.len();
+        // Without a chunk size, every chunk is written as soon as it arrives.
+        let mut file = BufWriter::with_capacity(chunk_size.unwrap_or(0), file);
+        let mut written = 0u64;
+
+        while let Some(frame) = self.frame().await {
+            if let Ok(bytes) = frame.map_err(Error::Library)?.into_data() {
+                if let Some(expected) = expected.as_mut() {
+                    expected.update(&bytes);
+                }
+                file.write_all(&bytes).await?;
+                written += bytes.len() as u64;
+            }
+        }
+        file.flush().await?;
+
+        if let Some(expected) = expected {
+            if let Err(err) = expected.verify() {
+                // Restore the file to its previous length, or remove it if it was empty.
+                let file = file.into_inner();
+                if start > 0 {
+                    let _ = file.set_len(start).await;
+                } else {
+                    drop(file);
+                    let _ = tokio::fs::remove_file(&path).await;
+                }
+                return Err(err);
+            }
+        }
+
+        Ok(written)
+    }
+}
+
+/// The content codings listed in the `Content-Encoding` headers, in the order they were
+/// applied, leaving out `identity`.
+pub fn content_codings(headers: &http::HeaderMap) -> Vec<String> {
+    headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|encoding| encoding.trim().to_ascii_lowercase())
+        .filter(|encoding| !encoding.is_empty() && encoding != "identity")
+        .collect()
+}
+
+/// Decode a body compressed with the given content coding.
+async fn decode(encoding: &str, data: &[u8]) -> io::Result<Bytes> {
+    let mut decoded = Vec::new();
+    match encoding {
+        "gzip" | "x-gzip" => GzipDecoder::new(data).read_to_end(&mut decoded).await?,
+        "br" => BrotliDecoder::new(data).read_to_end(&mut decoded).await?,
+        "deflate" => ZlibDecoder::new(data).read_to_end(&mut decoded).await?,
+        "zstd" => ZstdDecoder::new(data).read_to_end(&mut decoded).await?,
+        _ => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported content coding: {encoding}"),
+            ));
+        }
+    };
+    Ok(Bytes::from(decoded))
 }
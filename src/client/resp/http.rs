@@ -1,38 +1,86 @@
-use std::{fmt::Display, sync::Arc};
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    sync::{
+        Arc, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
 use arc_swap::ArcSwapOption;
 use bytes::Bytes;
+use encoding_rs::Encoding;
 use futures_util::{
-    TryFutureExt,
+    FutureExt, TryFutureExt,
     future::{self, BoxFuture},
 };
 use http::response::{Parts, Response as HttpResponse};
-use http_body_util::{BodyExt, Collected};
-use pyo3::{coroutine::CancelHandle, prelude::*, pybacked::PyBackedStr};
+use http_body_util::BodyExt;
+use indexmap::IndexMap;
+use pyo3::{
+    IntoPyObjectExt,
+    coroutine::CancelHandle,
+    exceptions::{PyRuntimeError, PyValueError},
+    prelude::*,
+    pybacked::PyBackedStr,
+    types::PyBytes,
+};
+use url::Url;
 use wreq::{self, Uri};
 
 use crate::{
     buffer::PyBuffer,
     client::{
-        SocketAddr,
-        body::{Json, Streamer},
-        nogil::NoGIL,
-        resp::ext::ResponseExt,
+        Client, SocketAddr,
+        body::{Json, Parts as Multipart, Streamer, charset},
+        nogil::{self, NoGIL},
+        req::{SentRequest, execute_request},
+        resp::{
+            JsonPages, Timings,
+            cache::CacheInfo,
+            digest::{Algorithm, Expected},
+            ext::ResponseExt,
+            links,
+        },
     },
     cookie::Cookie,
-    error::Error,
+    error::{Error, ValidationError},
     header::HeaderMap,
-    http::{StatusCode, Version},
-    redirect::History,
+    http::{Method, StatusCode, Version},
+    redirect::{History, Resumed},
     tls::TlsInfo,
 };
 
 /// A response from a request.
-#[pyclass(subclass, frozen, str, skip_from_py_object)]
+#[pyclass(subclass, frozen, skip_from_py_object)]
 pub struct Response {
     uri: Uri,
+    /// The method the request was sent with, from which the redirect history is worked out.
+    method: http::Method,
     parts: Parts,
     body: Arc<ArcSwapOption<Body>>,
+    trailers: Arc<ArcSwapOption<http::HeaderMap>>,
+    sent_request: Option<SentRequest>,
+    decode_fallback: bool,
+    /// Whether automatic decompression was turned off, so the body is kept as received.
+    undecoded: bool,
+    validator: Option<Arc<Py<PyAny>>>,
+    buffer_limit: Option<u64>,
+    client: Option<Client>,
+    elapsed: Duration,
+    timings: Timings,
+    /// Whether to parse the body on the blocking thread pool.
+    offload_decode: bool,
+    /// The size of the body once read, after any decompression.
+    decoded_bytes: Arc<OnceLock<u64>>,
+    /// The number of body bytes read off the connection, before any decompression.
+    wire_bytes: Option<Arc<AtomicU64>>,
+    /// Whether the body arrives as it was sent.
+    ///
+    /// Automatic decompression strips `Content-Encoding` and `Content-Length`, so a body
+    /// keeping either was not decompressed.
+    raw_body: bool,
 }
 
 /// Represents the state of the HTTP response body.
@@ -44,7 +92,7 @@ enum Body {
 }
 
 /// A blocking response from a request.
-#[pyclass(name = "Response", subclass, frozen, str, skip_from_py_object)]
+#[pyclass(name = "Response", subclass, frozen, skip_from_py_object)]
 pub struct BlockingResponse(Response);
 
 // ===== impl Response =====
@@ -58,7 +106,251 @@ impl Response {
             .map(ArcSwapOption::from_pointee)
             .map(Arc::new);
         let (parts, body) = response.into_parts();
-        Response { uri, parts, body }
+        let raw_body = parts.headers.contains_key(http::header::CONTENT_ENCODING)
+            || parts.headers.contains_key(http::header::CONTENT_LENGTH);
+        Response {
+            uri,
+            method: http::Method::GET,
+            parts,
+            body,
+            trailers: Arc::new(ArcSwapOption::empty()),
+            sent_request: None,
+            decode_fallback: false,
+            undecoded: false,
+            validator: None,
+            buffer_limit: None,
+            client: None,
+            elapsed: Duration::ZERO,
+            timings: Timings::default(),
+            offload_decode: false,
+            decoded_bytes: Arc::default(),
+            wire_bytes: None,
+            raw_body,
+        }
+    }
+
+    /// Record the method the request was sent with.
+    #[inline]
+    pub fn with_method(mut self, method: http::Method) -> Self {
+        self.method = method;
+        self
+    }
+
+    /// Record the time taken from starting the request until the response head arrived.
+    #[inline]
+    pub fn with_elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    /// Record the timing breakdown of the final attempt.
+    #[inline]
+    pub fn with_timings(mut self, timings: Timings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Attach the client that sent the request, used to fetch follow-up pages.
+    #[inline]
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Attach the finalized request that produced this response.
+    #[inline]
+    pub fn with_sent_request(mut self, sent_request: Option<SentRequest>) -> Self {
+        self.sent_request = sent_request;
+        self
+    }
+
+    /// Mark whether automatic decompression was turned off for the request.
+    #[inline]
+    pub fn with_undecoded(mut self, undecoded: bool) -> Self {
+        self.undecoded = undecoded;
+        self
+    }
+
+    /// Attach the counter of body bytes read off the connection.
+    #[inline]
+    pub fn with_wire_bytes(mut self, wire_bytes: Arc<AtomicU64>) -> Self {
+        self.wire_bytes = Some(wire_bytes);
+        self
+    }
+
+    /// Attach a validator that is handed the parsed JSON body by `json()`.
+    #[inline]
+    pub fn with_validator(mut self, validator: Option<Arc<Py<PyAny>>>) -> Self {
+        self.validator = validator;
+        self
+    }
+
+    /// Only keep bodies of at most `buffer_limit` bytes in memory for reuse.
+    #[inline]
+    pub fn with_buffer_limit(mut self, buffer_limit: Option<u64>) -> Self {
+        self.buffer_limit = buffer_limit;
+        self
+    }
+
+    /// Parse the body on the blocking thread pool instead of the runtime's worker threads.
+    #[inline]
+    pub fn with_offload_decode(mut self, offload_decode: bool) -> Self {
+        self.offload_decode = offload_decode;
+        self
+    }
+
+    /// Convert the parsed JSON body and run the validator on it, if one is attached.
+    ///
+    /// A validator error is raised as [`ValidationError`] with the original error as its cause.
+    fn validate<'py, T>(&self, py: Python<'py>, json: T) -> PyResult<Py<PyAny>>
+    where
+        T: IntoPyObject<'py>,
+    {
+        let json = json.into_py_any(py)?;
+        if let Some(validator) = &self.validator {
+            if let Err(err) = validator.call1(py, (json.clone_ref(py),)) {
+                let exc = ValidationError::new_err(format!("Response validation failed: {err}"));
+                exc.set_cause(py, Some(err));
+                return Err(exc);
+            }
+        }
+        Ok(json)
+    }
+
+    /// Buffer the body and extract the links it contains, see [`links::extract_links`].
+    fn links(
+        &self,
+        base: Option<PyBackedStr>,
+        attrs: Option<IndexMap<String, Vec<String>>>,
+    ) -> PyResult<impl Future<Output = PyResult<Vec<String>>> + Send + 'static> {
+        let url = Url::parse(&self.uri.to_string())
+            .map_err(|err| PyValueError::new_err(format!("Invalid response URL: {err}")))?;
+        let base = base
+            .map(|base| url.join(&base))
+            .transpose()
+            .map_err(|err| PyValueError::new_err(format!("Invalid base URL: {err}")))?;
+        Ok(self
+            .cache_response()
+            .and_then(|resp| ResponseExt::text(resp, None))
+            .map_ok(move |html| links::extract_links(&html, url, base, attrs))
+            .map_err(Into::into))
+    }
+
+    /// Find the target of a `Refresh` header, or of a `<meta http-equiv="refresh">` in an HTML
+    /// body when `meta` is set, and request it with the client that sent this response.
+    ///
+    /// Resolves to `None` when the response does not refresh to another URL.
+    fn refresh(
+        &self,
+        meta: bool,
+        wait: bool,
+    ) -> PyResult<impl Future<Output = PyResult<Option<Response>>> + Send + 'static> {
+        let url = Url::parse(&self.uri.to_string())
+            .map_err(|err| PyValueError::new_err(format!("Invalid response URL: {err}")))?;
+        let header = self
+            .parts
+            .headers
+            .get("refresh")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let is_html = self
+            .parts
+            .headers
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_none_or(|content_type| content_type.contains("html"));
+        let body = (header.is_none() && meta && is_html).then(|| self.cache_response());
+        let client = self.client.clone().unwrap_or_default();
+
+        Ok(async move {
+            let value = match (header, body) {
+                (Some(header), _) => Some(header),
+                (None, Some(body)) => {
+                    let html = ResponseExt::text(body.await?, None).await?;
+                    links::meta_refresh(&html)
+                }
+                (None, None) => None,
+            };
+            let Some((delay, target)) = value.as_deref().and_then(links::parse_refresh) else {
+                return Ok(None);
+            };
+            let target = url
+                .join(&target)
+                .map_err(|err| PyValueError::new_err(format!("Invalid refresh URL: {err}")))?;
+            if wait {
+                tokio::time::sleep(delay).await;
+            }
+            execute_request(client, Method::GET, target, None)
+                .await
+                .map(Some)
+        })
+    }
+
+    /// Fail unless the body is kept as it was received.
+    fn check_raw(&self) -> PyResult<()> {
+        if self.undecoded || self.raw_body {
+            Ok(())
+        } else {
+            Err(PyRuntimeError::new_err(
+                "The response body was decompressed, send the request with \
+                 `decompress=False` to read it as received",
+            ))
+        }
+    }
+
+    /// Get the body if it has already been read into memory.
+    fn buffered(&self) -> PyResult<Bytes> {
+        match self.body.load().as_deref() {
+            Some(Body::Reusable(bytes)) => Ok(bytes.clone()),
+            _ => Err(PyRuntimeError::new_err(
+                "The response body has not been read yet, call bytes(), text() or json() first",
+            )),
+        }
+    }
+
+    /// Read the whole body into memory, surfacing any body or decoding errors.
+    #[inline]
+    pub async fn buffer(&self) -> Result<(), Error> {
+        self.cache_response().await.map(drop)
+    }
+
+    /// Buffer a body received without automatic decompression and decode it by its
+    /// `Content-Encoding`.
+    ///
+    /// A body that cannot be decoded is kept as it was received, and the response is marked
+    /// with `decode_fallback` so the caller can tell.
+    pub async fn decode_or_raw(mut self) -> Result<Self, Error> {
+        let encoded = self.parts.headers.contains_key(http::header::CONTENT_ENCODING);
+        if !encoded {
+            return Ok(self);
+        }
+        let body = match self.cache_response().await?.decompress().await? {
+            Ok(decoded) => {
+                self.parts.headers.remove(http::header::CONTENT_ENCODING);
+                self.parts.headers.remove(http::header::CONTENT_LENGTH);
+                self.raw_body = false;
+                self.decoded_bytes = Arc::new(OnceLock::from(decoded.len() as u64));
+                decoded
+            }
+            Err(raw) => {
+                self.decode_fallback = true;
+                self.undecoded = true;
+                raw
+            }
+        };
+        // Keep the body whatever the buffer limit, it has been read in full already.
+        self.body.store(Some(Arc::new(Body::Reusable(body))));
+        Ok(self)
+    }
+
+    /// Buffer the body up front if its `Content-Length` is within the buffer limit.
+    pub async fn prefetch(&self) -> Result<(), Error> {
+        let small = self.buffer_limit.is_some_and(|limit| {
+            self.empty_response()
+                .content_length()
+                .is_some_and(|len| len <= limit)
+        });
+        if small { self.buffer().await } else { Ok(()) }
     }
 
     /// Builds a [`wreq::Response`] from the current response metadata and the given body.
@@ -75,20 +367,26 @@ impl Response {
     }
 
     /// Consumes the response [`Body`] and caches it in memory for reuse.
+    ///
+    /// Bodies larger than the buffer limit are handed out once without being cached.
     fn cache_response(&self) -> BoxFuture<'static, Result<wreq::Response, Error>> {
         if let Some(arc) = self.body.swap(None) {
             let parts = self.parts.clone();
             let body = self.body.clone();
+            let trailers = self.trailers.clone();
+            let limit = self.buffer_limit;
+            let decoded_bytes = self.decoded_bytes.clone();
             match Arc::into_inner(arc) {
                 Some(Body::Streamable(stream)) => {
                     return Box::pin(async move {
-                        let bytes = stream
-                            .collect()
-                            .await
-                            .map(Collected::to_bytes)
-                            .map_err(Error::Library)?;
+                        let collected = stream.collect().await.map_err(Error::Library)?;
+                        trailers.store(collected.trailers().cloned().map(Arc::new));
+                        let bytes = collected.to_bytes();
+                        let _ = decoded_bytes.set(bytes.len() as u64);
 
-                        body.store(Some(Arc::new(Body::Reusable(bytes.clone()))));
+                        if limit.is_none_or(|limit| bytes.len() as u64 <= limit) {
+                            body.store(Some(Arc::new(Body::Reusable(bytes.clone()))));
+                        }
                         let response = HttpResponse::from_parts(parts, bytes);
                         Ok(wreq::Response::from(response))
                     });
@@ -105,16 +403,69 @@ impl Response {
         Box::pin(future::err(Error::Memory))
     }
 
+    /// Reads the body into memory and hands the buffered response to `decode`.
+    ///
+    /// With `offload_decode` set, `decode` runs on the blocking thread pool, so parsing a large
+    /// body does not stall other requests on the runtime's worker threads.
+    fn decode<F, Fut, T>(&self, decode: F) -> BoxFuture<'static, Result<T, Error>>
+    where
+        F: FnOnce(wreq::Response) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, Error>> + Send + 'static,
+        T: Send + 'static,
+    {
+        let offload = self.offload_decode;
+        self.cache_response()
+            .and_then(move |resp| async move {
+                if !offload {
+                    return decode(resp).await;
+                }
+                let handle = tokio::runtime::Handle::current();
+                tokio::task::spawn_blocking(move || handle.block_on(decode(resp)))
+                    .await
+                    .map_err(std::io::Error::other)?
+            })
+            .boxed()
+    }
+
     /// Consumes the response [`Body`] for streaming without caching.
-    fn stream_response(&self) -> Result<wreq::Response, Error> {
+    ///
+    /// A body that has already been buffered is streamed from memory and kept for reuse.
+    pub fn stream_response(&self) -> Result<wreq::Response, Error> {
         if let Some(arc) = self.body.swap(None) {
-            if let Ok(Body::Streamable(body)) = Arc::try_unwrap(arc) {
-                return Ok(self.build_response(body));
+            match Arc::try_unwrap(arc) {
+                Ok(Body::Streamable(body)) => return Ok(self.build_response(body)),
+                Ok(Body::Reusable(bytes)) => {
+                    self.body
+                        .store(Some(Arc::new(Body::Reusable(bytes.clone()))));
+                    return Ok(self.build_response(bytes));
+                }
+                Err(_) => {}
             }
         }
         Err(Error::Memory)
     }
 
+    /// Fail with a status error if the status is 4xx or 5xx.
+    #[inline]
+    pub fn check_status(&self) -> Result<(), Error> {
+        Error::for_status(&self.uri, self.parts.status)
+    }
+
+    /// Read the body and parse it as JSON, without running the validator.
+    #[inline]
+    pub fn parse_json(&self) -> BoxFuture<'static, Result<Json, Error>> {
+        self.decode(ResponseExt::json::<Json>)
+    }
+
+    /// Take the body to send as the body of another request, without buffering it.
+    ///
+    /// Returns the body along with the response `Content-Type`, if any.
+    pub fn pipe_body(&self) -> Result<(wreq::Body, Option<http::HeaderValue>), Error> {
+        let content_type = self.parts.headers.get(http::header::CONTENT_TYPE).cloned();
+        let response = self.stream_response()?;
+        Ok((HttpResponse::from(response).into_body(), content_type))
+    }
+
     /// Forcefully destroys the response [`Body`], preventing any further reads.
     fn destroy(&self) {
         #[allow(clippy::option_map_unit_fn)]
@@ -139,6 +490,24 @@ impl Response {
         StatusCode(self.parts.status)
     }
 
+    /// Whether the status code is within 200-299.
+    #[getter]
+    pub fn ok(&self) -> bool {
+        self.parts.status.is_success()
+    }
+
+    /// Get the time taken from starting the request until the response head arrived.
+    #[getter]
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Get the breakdown of where the time before the response head arrived was spent.
+    #[getter]
+    pub fn timings(&self) -> Timings {
+        self.timings
+    }
+
     /// Get the HTTP version of the response.
     #[getter]
     pub fn version(&self) -> Version {
@@ -157,6 +526,20 @@ impl Response {
         Cookie::extract_headers_cookies(&self.parts.headers)
     }
 
+    /// Get the cookies set by this response, with their effective domain and path.
+    #[getter]
+    pub fn set_cookies(&self) -> Vec<Cookie> {
+        Cookie::extract_set_cookies(&self.parts.headers, &self.uri)
+    }
+
+    /// Get the entries of the `Link` headers keyed by `rel`, each with its `url` and
+    /// parameters.
+    #[getter(links)]
+    pub fn header_links(&self) -> IndexMap<String, IndexMap<String, String>> {
+        let values = self.parts.headers.get_all(http::header::LINK).iter();
+        links::parse_link_header(values.filter_map(|value| value.to_str().ok()))
+    }
+
     /// Get the content length of the response.
     #[getter]
     pub fn content_length(&self, py: Python) -> Option<u64> {
@@ -179,12 +562,17 @@ impl Response {
     #[getter]
     pub fn history(&self, py: Python) -> Vec<History> {
         py.detach(|| {
-            self.empty_response()
-                .extensions()
-                .get::<wreq::redirect::History>()
-                .map_or_else(Vec::new, |history| {
-                    history.into_iter().cloned().map(History).collect()
-                })
+            let response = self.empty_response();
+            let extensions = response.extensions();
+
+            // A chain resumed from a failed hop starts with the hops followed before it.
+            let mut hops = extensions
+                .get::<Resumed>()
+                .map_or_else(Vec::new, |resumed| resumed.0.clone());
+            if let Some(history) = extensions.get::<wreq::redirect::History>() {
+                hops.extend(history.into_iter().map(History::from));
+            }
+            History::chain(hops, &self.method)
         })
     }
 
@@ -200,19 +588,187 @@ impl Response {
         })
     }
 
-    /// Turn a response into an error if the server returned an error.
-    pub fn raise_for_status(&self) -> PyResult<()> {
-        self.empty_response()
-            .error_for_status()
-            .map(|_| ())
-            .map_err(Error::Library)
-            .map_err(Into::into)
+    /// Get the finalized request, if the request was made with `return_sent=True`.
+    #[getter]
+    pub fn sent_request(&self) -> Option<SentRequest> {
+        self.sent_request.clone()
+    }
+
+    /// Whether the body is the raw payload kept after decompression failed.
+    #[getter]
+    pub fn decode_fallback(&self) -> bool {
+        self.decode_fallback
+    }
+
+    /// Get the trailer headers of the response.
+    ///
+    /// Only available once the body has been fully read into memory.
+    #[getter]
+    pub fn trailers(&self) -> Option<HeaderMap> {
+        self.trailers
+            .load_full()
+            .map(|trailers| HeaderMap(trailers.as_ref().clone()))
+    }
+
+    /// Get the body bytes already read into memory by `bytes()`, `text()` or `json()`.
+    ///
+    /// Raises an error if the body has not been read yet.
+    #[getter]
+    pub fn content(&self) -> PyResult<PyBuffer> {
+        self.buffered().map(PyBuffer::from)
+    }
+
+    /// Get the size of the body after decompression, once it has been read into memory.
+    #[getter]
+    pub fn decoded_bytes(&self) -> Option<u64> {
+        self.decoded_bytes.get().copied()
+    }
+
+    /// Get the size of the body as transferred, before decompression, once it has been read
+    /// into memory.
+    #[getter]
+    pub fn downloaded_bytes(&self) -> Option<u64> {
+        let decoded = self.decoded_bytes.get().copied();
+        match &self.wire_bytes {
+            Some(wire_bytes) => decoded.map(|_| wire_bytes.load(Ordering::Relaxed)),
+            None => decoded.filter(|_| self.raw_body),
+        }
+    }
+
+    /// Get the caching metadata (`Age`, `Cache-Control`, `X-Cache`, ...) of the response.
+    pub fn cache_info(&self) -> CacheInfo {
+        CacheInfo::new(&self.parts.headers)
+    }
+
+    /// Raise a `StatusError` if the status is 4xx or 5xx, otherwise return the response.
+    ///
+    /// The body is left unread, so the error payload can still be read afterwards.
+    pub fn raise_for_status(slf: Bound<'_, Self>) -> PyResult<Bound<'_, Self>> {
+        slf.get()
+            .check_status()
+            .map_err(|err| err.with_response(slf.clone().into_any().unbind()))?;
+        Ok(slf)
+    }
+
+    /// Return the response itself if the status is 2xx, otherwise `default`.
+    pub fn ok_or(slf: Bound<'_, Self>, default: Py<PyAny>) -> Py<PyAny> {
+        if slf.get().parts.status.is_success() {
+            slf.into_any().unbind()
+        } else {
+            default
+        }
     }
 
     /// Get the response into a `Stream` of `Bytes` from the body.
-    pub fn stream(&self) -> PyResult<Streamer> {
+    ///
+    /// When `max_bytes` is set, the stream ends cleanly once that many bytes have been read.
+    /// When `autodetect_compression` is set and no `Content-Encoding` is declared, gzip and
+    /// zstd bodies are detected by their magic bytes and decompressed transparently.
+    /// When `chunk_timeout` is set, each read raises a timeout error if no chunk arrives in time.
+    /// When `chunk_size` is set, chunks are re-buffered into blocks of exactly that many bytes,
+    /// except for a shorter final one.
+    #[pyo3(signature = (
+        max_bytes = None,
+        autodetect_compression = false,
+        chunk_timeout = None,
+        chunk_size = None
+    ))]
+    pub fn stream(
+        &self,
+        max_bytes: Option<u64>,
+        autodetect_compression: bool,
+        chunk_timeout: Option<Duration>,
+        chunk_size: Option<usize>,
+    ) -> PyResult<Streamer> {
+        check_chunk_size(chunk_size)?;
+        self.stream_response()
+            .map(|resp| {
+                Streamer::new(
+                    resp,
+                    max_bytes,
+                    autodetect_compression,
+                    chunk_timeout,
+                    chunk_size,
+                )
+            })
+            .map_err(Into::into)
+    }
+
+    /// Iterate over the response body like `requests.Response.iter_content`.
+    ///
+    /// Yields chunks of `chunk_size` bytes (the last one may be shorter), or chunks as they
+    /// arrive when unset. With `decode_unicode`, chunks are decoded to `str` using the
+    /// response charset.
+    #[pyo3(signature = (chunk_size = None, decode_unicode = false))]
+    pub fn iter_content(
+        &self,
+        chunk_size: Option<usize>,
+        decode_unicode: bool,
+    ) -> PyResult<Streamer> {
+        check_chunk_size(chunk_size)?;
+        self.stream_response()
+            .map(|resp| Streamer::content(resp, chunk_size, decode_unicode))
+            .map_err(Into::into)
+    }
+
+    /// Iterate over the lines of the response body, e.g. of NDJSON or event streams.
+    ///
+    /// Lines are split on `delimiter`, or on `\n` with a trailing `\r` stripped when unset,
+    /// and decoded to `str` using `encoding`, or the response charset when unset, unless
+    /// `decode_unicode` is false. A line longer than `max_line_length` bytes raises
+    /// `BodyTooLarge` instead of being buffered without bound.
+    #[pyo3(signature = (delimiter = None, decode_unicode = true, encoding = None, max_line_length = None))]
+    pub fn iter_lines(
+        &self,
+        delimiter: Option<Vec<u8>>,
+        decode_unicode: bool,
+        encoding: Option<PyBackedStr>,
+        max_line_length: Option<usize>,
+    ) -> PyResult<Streamer> {
+        if delimiter.as_ref().is_some_and(Vec::is_empty) {
+            return Err(PyValueError::new_err("`delimiter` must not be empty"));
+        }
+        let encoding = encoding
+            .map(|label| {
+                Encoding::for_label(label.as_bytes()).ok_or_else(|| {
+                    PyValueError::new_err(format!("Unknown encoding: {}", &*label))
+                })
+            })
+            .transpose()?;
         self.stream_response()
-            .map(Streamer::new)
+            .map(|resp| Streamer::lines(resp, delimiter, decode_unicode, encoding, max_line_length))
+            .map_err(Into::into)
+    }
+
+    /// Iterate over the parts of a multipart body, e.g. a `multipart/x-mixed-replace` MJPEG
+    /// stream, yielding `(headers, body)` for each part.
+    ///
+    /// The boundary is read from the `Content-Type` header.
+    pub fn iter_multipart(&self) -> PyResult<Streamer> {
+        let parts = Multipart::new(&self.parts.headers).ok_or_else(|| {
+            PyValueError::new_err("The response is not multipart or declares no boundary")
+        })?;
+        self.stream_response()
+            .map(|resp| Streamer::parts(resp, parts))
+            .map_err(Into::into)
+    }
+
+    /// Follow cursor-based pagination, yielding the parsed JSON of each page.
+    ///
+    /// The cursor is read from each page at the dotted `cursor_path`, and `next_url` is called
+    /// with it to build the URL of the next page. Iteration stops when the cursor is missing,
+    /// null or empty, or when `next_url` returns `None`.
+    pub fn paginate_json(&self, cursor_path: PyBackedStr, next_url: Py<PyAny>) -> JsonPages {
+        let client = self.client.clone().unwrap_or_default();
+        JsonPages::new(client, self.parse_json(), &cursor_path, next_url)
+    }
+
+    /// Iterate over the response body as read-only `memoryview` chunks, as they arrive.
+    ///
+    /// Each view points straight at the received bytes instead of copying them into `bytes`.
+    pub fn iter_chunks(&self) -> PyResult<Streamer> {
+        self.stream_response()
+            .map(Streamer::views)
             .map_err(Into::into)
     }
 
@@ -224,19 +780,86 @@ impl Response {
         encoding: Option<PyBackedStr>,
     ) -> PyResult<String> {
         let fut = self
-            .cache_response()
-            .and_then(|resp| ResponseExt::text(resp, encoding))
+            .decode(|resp| ResponseExt::text(resp, encoding))
             .map_err(Into::into);
         NoGIL::new(fut, cancel).await
     }
 
+    /// Extract absolute URLs from the links of an HTML body.
+    ///
+    /// Links are resolved against `base`, the document's `<base href>`, or the response URL,
+    /// in that order. `attrs` maps tag names to the attributes searched for links.
+    #[pyo3(signature = (base = None, attrs = None))]
+    pub async fn extract_links(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+        base: Option<PyBackedStr>,
+        attrs: Option<IndexMap<String, Vec<String>>>,
+    ) -> PyResult<Vec<String>> {
+        let fut = self.links(base, attrs)?;
+        NoGIL::new(fut, cancel).await
+    }
+
+    /// Follow a `Refresh` redirect, returning the response of the refreshed page.
+    ///
+    /// The target is read from the `Refresh` header, or from a `<meta http-equiv="refresh">`
+    /// tag in an HTML body when `meta` is set. The refresh delay is waited out when `wait` is
+    /// set. Returns `None` when the response does not refresh to another URL.
+    #[pyo3(signature = (meta = true, wait = false))]
+    pub async fn follow_refresh(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+        meta: bool,
+        wait: bool,
+    ) -> PyResult<Option<Response>> {
+        let fut = self.refresh(meta, wait)?;
+        NoGIL::new(fut, cancel).await
+    }
+
     /// Get the JSON content of the response.
-    pub async fn json(&self, #[pyo3(cancel_handle)] cancel: CancelHandle) -> PyResult<Json> {
+    ///
+    /// With `loads`, the body bytes are parsed by calling it instead, e.g. `orjson.loads`.
+    #[pyo3(signature = (loads = None))]
+    pub async fn json(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+        loads: Option<Py<PyAny>>,
+    ) -> PyResult<Py<PyAny>> {
+        if let Some(loads) = loads {
+            let fut = self
+                .cache_response()
+                .and_then(ResponseExt::bytes)
+                .map_err(Into::into);
+            let bytes = NoGIL::new(fut, cancel).await?;
+            return Python::attach(|py| {
+                let json = loads.call1(py, (PyBytes::new(py, &bytes),))?;
+                self.validate(py, json)
+            });
+        }
         let fut = self
-            .cache_response()
-            .and_then(ResponseExt::json::<Json>)
+            .decode(ResponseExt::json::<Json>)
             .map_err(Into::into);
-        NoGIL::new(fut, cancel).await
+        let json = NoGIL::new(fut, cancel).await?;
+        Python::attach(|py| self.validate(py, json))
+    }
+
+    /// Get the JSON content of the response, or `default` if the status is not 2xx or the
+    /// body cannot be read or parsed. Validator errors are still raised.
+    pub async fn json_or(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+        default: Py<PyAny>,
+    ) -> PyResult<Py<PyAny>> {
+        if !self.parts.status.is_success() {
+            return Ok(default);
+        }
+        let fut = self
+            .decode(ResponseExt::json::<Json>)
+            .map(|json| Ok::<_, PyErr>(json.ok()));
+        match NoGIL::new(fut, cancel).await? {
+            Some(json) => Python::attach(|py| self.validate(py, json)),
+            None => Ok(default),
+        }
     }
 
     /// Get the bytes content of the response.
@@ -249,6 +872,67 @@ impl Response {
         NoGIL::new(fut, cancel).await
     }
 
+    /// Get the body exactly as received, without undoing its `Content-Encoding`.
+    ///
+    /// Decompression happens while the body is read, so this needs the request to be sent
+    /// with `decompress=False`, unless the body was not compressed to begin with.
+    pub async fn raw_bytes(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+    ) -> PyResult<PyBuffer> {
+        self.check_raw()?;
+        self.bytes(cancel).await
+    }
+
+    /// Stream the response body into a file, returning the number of bytes written.
+    ///
+    /// When `digest` is given, the body is hashed with `algorithm` while it is written and a
+    /// `DigestMismatch` error is raised (and the written bytes discarded) if the digests differ.
+    /// The file is overwritten unless `append` is set, and small chunks are gathered into
+    /// blocks of up to `chunk_size` bytes when it is set.
+    #[pyo3(signature = (
+        path,
+        digest = None,
+        algorithm = Algorithm::Sha256,
+        chunk_size = None,
+        append = false
+    ))]
+    pub async fn save(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+        path: PathBuf,
+        digest: Option<PyBackedStr>,
+        algorithm: Algorithm,
+        chunk_size: Option<usize>,
+        append: bool,
+    ) -> PyResult<u64> {
+        check_chunk_size(chunk_size)?;
+        let expected = digest.map(|hex| Expected::new(algorithm, &hex));
+        let fut = future::ready(self.stream_response())
+            .and_then(move |resp| ResponseExt::save(resp, path, expected, chunk_size, append))
+            .map_err(Into::into);
+        NoGIL::new(fut, cancel).await
+    }
+
+    /// Download the response body into a file without buffering it in memory, returning the
+    /// number of bytes written.
+    ///
+    /// When `chunk_size` is set, small chunks are gathered and written to the file in blocks of
+    /// up to that many bytes instead of one write per chunk received.
+    #[pyo3(signature = (path, chunk_size = None))]
+    pub async fn download(
+        &self,
+        #[pyo3(cancel_handle)] cancel: CancelHandle,
+        path: PathBuf,
+        chunk_size: Option<usize>,
+    ) -> PyResult<u64> {
+        check_chunk_size(chunk_size)?;
+        let fut = future::ready(self.stream_response())
+            .and_then(move |resp| ResponseExt::save(resp, path, None, chunk_size, false))
+            .map_err(Into::into);
+        NoGIL::new(fut, cancel).await
+    }
+
     /// Close the response.
     ///
     /// This method closes the network connection regardless of whether connection pooling is
@@ -275,6 +959,22 @@ impl Response {
     async fn __aexit__(&self, _exc_type: Py<PyAny>, _exc_val: Py<PyAny>, _traceback: Py<PyAny>) {
         self.close().await
     }
+
+    /// Get the buffered body, raising if it has not been read yet.
+    fn __bytes__(&self) -> PyResult<PyBuffer> {
+        self.buffered().map(PyBuffer::from)
+    }
+
+    /// Get the buffered body decoded with the response charset, raising if it has not been
+    /// read yet.
+    fn __str__(&self, py: Python) -> PyResult<String> {
+        let bytes = self.buffered()?;
+        Ok(py.detach(|| charset(&self.parts.headers).decode(&bytes).0.into_owned()))
+    }
+
+    fn __repr__(&self) -> String {
+        self.to_string()
+    }
 }
 
 impl Display for Response {
@@ -298,6 +998,14 @@ impl Drop for Response {
 
 // ===== impl BlockingResponse =====
 
+impl BlockingResponse {
+    /// Take the body to send as the body of another request, without buffering it.
+    #[inline]
+    pub fn pipe_body(&self) -> Result<(wreq::Body, Option<http::HeaderValue>), Error> {
+        self.0.pipe_body()
+    }
+}
+
 #[pymethods]
 impl BlockingResponse {
     /// Get the URL of the response.
@@ -312,6 +1020,24 @@ impl BlockingResponse {
         self.0.status()
     }
 
+    /// Whether the status code is within 200-299.
+    #[getter]
+    pub fn ok(&self) -> bool {
+        self.0.ok()
+    }
+
+    /// Get the time taken from starting the request until the response head arrived.
+    #[getter]
+    pub fn elapsed(&self) -> Duration {
+        self.0.elapsed()
+    }
+
+    /// Get the breakdown of where the time before the response head arrived was spent.
+    #[getter]
+    pub fn timings(&self) -> Timings {
+        self.0.timings()
+    }
+
     /// Get the HTTP version of the response.
     #[getter]
     pub fn version(&self) -> Version {
@@ -330,6 +1056,18 @@ impl BlockingResponse {
         self.0.cookies()
     }
 
+    /// Get the cookies set by this response, with their effective domain and path.
+    #[getter]
+    pub fn set_cookies(&self) -> Vec<Cookie> {
+        self.0.set_cookies()
+    }
+
+    /// Get the entries of the `Link` headers keyed by `rel`.
+    #[getter(links)]
+    pub fn header_links(&self) -> IndexMap<String, IndexMap<String, String>> {
+        self.0.header_links()
+    }
+
     /// Get the content length of the response.
     #[getter]
     pub fn content_length(&self, py: Python) -> Option<u64> {
@@ -360,54 +1098,261 @@ impl BlockingResponse {
         self.0.tls_info(py)
     }
 
-    /// Turn a response into an error if the server returned an error.
+    /// Get the finalized request, if the request was made with `return_sent=True`.
+    #[getter]
+    pub fn sent_request(&self) -> Option<SentRequest> {
+        self.0.sent_request()
+    }
+
+    /// Whether the body is the raw payload kept after decompression failed.
+    #[getter]
+    pub fn decode_fallback(&self) -> bool {
+        self.0.decode_fallback()
+    }
+
+    /// Get the trailer headers of the response.
+    ///
+    /// Only available once the body has been fully read into memory.
+    #[getter]
+    pub fn trailers(&self) -> Option<HeaderMap> {
+        self.0.trailers()
+    }
+
+    /// Get the body bytes already read into memory by `bytes()`, `text()` or `json()`.
+    ///
+    /// Raises an error if the body has not been read yet.
+    #[getter]
+    pub fn content(&self) -> PyResult<PyBuffer> {
+        self.0.content()
+    }
+
+    /// Get the size of the body after decompression, once it has been read into memory.
+    #[getter]
+    pub fn decoded_bytes(&self) -> Option<u64> {
+        self.0.decoded_bytes()
+    }
+
+    /// Get the size of the body as transferred, before decompression, once it has been read
+    /// into memory.
+    #[getter]
+    pub fn downloaded_bytes(&self) -> Option<u64> {
+        self.0.downloaded_bytes()
+    }
+
+    /// Get the caching metadata (`Age`, `Cache-Control`, `X-Cache`, ...) of the response.
     #[inline]
-    pub fn raise_for_status(&self) -> PyResult<()> {
-        self.0.raise_for_status()
+    pub fn cache_info(&self) -> CacheInfo {
+        self.0.cache_info()
+    }
+
+    /// Raise a `StatusError` if the status is 4xx or 5xx, otherwise return the response.
+    pub fn raise_for_status(slf: Bound<'_, Self>) -> PyResult<Bound<'_, Self>> {
+        slf.get()
+            .0
+            .check_status()
+            .map_err(|err| err.with_response(slf.clone().into_any().unbind()))?;
+        Ok(slf)
+    }
+
+    /// Follow cursor-based pagination, yielding the parsed JSON of each page.
+    #[inline]
+    pub fn paginate_json(&self, cursor_path: PyBackedStr, next_url: Py<PyAny>) -> JsonPages {
+        self.0.paginate_json(cursor_path, next_url)
+    }
+
+    /// Return the response itself if the status is 2xx, otherwise `default`.
+    pub fn ok_or(slf: Bound<'_, Self>, default: Py<PyAny>) -> Py<PyAny> {
+        if slf.get().0.parts.status.is_success() {
+            slf.into_any().unbind()
+        } else {
+            default
+        }
     }
 
     /// Get the response into a `Stream` of `Bytes` from the body.
+    ///
+    /// When `max_bytes` is set, the stream ends cleanly once that many bytes have been read.
+    #[inline]
+    #[pyo3(signature = (
+        max_bytes = None,
+        autodetect_compression = false,
+        chunk_timeout = None,
+        chunk_size = None
+    ))]
+    pub fn stream(
+        &self,
+        max_bytes: Option<u64>,
+        autodetect_compression: bool,
+        chunk_timeout: Option<Duration>,
+        chunk_size: Option<usize>,
+    ) -> PyResult<Streamer> {
+        self.0
+            .stream(max_bytes, autodetect_compression, chunk_timeout, chunk_size)
+    }
+
+    /// Iterate over the response body like `requests.Response.iter_content`.
     #[inline]
-    pub fn stream(&self) -> PyResult<Streamer> {
-        self.0.stream()
+    #[pyo3(signature = (chunk_size = None, decode_unicode = false))]
+    pub fn iter_content(
+        &self,
+        chunk_size: Option<usize>,
+        decode_unicode: bool,
+    ) -> PyResult<Streamer> {
+        self.0.iter_content(chunk_size, decode_unicode)
+    }
+
+    /// Iterate over the lines of the response body, e.g. of NDJSON or event streams.
+    #[inline]
+    #[pyo3(signature = (delimiter = None, decode_unicode = true, encoding = None, max_line_length = None))]
+    pub fn iter_lines(
+        &self,
+        delimiter: Option<Vec<u8>>,
+        decode_unicode: bool,
+        encoding: Option<PyBackedStr>,
+        max_line_length: Option<usize>,
+    ) -> PyResult<Streamer> {
+        self.0.iter_lines(delimiter, decode_unicode, encoding, max_line_length)
+    }
+
+    /// Iterate over the parts of a multipart body, yielding `(headers, body)` for each part.
+    #[inline]
+    pub fn iter_multipart(&self) -> PyResult<Streamer> {
+        self.0.iter_multipart()
+    }
+
+    /// Iterate over the response body as read-only `memoryview` chunks, as they arrive.
+    #[inline]
+    pub fn iter_chunks(&self) -> PyResult<Streamer> {
+        self.0.iter_chunks()
     }
 
     /// Get the text content with the response encoding, defaulting to utf-8 when unspecified.
     #[pyo3(signature = (encoding = None))]
     pub fn text(&self, py: Python, encoding: Option<PyBackedStr>) -> PyResult<String> {
-        py.detach(|| {
-            let fut = self
-                .0
-                .cache_response()
-                .and_then(|resp| ResponseExt::text(resp, encoding))
-                .map_err(Into::into);
-            pyo3_async_runtimes::tokio::get_runtime().block_on(fut)
-        })
+        let fut = self
+            .0
+            .decode(|resp| ResponseExt::text(resp, encoding))
+            .map_err(Into::into);
+        nogil::block_on(py, fut)
     }
 
-    /// Get the JSON content of the response.
-    pub fn json(&self, py: Python) -> PyResult<Json> {
-        py.detach(|| {
+    /// Extract absolute URLs from the links of an HTML body.
+    #[pyo3(signature = (base = None, attrs = None))]
+    pub fn extract_links(
+        &self,
+        py: Python,
+        base: Option<PyBackedStr>,
+        attrs: Option<IndexMap<String, Vec<String>>>,
+    ) -> PyResult<Vec<String>> {
+        let fut = self.0.links(base, attrs)?;
+        nogil::block_on(py, fut)
+    }
+
+    /// Follow a `Refresh` redirect, returning the response of the refreshed page.
+    #[pyo3(signature = (meta = true, wait = false))]
+    pub fn follow_refresh(
+        &self,
+        py: Python,
+        meta: bool,
+        wait: bool,
+    ) -> PyResult<Option<BlockingResponse>> {
+        let fut = self.0.refresh(meta, wait)?;
+        nogil::block_on(py, fut).map(|response| response.map(Into::into))
+    }
+
+    /// Get the JSON content of the response, parsed by `loads` when given.
+    #[pyo3(signature = (loads = None))]
+    pub fn json(&self, py: Python, loads: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        if let Some(loads) = loads {
             let fut = self
                 .0
                 .cache_response()
-                .and_then(ResponseExt::json::<Json>)
+                .and_then(ResponseExt::bytes)
                 .map_err(Into::into);
-            pyo3_async_runtimes::tokio::get_runtime().block_on(fut)
-        })
+            let bytes = nogil::block_on(py, fut)?;
+            let json = loads.call1(py, (PyBytes::new(py, &bytes),))?;
+            return self.0.validate(py, json);
+        }
+        let fut = self
+            .0
+            .decode(ResponseExt::json::<Json>)
+            .map_err(Into::into);
+        let json = nogil::block_on(py, fut)?;
+        self.0.validate(py, json)
+    }
+
+    /// Get the JSON content of the response, or `default` if the status is not 2xx or the
+    /// body cannot be read or parsed. Validator errors are still raised.
+    pub fn json_or(&self, py: Python, default: Py<PyAny>) -> PyResult<Py<PyAny>> {
+        if !self.0.parts.status.is_success() {
+            return Ok(default);
+        }
+        let fut = self
+            .0
+            .decode(ResponseExt::json::<Json>)
+            .map(|json| Ok::<_, PyErr>(json.ok()));
+        match nogil::block_on(py, fut)? {
+            Some(json) => self.0.validate(py, json),
+            None => Ok(default),
+        }
     }
 
     /// Get the bytes content of the response.
     pub fn bytes(&self, py: Python) -> PyResult<PyBuffer> {
-        py.detach(|| {
-            let fut = self
-                .0
-                .cache_response()
-                .and_then(ResponseExt::bytes)
-                .map_ok(PyBuffer::from)
-                .map_err(Into::into);
-            pyo3_async_runtimes::tokio::get_runtime().block_on(fut)
-        })
+        let fut = self
+            .0
+            .cache_response()
+            .and_then(ResponseExt::bytes)
+            .map_ok(PyBuffer::from)
+            .map_err(Into::into);
+        nogil::block_on(py, fut)
+    }
+
+    /// Get the body exactly as received, without undoing its `Content-Encoding`.
+    pub fn raw_bytes(&self, py: Python) -> PyResult<PyBuffer> {
+        self.0.check_raw()?;
+        self.bytes(py)
+    }
+
+    /// Stream the response body into a file, returning the number of bytes written.
+    ///
+    /// When `digest` is given, the body is hashed with `algorithm` while it is written and a
+    /// `DigestMismatch` error is raised (and the written bytes discarded) if the digests differ.
+    /// The file is overwritten unless `append` is set, and small chunks are gathered into
+    /// blocks of up to `chunk_size` bytes when it is set.
+    #[pyo3(signature = (
+        path,
+        digest = None,
+        algorithm = Algorithm::Sha256,
+        chunk_size = None,
+        append = false
+    ))]
+    pub fn save(
+        &self,
+        py: Python,
+        path: PathBuf,
+        digest: Option<PyBackedStr>,
+        algorithm: Algorithm,
+        chunk_size: Option<usize>,
+        append: bool,
+    ) -> PyResult<u64> {
+        check_chunk_size(chunk_size)?;
+        let expected = digest.map(|hex| Expected::new(algorithm, &hex));
+        let fut = future::ready(self.0.stream_response())
+            .and_then(move |resp| ResponseExt::save(resp, path, expected, chunk_size, append))
+            .map_err(Into::into);
+        nogil::block_on(py, fut)
+    }
+
+    /// Download the response body into a file without buffering it in memory, returning the
+    /// number of bytes written.
+    #[pyo3(signature = (path, chunk_size = None))]
+    pub fn download(&self, py: Python, path: PathBuf, chunk_size: Option<usize>) -> PyResult<u64> {
+        check_chunk_size(chunk_size)?;
+        let fut = future::ready(self.0.stream_response())
+            .and_then(move |resp| ResponseExt::save(resp, path, None, chunk_size, false))
+            .map_err(Into::into);
+        nogil::block_on(py, fut)
     }
 
     /// Close the response.
@@ -441,6 +1386,24 @@ impl BlockingResponse {
     ) {
         self.close(py)
     }
+
+    /// Get the buffered body, raising if it has not been read yet.
+    #[inline]
+    fn __bytes__(&self) -> PyResult<PyBuffer> {
+        self.0.__bytes__()
+    }
+
+    /// Get the buffered body decoded with the response charset, raising if it has not been
+    /// read yet.
+    #[inline]
+    fn __str__(&self, py: Python) -> PyResult<String> {
+        self.0.__str__(py)
+    }
+
+    #[inline]
+    fn __repr__(&self) -> String {
+        self.to_string()
+    }
 }
 
 impl From<Response> for BlockingResponse {
@@ -462,3 +1425,13 @@ impl Drop for BlockingResponse {
         self.0.destroy();
     }
 }
+
+/// Reject a zero `chunk_size`.
+fn check_chunk_size(chunk_size: Option<usize>) -> PyResult<()> {
+    if chunk_size == Some(0) {
+        return Err(PyValueError::new_err(
+            "`chunk_size` must be greater than zero",
+        ));
+    }
+    Ok(())
+}
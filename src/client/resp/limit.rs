@@ -0,0 +1,122 @@
+//! Capping how large a response body may grow.
+//!
+//! A body announcing a larger `Content-Length` is refused up front. Otherwise the bytes are
+//! counted as they are read, by buffering and streaming alike, since the length may be
+//! missing or wrong. The limit applies to the body after any automatic decompression.
+
+use std::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll, ready},
+};
+
+use bytes::Bytes;
+use http::response::Response as HttpResponse;
+use http_body::{Body, Frame, SizeHint};
+use pin_project_lite::pin_project;
+
+use crate::error::Error;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The error a body fails with once it grows past its limit.
+#[derive(Debug)]
+pub struct BodyTooLarge(pub u64);
+
+pin_project! {
+    /// A body failing once more than `limit` bytes of data have been read from it.
+    ///
+    /// Trailer frames and errors of the inner body are passed through unchanged.
+    struct Capped<B> {
+        #[pin]
+        inner: B,
+        read: u64,
+        limit: u64,
+    }
+}
+
+impl fmt::Display for BodyTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "response body exceeds the limit of {} bytes", self.0)
+    }
+}
+
+impl std::error::Error for BodyTooLarge {}
+
+impl<B> Body for Capped<B>
+where
+    B: Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxError>>> {
+        let this = self.project();
+        let frame = match ready!(this.inner.poll_frame(cx)) {
+            Some(Ok(frame)) => frame,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => return Poll::Ready(None),
+        };
+        if let Some(data) = frame.data_ref() {
+            *this.read += data.len() as u64;
+            if *this.read > *this.limit {
+                return Poll::Ready(Some(Err(BoxError::from(BodyTooLarge(*this.limit)))));
+            }
+        }
+        Poll::Ready(Some(Ok(frame)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Cap the body of the response at `limit` bytes.
+pub fn cap(response: wreq::Response, limit: u64) -> Result<wreq::Response, Error> {
+    if response.content_length().is_some_and(|len| len > limit) {
+        return Err(Error::BodyTooLarge(limit));
+    }
+    let (parts, body) = HttpResponse::from(response).into_parts();
+    let body = wreq::Body::wrap(Capped {
+        inner: body,
+        read: 0,
+        limit,
+    });
+    Ok(wreq::Response::from(HttpResponse::from_parts(parts, body)))
+}
+
+/// Find the error a capped body passed through from the body it wraps, so it is reported
+/// with its own kind rather than as a body error.
+pub fn passed_through(err: &wreq::Error) -> &wreq::Error {
+    if !err.is_body() {
+        return err;
+    }
+    let mut source = std::error::Error::source(err);
+    while let Some(inner) = source {
+        if let Some(inner) = inner.downcast_ref::<wreq::Error>() {
+            return inner;
+        }
+        source = inner.source();
+    }
+    err
+}
+
+/// Find the body limit that caused the given error, if any.
+pub fn exceeded(err: &(dyn std::error::Error + 'static)) -> Option<u64> {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(BodyTooLarge(limit)) = err.downcast_ref::<BodyTooLarge>() {
+            return Some(*limit);
+        }
+        source = err.source();
+    }
+    None
+}
@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use indexmap::{IndexMap, IndexSet};
+use scraper::{ElementRef, Html, Selector};
+use url::Url;
+
+/// The tags and attributes searched for links when no custom set is given.
+const DEFAULT_LINK_ATTRS: &[(&str, &str)] = &[
+    ("a", "href"),
+    ("area", "href"),
+    ("link", "href"),
+    ("img", "src"),
+    ("script", "src"),
+    ("iframe", "src"),
+    ("frame", "src"),
+    ("embed", "src"),
+    ("source", "src"),
+    ("audio", "src"),
+    ("video", "src"),
+    ("form", "action"),
+];
+
+/// Extract absolute `http(s)` URLs from an HTML document.
+///
+/// Links are resolved against `base`, or against the document's `<base href>` (itself resolved
+/// against the response URL) when no explicit base is given. Results are deduplicated and kept
+/// in document order.
+pub fn extract_links(
+    html: &str,
+    url: Url,
+    base: Option<Url>,
+    attrs: Option<IndexMap<String, Vec<String>>>,
+) -> Vec<String> {
+    let document = Html::parse_document(html);
+
+    let base = base.unwrap_or_else(|| {
+        Selector::parse("base[href]")
+            .ok()
+            .and_then(|selector| {
+                document
+                    .select(&selector)
+                    .next()
+                    .and_then(|base| base.value().attr("href"))
+                    .and_then(|href| url.join(href.trim()).ok())
+            })
+            .unwrap_or(url)
+    });
+
+    let attrs = attrs
+        .map(|attrs| {
+            attrs
+                .into_iter()
+                .map(|(tag, attrs)| {
+                    let attrs = attrs.iter().map(|attr| attr.to_ascii_lowercase()).collect();
+                    (tag.to_ascii_lowercase(), attrs)
+                })
+                .collect::<IndexMap<_, Vec<_>>>()
+        })
+        .unwrap_or_else(|| {
+            DEFAULT_LINK_ATTRS
+                .iter()
+                .fold(IndexMap::new(), |mut attrs, (tag, attr)| {
+                    attrs
+                        .entry(tag.to_string())
+                        .or_insert_with(Vec::new)
+                        .push(attr.to_string());
+                    attrs
+                })
+        });
+
+    let mut links = IndexSet::new();
+    for element in document
+        .root_element()
+        .descendants()
+        .filter_map(ElementRef::wrap)
+    {
+        let element = element.value();
+        let Some(names) = attrs.get(element.name()) else {
+            continue;
+        };
+        for value in names.iter().filter_map(|name| element.attr(name)) {
+            if let Ok(link) = base.join(value.trim()) {
+                if matches!(link.scheme(), "http" | "https") {
+                    links.insert(link.to_string());
+                }
+            }
+        }
+    }
+
+    links.into_iter().collect()
+}
+
+/// Parse a `Refresh` header value, or the `content` of a `<meta http-equiv="refresh">`, such
+/// as `5; url=https://example.com/`, into its delay and target.
+///
+/// Returns `None` when no target is given, since that only reloads the same page.
+pub fn parse_refresh(value: &str) -> Option<(Duration, String)> {
+    let (delay, rest) = value.split_once([';', ',']).unwrap_or((value, ""));
+    let delay = delay
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|delay| delay.is_finite() && *delay >= 0.0)?;
+    let rest = rest.trim_start();
+    let target = match rest.get(..3) {
+        Some(prefix) if prefix.eq_ignore_ascii_case("url") => {
+            rest[3..].trim_start().strip_prefix('=')?
+        }
+        _ => rest,
+    };
+    let target = target.trim().trim_matches(['"', '\'']).trim();
+    (!target.is_empty()).then(|| (Duration::from_secs_f64(delay), target.to_owned()))
+}
+
+/// Find the `content` of the first `<meta http-equiv="refresh">` in an HTML document.
+pub fn meta_refresh(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("meta[http-equiv][content]").ok()?;
+    document
+        .select(&selector)
+        .map(|meta| meta.value())
+        .find(|meta| {
+            meta.attr("http-equiv")
+                .is_some_and(|equiv| equiv.trim().eq_ignore_ascii_case("refresh"))
+        })
+        .and_then(|meta| meta.attr("content"))
+        .map(str::to_owned)
+}
+
+/// Parse `Link` header values (RFC 8288) into their entries keyed by `rel`, or by URL when
+/// an entry has none, each holding the `url` and its other parameters.
+///
+/// Entries without a `<url>` are skipped, and later entries win over earlier ones with the
+/// same key.
+pub fn parse_link_header<'a>(
+    values: impl Iterator<Item = &'a str>,
+) -> IndexMap<String, IndexMap<String, String>> {
+    let mut links = IndexMap::new();
+    for entry in values.flat_map(split_unquoted::<','>) {
+        let entry = entry.trim();
+        let Some((url, params)) = entry
+            .strip_prefix('<')
+            .and_then(|rest| rest.split_once('>'))
+        else {
+            continue;
+        };
+        let mut link = IndexMap::new();
+        link.insert("url".to_owned(), url.trim().to_owned());
+        for param in split_unquoted::<';'>(params) {
+            let Some((name, value)) = param.split_once('=') else {
+                continue;
+            };
+            let name = name.trim().to_ascii_lowercase();
+            if !name.is_empty() && name != "url" {
+                link.insert(name, value.trim().trim_matches('"').to_owned());
+            }
+        }
+        let key = link.get("rel").unwrap_or(&link["url"]).clone();
+        links.insert(key, link);
+    }
+    links
+}
+
+/// Split `value` on `SEP`, except inside quotes or angle brackets.
+fn split_unquoted<const SEP: char>(value: &str) -> Vec<&str> {
+    let (mut parts, mut start) = (Vec::new(), 0);
+    let (mut quoted, mut bracketed) = (false, false);
+    for (i, c) in value.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '<' if !quoted => bracketed = true,
+            '>' if !quoted => bracketed = false,
+            c if c == SEP && !quoted && !bracketed => {
+                parts.push(&value[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
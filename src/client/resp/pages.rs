@@ -0,0 +1,130 @@
+//! Following cursor-based pagination of JSON APIs.
+
+use std::sync::Arc;
+
+use futures_util::future::BoxFuture;
+use pyo3::{
+    IntoPyObjectExt,
+    prelude::*,
+    types::{PyDict, PyList},
+};
+use tokio::sync::Mutex;
+
+use crate::{
+    client::{Client, body::Json, nogil, req::execute_request},
+    error::Error,
+    http::Method,
+};
+
+/// An iterator over the pages of a cursor-paginated JSON API.
+#[derive(Clone)]
+#[pyclass(subclass, frozen, skip_from_py_object)]
+pub struct JsonPages(Arc<Mutex<Pages>>);
+
+/// The pagination state.
+struct Pages {
+    client: Client,
+    /// The dotted path of the cursor in each page.
+    cursor_path: Vec<String>,
+    /// Builds the URL of the next page from a cursor.
+    next_url: Arc<Py<PyAny>>,
+    next: Next,
+}
+
+/// What the next iteration yields.
+enum Next {
+    /// The body of the first page, being read.
+    Page(BoxFuture<'static, Result<Json, Error>>),
+    /// The URL of the next page, not requested yet.
+    Url(String),
+    /// No pages are left.
+    Done,
+}
+
+// ===== impl JsonPages =====
+
+impl JsonPages {
+    /// Create a [`JsonPages`] starting with the given first page.
+    pub fn new(
+        client: Client,
+        first: BoxFuture<'static, Result<Json, Error>>,
+        cursor_path: &str,
+        next_url: Py<PyAny>,
+    ) -> JsonPages {
+        JsonPages(Arc::new(Mutex::new(Pages {
+            client,
+            cursor_path: cursor_path.split('.').map(str::to_owned).collect(),
+            next_url: Arc::new(next_url),
+            next: Next::Page(first),
+        })))
+    }
+
+    async fn next(self, error: fn() -> Error) -> PyResult<Py<PyAny>> {
+        let mut pages = self.0.lock().await;
+
+        let json = match std::mem::replace(&mut pages.next, Next::Done) {
+            Next::Page(page) => page.await?,
+            Next::Url(url) => {
+                let resp = execute_request(pages.client.clone(), Method::GET, url, None).await?;
+                resp.check_status()?;
+                resp.parse_json().await?
+            }
+            Next::Done => return Err(error().into()),
+        };
+
+        Python::attach(|py| {
+            let page = json.into_py_any(py)?;
+            let cursor = lookup(page.bind(py), &pages.cursor_path);
+            if let Some(cursor) = cursor {
+                let url = pages.next_url.call1(py, (cursor,))?;
+                if let Some(url) = url.extract::<Option<String>>(py)? {
+                    pages.next = Next::Url(url);
+                }
+            }
+            Ok(page)
+        })
+    }
+}
+
+/// Walk a dotted path through nested objects and arrays.
+///
+/// Returns `None` when a segment is missing or the value found is `None` or empty.
+fn lookup<'py>(page: &Bound<'py, PyAny>, path: &[String]) -> Option<Bound<'py, PyAny>> {
+    let mut value = page.clone();
+    for segment in path {
+        value = if let Ok(object) = value.cast::<PyDict>() {
+            object.get_item(segment).ok()??
+        } else if let Ok(array) = value.cast::<PyList>() {
+            array.get_item(segment.parse().ok()?).ok()?
+        } else {
+            return None;
+        };
+    }
+    (!value.is_none() && value.is_truthy().unwrap_or(true)).then_some(value)
+}
+
+#[pymethods]
+impl JsonPages {
+    #[inline]
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    #[inline]
+    fn __next__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        nogil::block_on(py, self.clone().next(|| Error::StopIteration))
+    }
+
+    #[inline]
+    fn __aiter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    #[inline]
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(
+            py,
+            self.clone().next(|| Error::StopAsyncIteration),
+        )
+    }
+}
@@ -0,0 +1,369 @@
+//! Reading server-sent events, following the `text/event-stream` format.
+//!
+//! The stream is reconnected whenever it ends or breaks off, after the delay the server last
+//! asked for with `retry:`, and resumes from the last event ID by sending `Last-Event-ID`.
+
+use std::{sync::Arc, time::Duration};
+
+use bytes::{Buf, BytesMut};
+use http::{
+    HeaderName, HeaderValue, StatusCode,
+    header::{ACCEPT, CACHE_CONTROL},
+};
+use pyo3::{prelude::*, types::PyDict};
+use tokio::sync::Mutex;
+
+use crate::{
+    client::{
+        Client, nogil,
+        req::{Request, execute_request},
+    },
+    error::{ConnectionError, ConnectionResetError, Error},
+    http::Method,
+};
+
+/// The delay before reconnecting until the server sets one.
+const DEFAULT_RECONNECT: Duration = Duration::from_secs(3);
+
+/// The header resuming a stream from the last event received.
+const LAST_EVENT_ID: HeaderName = HeaderName::from_static("last-event-id");
+
+/// An event read from a server-sent event stream.
+#[pyclass(subclass, frozen, skip_from_py_object)]
+pub struct ServerSentEvent {
+    event: String,
+    data: String,
+    id: String,
+    retry: Option<Duration>,
+}
+
+/// A server-sent event stream, iterated to receive its events.
+#[derive(Clone)]
+#[pyclass(subclass, frozen, skip_from_py_object)]
+pub struct EventSource(Arc<Mutex<Source>>);
+
+/// The connection state of an [`EventSource`].
+struct Source {
+    client: Client,
+    url: String,
+    /// The request options, extracted afresh for every reconnection.
+    kwds: Option<Arc<Py<PyDict>>>,
+    /// The options of the first connection, extracted up front to fail early.
+    first: Option<Request>,
+    /// The body of the current connection, if one is open.
+    body: Option<wreq::Response>,
+    parser: Parser,
+    /// Whether the stream has been connected before, so the next connection waits first.
+    reconnecting: bool,
+    /// Set once the server answers `204 No Content`, asking not to reconnect.
+    done: bool,
+}
+
+/// Line splitting and field parsing state of an event stream.
+struct Parser {
+    buffer: BytesMut,
+    /// Whether the byte order mark at the start of a stream has been checked for.
+    started: bool,
+    /// Whether the last line ended with `\r`, so a `\n` right after belongs to it.
+    after_cr: bool,
+    event: Option<String>,
+    data: Option<String>,
+    retry: Option<Duration>,
+    /// The ID set by the event being built, which becomes the last event ID once the event
+    /// is dispatched.
+    pending_id: Option<String>,
+    /// The ID of the last dispatched event, which outlives the event that set it.
+    last_event_id: String,
+    /// The delay before reconnecting.
+    reconnect: Duration,
+}
+
+// ===== impl ServerSentEvent =====
+
+#[pymethods]
+impl ServerSentEvent {
+    /// Get the event type, `"message"` unless the server named one.
+    #[getter]
+    fn event(&self) -> &str {
+        &self.event
+    }
+
+    /// Get the event data, with multiple `data:` lines joined by newlines.
+    #[getter]
+    fn data(&self) -> &str {
+        &self.data
+    }
+
+    /// Get the last event ID seen, or `None` if the server has not set one.
+    #[getter]
+    fn id(&self) -> Option<&str> {
+        (!self.id.is_empty()).then_some(self.id.as_str())
+    }
+
+    /// Get the reconnection delay set along with this event, if any.
+    #[getter]
+    fn retry(&self) -> Option<Duration> {
+        self.retry
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ServerSentEvent(event={:?}, data={:?}, id={:?}, retry={:?})",
+            self.event,
+            self.data,
+            self.id(),
+            self.retry
+        )
+    }
+}
+
+// ===== impl EventSource =====
+
+impl EventSource {
+    /// Create an [`EventSource`] for the given URL, connecting on the first iteration.
+    pub fn new(client: Client, url: String, kwds: Option<Bound<PyDict>>) -> PyResult<EventSource> {
+        let first = kwds.as_ref().map(|kwds| kwds.extract()).transpose()?;
+        Ok(EventSource(Arc::new(Mutex::new(Source {
+            client,
+            url,
+            kwds: kwds.map(|kwds| Arc::new(kwds.unbind())),
+            first,
+            body: None,
+            parser: Parser::default(),
+            reconnecting: false,
+            done: false,
+        }))))
+    }
+
+    async fn next(self, error: fn() -> Error) -> PyResult<ServerSentEvent> {
+        let mut source = self.0.lock().await;
+        loop {
+            // A full event is already buffered.
+            if let Some(event) = source.parser.next_event() {
+                return Ok(event);
+            }
+            if source.done {
+                return Err(error().into());
+            }
+            let Some(body) = source.body.as_mut() else {
+                source.connect().await?;
+                continue;
+            };
+            match body.chunk().await {
+                Ok(Some(bytes)) => source.parser.feed(&bytes),
+                // The stream ended or broke off, drop the partial event and reconnect.
+                Ok(None) | Err(_) => {
+                    source.body = None;
+                    source.parser.reset();
+                }
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl EventSource {
+    #[inline]
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    #[inline]
+    fn __next__(&self, py: Python) -> PyResult<ServerSentEvent> {
+        nogil::block_on(py, self.clone().next(|| Error::StopIteration))
+    }
+
+    #[inline]
+    fn __aiter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    #[inline]
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        pyo3_async_runtimes::tokio::future_into_py(
+            py,
+            self.clone().next(|| Error::StopAsyncIteration),
+        )
+    }
+}
+
+// ===== impl Source =====
+
+impl Source {
+    /// Open a connection, waiting the reconnection delay first unless it is the first one.
+    ///
+    /// Connection failures while reconnecting are retried on the next call, other errors and
+    /// error statuses are raised.
+    async fn connect(&mut self) -> PyResult<()> {
+        let reconnecting = self.reconnecting;
+        if reconnecting {
+            tokio::time::sleep(self.parser.reconnect).await;
+        }
+        self.reconnecting = true;
+
+        let request = match self.first.take() {
+            Some(request) => request,
+            None => Python::attach(|py| {
+                self.kwds
+                    .as_ref()
+                    .map(|kwds| kwds.bind(py).extract::<Request>())
+                    .transpose()
+            })?
+            .unwrap_or_default(),
+        };
+        let mut request = request
+            .with_default_header(ACCEPT, HeaderValue::from_static("text/event-stream"))
+            .with_default_header(CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+        let id = HeaderValue::from_str(&self.parser.last_event_id)
+            .ok()
+            .filter(|id| !id.is_empty());
+        if let Some(id) = id {
+            request = request.with_header(LAST_EVENT_ID, id);
+        }
+
+        let resp = match execute_request(self.client.clone(), Method::GET, &self.url, Some(request))
+            .await
+        {
+            Ok(resp) => resp,
+            Err(err) if reconnecting && Python::attach(|py| is_connection_error(py, &err)) => {
+                return Ok(());
+            }
+            Err(err) => return Err(err),
+        };
+        let body = resp.stream_response()?;
+        if body.status() == StatusCode::NO_CONTENT {
+            self.done = true;
+            return Ok(());
+        }
+        Error::for_status(body.uri(), body.status())?;
+        self.body = Some(body);
+        Ok(())
+    }
+}
+
+/// Whether an error is a connection failure worth reconnecting after.
+fn is_connection_error(py: Python, err: &PyErr) -> bool {
+    err.is_instance_of::<ConnectionError>(py) || err.is_instance_of::<ConnectionResetError>(py)
+}
+
+// ===== impl Parser =====
+
+impl Default for Parser {
+    fn default() -> Self {
+        Parser {
+            buffer: BytesMut::new(),
+            started: false,
+            after_cr: false,
+            event: None,
+            data: None,
+            retry: None,
+            pending_id: None,
+            last_event_id: String::new(),
+            reconnect: DEFAULT_RECONNECT,
+        }
+    }
+}
+
+impl Parser {
+    /// Buffer bytes received from the stream, dropping a leading byte order mark.
+    fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+        if !self.started && !self.buffer.is_empty() {
+            self.started = true;
+            if self.buffer.starts_with(b"\xEF\xBB\xBF") {
+                self.buffer.advance(3);
+            }
+        }
+    }
+
+    /// Forget the partial event and buffered bytes of a connection that ended, keeping the
+    /// last event ID and reconnection delay.
+    fn reset(&mut self) {
+        self.buffer.clear();
+        self.started = false;
+        self.after_cr = false;
+        self.event = None;
+        self.data = None;
+        self.retry = None;
+        self.pending_id = None;
+    }
+
+    /// Process buffered lines until an event is complete.
+    fn next_event(&mut self) -> Option<ServerSentEvent> {
+        while let Some(line) = self.next_line() {
+            if let Some(event) = self.process(&String::from_utf8_lossy(&line)) {
+                return Some(event);
+            }
+        }
+        None
+    }
+
+    /// Split off the next line, which may end with `\r\n`, `\n` or `\r`.
+    fn next_line(&mut self) -> Option<BytesMut> {
+        if self.after_cr && !self.buffer.is_empty() {
+            if self.buffer[0] == b'\n' {
+                self.buffer.advance(1);
+            }
+            self.after_cr = false;
+        }
+        let pos = memchr::memchr2(b'\r', b'\n', &self.buffer)?;
+        let mut line = self.buffer.split_to(pos + 1);
+        self.after_cr = line[pos] == b'\r';
+        line.truncate(pos);
+        Some(line)
+    }
+
+    /// Apply a line to the event being built, returning the event once a blank line ends it.
+    fn process(&mut self, line: &str) -> Option<ServerSentEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        // Comments are used to keep idle connections open.
+        if line.starts_with(':') {
+            return None;
+        }
+        let (name, value) = match line.split_once(':') {
+            Some((name, value)) => (name, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match name {
+            "event" => self.event = Some(value.to_owned()),
+            "data" => {
+                let data = self.data.get_or_insert_with(String::new);
+                data.push_str(value);
+                data.push('\n');
+            }
+            "id" if !value.contains('\0') => self.pending_id = Some(value.to_owned()),
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse() {
+                    let retry = Duration::from_millis(millis);
+                    self.retry = Some(retry);
+                    self.reconnect = retry;
+                }
+            }
+            _ => {}
+        }
+        None
+    }
+
+    /// Finish the event being built, skipping it when it carries no data.
+    ///
+    /// The event's ID is kept as the last event ID either way.
+    fn dispatch(&mut self) -> Option<ServerSentEvent> {
+        if let Some(id) = self.pending_id.take() {
+            self.last_event_id = id;
+        }
+        let event = self.event.take();
+        let retry = self.retry.take();
+        let mut data = self.data.take()?;
+        data.pop();
+        Some(ServerSentEvent {
+            event: event
+                .filter(|event| !event.is_empty())
+                .unwrap_or_else(|| "message".to_owned()),
+            data,
+            id: self.last_event_id.clone(),
+            retry,
+        })
+    }
+}
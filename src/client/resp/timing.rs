@@ -0,0 +1,43 @@
+use std::time::Duration;
+
+use pyo3::prelude::*;
+
+/// A breakdown of where the time before the response head arrived was spent.
+///
+/// Connection setup and the TLS handshake are not reported on their own, they are part
+/// of `ttfb` whenever a new connection had to be opened.
+#[derive(Clone, Copy, Default)]
+#[pyclass(subclass, frozen, skip_from_py_object)]
+pub struct Timings {
+    dns: Option<Duration>,
+    ttfb: Duration,
+}
+
+// ===== impl Timings =====
+
+impl Timings {
+    /// Create [`Timings`] for the final attempt of a request.
+    #[inline]
+    pub fn new(dns: Option<Duration>, ttfb: Duration) -> Timings {
+        Timings { dns, ttfb }
+    }
+}
+
+#[pymethods]
+impl Timings {
+    /// Get the time spent resolving host names, or `None` if no lookup was made.
+    #[getter]
+    fn dns(&self) -> Option<Duration> {
+        self.dns
+    }
+
+    /// Get the time from sending the request until the response head arrived.
+    #[getter]
+    fn ttfb(&self) -> Duration {
+        self.ttfb
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Timings(dns={:?}, ttfb={:?})", self.dns, self.ttfb)
+    }
+}
@@ -0,0 +1,162 @@
+//! Counting the bytes of a response body as they come off the wire.
+//!
+//! Automatic decompression happens inside the HTTP stack, past the point where the body can
+//! be observed, so requests are sent with it turned off and bodies are decoded here instead:
+//! each body is counted as received first, then decoded by its `Content-Encoding`.
+
+use std::{
+    io,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    task::{Context, Poll, ready},
+};
+
+use async_compression::tokio::bufread::{BrotliDecoder, GzipDecoder, ZlibDecoder, ZstdDecoder};
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use http::{
+    HeaderValue,
+    header::{CONTENT_ENCODING, CONTENT_LENGTH},
+    response::Response as HttpResponse,
+};
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::BodyExt;
+use pin_project_lite::pin_project;
+use tokio::io::{AsyncRead, BufReader};
+use tokio_util::io::{ReaderStream, StreamReader};
+
+use super::ext::content_codings;
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// The content codings response bodies are decoded from.
+#[derive(Clone, Copy)]
+pub struct Codings {
+    pub gzip: bool,
+    pub brotli: bool,
+    pub deflate: bool,
+    pub zstd: bool,
+}
+
+pin_project! {
+    /// A body adding the length of every data frame read from it to a shared counter.
+    ///
+    /// Frames and errors of the inner body are passed through unchanged.
+    struct Counted<B> {
+        #[pin]
+        inner: B,
+        read: Arc<AtomicU64>,
+    }
+}
+
+// ===== impl Codings =====
+
+impl Codings {
+    /// Whether no coding is accepted at all.
+    pub fn is_empty(&self) -> bool {
+        !(self.gzip || self.brotli || self.deflate || self.zstd)
+    }
+
+    /// The `Accept-Encoding` header listing the accepted codings, in the order browsers send
+    /// them.
+    pub fn accept_encoding(&self) -> Option<HeaderValue> {
+        let accepted = [
+            (self.gzip, "gzip"),
+            (self.deflate, "deflate"),
+            (self.brotli, "br"),
+            (self.zstd, "zstd"),
+        ]
+        .into_iter()
+        .filter_map(|(accepted, coding)| accepted.then_some(coding))
+        .collect::<Vec<_>>();
+        (!accepted.is_empty())
+            .then(|| accepted.join(", "))
+            .and_then(|value| HeaderValue::try_from(value).ok())
+    }
+
+    fn accepts(&self, coding: &str) -> bool {
+        match coding {
+            "gzip" | "x-gzip" => self.gzip,
+            "br" => self.brotli,
+            "deflate" => self.deflate,
+            "zstd" => self.zstd,
+            _ => false,
+        }
+    }
+}
+
+// ===== impl Counted =====
+
+impl<B> Body for Counted<B>
+where
+    B: Body<Data = Bytes>,
+    B::Error: Into<BoxError>,
+{
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, BoxError>>> {
+        let this = self.project();
+        let frame = match ready!(this.inner.poll_frame(cx)) {
+            Some(Ok(frame)) => frame,
+            Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+            None => return Poll::Ready(None),
+        };
+        if let Some(data) = frame.data_ref() {
+            this.read.fetch_add(data.len() as u64, Ordering::Relaxed);
+        }
+        Poll::Ready(Some(Ok(frame)))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Count the body of the response into `read` as it arrives, then decode it by its
+/// `Content-Encoding` if every coding listed is one of `codings`.
+///
+/// A decoded body loses its `Content-Encoding` and `Content-Length` headers, and its
+/// trailers, as with automatic decompression.
+pub fn count(
+    response: wreq::Response,
+    codings: Option<Codings>,
+    read: Arc<AtomicU64>,
+) -> wreq::Response {
+    let (mut parts, body) = HttpResponse::from(response).into_parts();
+    let body = Counted { inner: body, read };
+    let encodings = content_codings(&parts.headers);
+    let decodable = !encodings.is_empty()
+        && codings.is_some_and(|codings| encodings.iter().all(|coding| codings.accepts(coding)));
+    if !decodable {
+        let body = wreq::Body::wrap(body);
+        return wreq::Response::from(HttpResponse::from_parts(parts, body));
+    }
+
+    parts.headers.remove(CONTENT_ENCODING);
+    parts.headers.remove(CONTENT_LENGTH);
+    let data = body.into_data_stream().map_err(io::Error::other);
+    let mut reader: Pin<Box<dyn AsyncRead + Send>> = Box::pin(StreamReader::new(data));
+    // Codings are listed in the order they were applied, so undo them from the last.
+    for coding in encodings.iter().rev() {
+        let buffered = BufReader::new(reader);
+        reader = match coding.as_str() {
+            "br" => Box::pin(BrotliDecoder::new(buffered)),
+            "deflate" => Box::pin(ZlibDecoder::new(buffered)),
+            "zstd" => Box::pin(ZstdDecoder::new(buffered)),
+            _ => Box::pin(GzipDecoder::new(buffered)),
+        };
+    }
+    let body = wreq::Body::wrap_stream(ReaderStream::new(reader));
+    wreq::Response::from(HttpResponse::from_parts(parts, body))
+}
@@ -4,7 +4,7 @@ pub mod msg;
 use std::{fmt::Display, time::Duration};
 
 use msg::Message;
-use pyo3::{coroutine::CancelHandle, prelude::*, pybacked::PyBackedStr};
+use pyo3::{coroutine::CancelHandle, intern, prelude::*, pybacked::PyBackedStr};
 use tokio::sync::mpsc;
 use wreq::{
     header::HeaderValue,
@@ -12,7 +12,11 @@ use wreq::{
 };
 
 use crate::{
-    client::{SocketAddr, nogil::NoGIL},
+    buffer::PyBuffer,
+    client::{
+        SocketAddr,
+        nogil::{self, NoGIL},
+    },
     cookie::Cookie,
     error::Error,
     header::HeaderMap,
@@ -49,6 +53,68 @@ pub struct WebSocket {
 #[pyclass(name = "WebSocket", subclass, frozen, str)]
 pub struct BlockingWebSocket(WebSocket);
 
+/// An iterator over received WebSocket messages with their receive metadata.
+///
+/// Each item is a `(seq, recv_time, message)` record, see [`cmd::Record`].
+#[pyclass(subclass, frozen)]
+pub struct MessageRecords {
+    cmd: mpsc::UnboundedSender<cmd::Command>,
+    timeout: Option<Duration>,
+}
+
+/// Python callbacks dispatched by the `run` receive loop.
+struct Handlers {
+    on_text: Option<Py<PyAny>>,
+    on_binary: Option<Py<PyAny>>,
+    on_close: Option<Py<PyAny>>,
+}
+
+// ===== impl Handlers =====
+
+impl Handlers {
+    /// Invoke the callback matching the kind of `message`, returning its result if one was
+    /// called. `None` means the connection ended without a close frame.
+    fn call<'py>(
+        &self,
+        py: Python<'py>,
+        message: Option<Message>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let (callback, args) = match message.map(|message| message.0) {
+            Some(ws::message::Message::Text(text)) => {
+                (&self.on_text, (text.as_str(),).into_pyobject(py)?)
+            }
+            Some(ws::message::Message::Binary(data)) => {
+                (&self.on_binary, (PyBuffer::from(data),).into_pyobject(py)?)
+            }
+            Some(ws::message::Message::Close(frame)) => {
+                let (code, reason) = frame
+                    .map(|frame| (u16::from(frame.code), frame.reason.to_string()))
+                    .unzip();
+                (&self.on_close, (code, reason).into_pyobject(py)?)
+            }
+            None => (
+                &self.on_close,
+                (None::<u16>, None::<String>).into_pyobject(py)?,
+            ),
+            Some(_) => return Ok(None),
+        };
+
+        callback
+            .as_ref()
+            .map(|callback| callback.bind(py).call1(args))
+            .transpose()
+    }
+}
+
+/// Returns `true` if no further messages will be received after `message`.
+#[inline]
+fn is_final(message: &Option<Message>) -> bool {
+    matches!(
+        message,
+        None | Some(Message(ws::message::Message::Close(_)))
+    )
+}
+
 // ===== impl WebSocket =====
 
 impl WebSocket {
@@ -141,6 +207,54 @@ impl WebSocket {
         let tx = self.cmd.clone();
         NoGIL::new(cmd::close(tx, code, reason), cancel).await
     }
+
+    /// Iterate over received messages, yielding `(seq, recv_time, message)` records.
+    #[pyo3(signature = (timeout=None))]
+    pub fn records(&self, timeout: Option<Duration>) -> MessageRecords {
+        MessageRecords {
+            cmd: self.cmd.clone(),
+            timeout,
+        }
+    }
+
+    /// Run the receive loop, dispatching each message to the matching callback until closed.
+    ///
+    /// Callbacks may be plain functions or coroutine functions; awaitable results are awaited
+    /// before the next message is dispatched.
+    #[pyo3(signature = (on_text=None, on_binary=None, on_close=None))]
+    pub fn run<'py>(
+        &self,
+        py: Python<'py>,
+        on_text: Option<Py<PyAny>>,
+        on_binary: Option<Py<PyAny>>,
+        on_close: Option<Py<PyAny>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let tx = self.cmd.clone();
+        let handlers = Handlers {
+            on_text,
+            on_binary,
+            on_close,
+        };
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            loop {
+                let message = cmd::recv(tx.clone(), None).await?;
+                let last = is_final(&message);
+                let pending = Python::attach(|py| {
+                    handlers
+                        .call(py, message)?
+                        .filter(|ret| ret.hasattr(intern!(py, "__await__")).unwrap_or(false))
+                        .map(pyo3_async_runtimes::tokio::into_future)
+                        .transpose()
+                })?;
+                if let Some(pending) = pending {
+                    pending.await?;
+                }
+                if last {
+                    return Ok(());
+                }
+            }
+        })
+    }
 }
 
 #[pymethods]
@@ -217,10 +331,7 @@ impl BlockingWebSocket {
     /// Receive a message from the WebSocket.
     #[pyo3(signature = (timeout=None))]
     pub fn recv(&self, py: Python, timeout: Option<Duration>) -> PyResult<Option<Message>> {
-        py.detach(|| {
-            pyo3_async_runtimes::tokio::get_runtime()
-                .block_on(cmd::recv(self.0.cmd.clone(), timeout))
-        })
+        nogil::block_on(py, cmd::recv(self.0.cmd.clone(), timeout))
     }
 
     /// Send a message to the WebSocket.
@@ -257,6 +368,39 @@ impl BlockingWebSocket {
             ))
         })
     }
+
+    /// Iterate over received messages, yielding `(seq, recv_time, message)` records.
+    #[pyo3(signature = (timeout=None))]
+    pub fn records(&self, timeout: Option<Duration>) -> MessageRecords {
+        self.0.records(timeout)
+    }
+
+    /// Run the receive loop, dispatching each message to the matching callback until closed.
+    #[pyo3(signature = (on_text=None, on_binary=None, on_close=None))]
+    pub fn run(
+        &self,
+        py: Python,
+        on_text: Option<Py<PyAny>>,
+        on_binary: Option<Py<PyAny>>,
+        on_close: Option<Py<PyAny>>,
+    ) -> PyResult<()> {
+        let tx = self.0.cmd.clone();
+        let handlers = Handlers {
+            on_text,
+            on_binary,
+            on_close,
+        };
+        nogil::block_on(py, async move {
+            loop {
+                let message = cmd::recv(tx.clone(), None).await?;
+                let last = is_final(&message);
+                Python::attach(|py| handlers.call(py, message).map(drop))?;
+                if last {
+                    return Ok(());
+                }
+            }
+        })
+    }
 }
 
 #[pymethods]
@@ -291,3 +435,32 @@ impl Display for BlockingWebSocket {
         self.0.fmt(f)
     }
 }
+
+// ===== impl MessageRecords =====
+
+#[pymethods]
+impl MessageRecords {
+    #[inline]
+    fn __iter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python) -> PyResult<cmd::Record> {
+        nogil::block_on(py, cmd::recv_record(self.cmd.clone(), self.timeout))?
+            .ok_or_else(|| Error::StopIteration.into())
+    }
+
+    #[inline]
+    fn __aiter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let (tx, timeout) = (self.cmd.clone(), self.timeout);
+        pyo3_async_runtimes::tokio::future_into_py(py, async move {
+            cmd::recv_record(tx, timeout)
+                .await?
+                .ok_or_else(|| Error::StopAsyncIteration.into())
+        })
+    }
+}
@@ -5,7 +5,7 @@
 //! WebSocket background task. It enables safe, concurrent, and ergonomic control
 //! of WebSocket communication from Python bindings.
 
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use bytes::Bytes;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
@@ -20,6 +20,14 @@ use super::{
     ws::{self, WebSocket},
 };
 
+/// A received message stamped with its sequence number and receive time.
+///
+/// The sequence number starts at `0` and increases by one for every message read from the
+/// connection. The receive time is the monotonic time elapsed since the background task
+/// started, captured right after the message is read, so it is neither affected by Python
+/// scheduling delays nor by changes to the system clock.
+pub type Record = (u64, Duration, Message);
+
 /// Commands for WebSocket operations.
 pub enum Command {
     /// Send a WebSocket message.
@@ -35,7 +43,7 @@ pub enum Command {
     /// Receive a WebSocket message.
     ///
     /// Contains an optional timeout and a oneshot sender for the result.
-    Recv(Option<Duration>, Sender<PyResult<Option<Message>>>),
+    Recv(Option<Duration>, Sender<PyResult<Option<Record>>>),
 
     /// Close the WebSocket connection.
     ///
@@ -48,6 +56,8 @@ pub enum Command {
 /// Handles sending, receiving, and closing the WebSocket connection based on received commands.
 pub async fn task(ws: WebSocket, mut cmd: UnboundedReceiver<Command>) {
     let (mut writer, mut reader) = ws.split();
+    let base = Instant::now();
+    let mut seq = 0u64;
     while let Some(command) = cmd.recv().await {
         match command {
             Command::Send(msg, tx) => {
@@ -74,7 +84,13 @@ pub async fn task(ws: WebSocket, mut cmd: UnboundedReceiver<Command>) {
                     reader
                         .try_next()
                         .await
-                        .map(|opt| opt.map(Message))
+                        .map(|opt| {
+                            opt.map(|msg| {
+                                let record = (seq, base.elapsed(), Message(msg));
+                                seq += 1;
+                                record
+                            })
+                        })
                         .map_err(Error::Library)
                         .map_err(Into::into)
                 };
@@ -127,6 +143,20 @@ pub async fn recv(
     cmd: UnboundedSender<Command>,
     timeout: Option<Duration>,
 ) -> PyResult<Option<Message>> {
+    recv_record(cmd, timeout)
+        .await
+        .map(|record| record.map(|(_, _, msg)| msg))
+}
+
+/// Sends a [`Command::Recv`] to the background task and awaits a message together with its
+/// [`Record`] metadata.
+///
+/// Returns the received record or an error if the connection is closed or timeout.
+#[inline]
+pub async fn recv_record(
+    cmd: UnboundedSender<Command>,
+    timeout: Option<Duration>,
+) -> PyResult<Option<Record>> {
     send_command(cmd, |tx| Command::Recv(timeout, tx)).await?
 }
 
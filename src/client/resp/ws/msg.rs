@@ -6,15 +6,17 @@
 //! extracting message content (such as text, binary data, ping/pong payloads, and close reason).
 //!
 //! The `Message` type is used for sending and receiving WebSocket messages in a unified way.
+//! Raw fragment frames can also be built for exercising a server's message reassembly.
 
 use std::fmt::Debug;
 
 use bytes::Bytes;
 use pyo3::{
+    exceptions::PyValueError,
     prelude::*,
     pybacked::{PyBackedBytes, PyBackedStr},
 };
-use wreq::ws::message::{self, CloseCode, CloseFrame, Utf8Bytes};
+use wreq::ws::message::{self, CloseCode, CloseFrame, Data, Frame, OpCode, Utf8Bytes};
 
 use crate::{buffer::PyBuffer, client::body::Json, error::Error};
 
@@ -32,6 +34,13 @@ pub enum TextLike {
     Json(Json),
 }
 
+/// An enum representing the payload of a fragment frame.
+#[derive(FromPyObject)]
+pub enum FragmentData {
+    Bytes(PyBackedBytes),
+    Text(PyBackedStr),
+}
+
 /// A WebSocket message.
 #[derive(Debug, Clone)]
 #[pyclass(subclass, str, frozen, from_py_object)]
@@ -171,6 +180,32 @@ impl Message {
         });
         Self(msg)
     }
+
+    /// Creates a raw data frame for sending a message in fragments.
+    ///
+    /// The first fragment uses the `text` or `binary` opcode, the following ones use
+    /// `continue`, and the last one sets `fin`. Frames are sent as-is, so a wrong sequence
+    /// violates the WebSocket protocol (RFC 6455, section 5.4).
+    #[staticmethod]
+    #[pyo3(signature = (data, fin = false, opcode = "continue"))]
+    pub fn fragment(data: FragmentData, fin: bool, opcode: &str) -> PyResult<Self> {
+        let opcode = match opcode {
+            "continue" => Data::Continue,
+            "text" => Data::Text,
+            "binary" => Data::Binary,
+            _ => {
+                return Err(PyValueError::new_err(format!(
+                    "Invalid fragment opcode: {opcode}, expected one of continue, text, binary"
+                )));
+            }
+        };
+        let data = match data {
+            FragmentData::Bytes(bytes) => Bytes::from_owner(bytes),
+            FragmentData::Text(text) => Bytes::from_owner(text),
+        };
+        let frame = Frame::message(data, OpCode::Data(opcode), fin);
+        Ok(Self(message::Message::Frame(frame)))
+    }
 }
 
 impl_print_str!(Display, Message);
@@ -0,0 +1,216 @@
+//! Retrying requests that fail transiently.
+//!
+//! A request is retried after connection errors and after responses with one of the
+//! configured statuses, waiting an exponentially growing delay between attempts unless the
+//! server asks for a specific one with `Retry-After`. A client-wide policy only retries
+//! idempotent methods unless told otherwise.
+
+use std::time::{Duration, SystemTime};
+
+use http::{HeaderMap, Method, StatusCode, header::RETRY_AFTER};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::error::Error;
+
+/// The statuses retried when none are given.
+const DEFAULT_STATUSES: &[u16] = &[429, 502, 503, 504];
+
+/// The methods a client-wide policy retries when none are given.
+const IDEMPOTENT_METHODS: &[Method] = &[
+    Method::GET,
+    Method::HEAD,
+    Method::PUT,
+    Method::DELETE,
+    Method::OPTIONS,
+    Method::TRACE,
+];
+
+/// A retry policy for transient failures.
+#[derive(Clone)]
+#[pyclass(frozen, from_py_object)]
+pub struct Retry {
+    max_attempts: u32,
+    backoff: Duration,
+    jitter: bool,
+    statuses: Vec<u16>,
+    /// The methods that are retried, or `None` for all of them.
+    methods: Option<Vec<Method>>,
+}
+
+#[pymethods]
+impl Retry {
+    /// Create a new [`Retry`] policy.
+    #[new]
+    #[pyo3(signature = (
+        max_attempts = 3,
+        backoff = Duration::from_millis(500),
+        jitter = true,
+        statuses = None
+    ))]
+    fn new(
+        max_attempts: u32,
+        backoff: Duration,
+        jitter: bool,
+        statuses: Option<Vec<u16>>,
+    ) -> PyResult<Retry> {
+        if max_attempts == 0 {
+            return Err(PyValueError::new_err(
+                "`max_attempts` must be greater than zero",
+            ));
+        }
+        Ok(Retry {
+            max_attempts,
+            backoff,
+            jitter,
+            statuses: statuses.unwrap_or_else(|| DEFAULT_STATUSES.to_vec()),
+            methods: None,
+        })
+    }
+
+    /// The total number of attempts, including the first one.
+    #[getter]
+    fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// The delay before the first retry, doubled for each further one.
+    #[getter]
+    fn backoff(&self) -> Duration {
+        self.backoff
+    }
+
+    /// Whether delays are randomized.
+    #[getter]
+    fn jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// The response statuses that are retried.
+    #[getter]
+    fn statuses(&self) -> Vec<u16> {
+        self.statuses.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Retry(max_attempts={}, backoff={:?}, jitter={}, statuses={:?})",
+            self.max_attempts, self.backoff, self.jitter, self.statuses
+        )
+    }
+}
+
+impl Retry {
+    /// Create the client-wide policy, retrying only idempotent methods unless `methods` is given.
+    pub fn for_client(
+        max_retries: u32,
+        backoff: Duration,
+        statuses: Option<Vec<u16>>,
+        methods: Option<Vec<Method>>,
+    ) -> Retry {
+        Retry {
+            max_attempts: max_retries.saturating_add(1),
+            backoff,
+            jitter: true,
+            statuses: statuses.unwrap_or_else(|| DEFAULT_STATUSES.to_vec()),
+            methods: Some(methods.unwrap_or_else(|| IDEMPOTENT_METHODS.to_vec())),
+        }
+    }
+
+    /// Resolve the policy for a request: its own `retry` if given, otherwise the client-wide
+    /// one, with the flat options the request sets laid over it.
+    ///
+    /// Giving a policy or `max_retries` opts the request in whatever its method, while tuning
+    /// only the backoff or the statuses keeps the client's method filter.
+    pub fn for_request(
+        client: Option<&Retry>,
+        own: Option<Retry>,
+        max_retries: Option<u32>,
+        backoff: Option<Duration>,
+        statuses: Option<Vec<u16>>,
+    ) -> Option<Retry> {
+        let opted_in = own.is_some() || max_retries.is_some();
+        let tuned = max_retries.is_some() || backoff.is_some() || statuses.is_some();
+        let mut retry = match own {
+            Some(own) => own,
+            None if tuned => client
+                .cloned()
+                .unwrap_or_else(|| Retry::for_client(2, Duration::from_millis(500), None, None)),
+            None => return client.cloned(),
+        };
+        if let Some(max_retries) = max_retries {
+            retry.max_attempts = max_retries.saturating_add(1);
+        }
+        if let Some(backoff) = backoff {
+            retry.backoff = backoff;
+        }
+        if let Some(statuses) = statuses {
+            retry.statuses = statuses;
+        }
+        if opted_in {
+            retry.methods = None;
+        }
+        Some(retry)
+    }
+
+    /// Whether requests with this method are retried.
+    #[inline]
+    pub fn retries_method(&self, method: &Method) -> bool {
+        self.methods
+            .as_ref()
+            .is_none_or(|methods| methods.contains(method))
+    }
+
+    /// Whether another attempt may follow the given number of attempts.
+    #[inline]
+    pub fn allows(&self, attempts: u32) -> bool {
+        attempts < self.max_attempts
+    }
+
+    /// Whether a response with this status should be retried.
+    #[inline]
+    pub fn retries_status(&self, status: StatusCode) -> bool {
+        self.statuses.contains(&status.as_u16())
+    }
+
+    /// Whether a failed attempt should be retried.
+    pub fn retries_error(err: &Error) -> bool {
+        matches!(err, Error::Library(err) if err.is_connect() || err.is_connection_reset())
+    }
+
+    /// The delay before the next attempt, preferring the server's `Retry-After` hint.
+    ///
+    /// Unless a timeout bounds the request, the hint is capped at the longest backoff the
+    /// policy would wait, so a server cannot stall the request indefinitely.
+    pub fn delay(&self, attempts: u32, headers: Option<&HeaderMap>, bounded: bool) -> Duration {
+        if let Some(delay) = headers.and_then(retry_after) {
+            return if bounded {
+                delay
+            } else {
+                delay.min(self.backoff_after(self.max_attempts.saturating_sub(1)))
+            };
+        }
+        let delay = self.backoff_after(attempts);
+        if self.jitter {
+            // Keep at least half of the delay so retries still back off.
+            delay / 2 + delay.mul_f64(fastrand::f64() / 2.0)
+        } else {
+            delay
+        }
+    }
+
+    /// The backoff after the given number of attempts, before any jitter.
+    fn backoff_after(&self, attempts: u32) -> Duration {
+        self.backoff
+            .saturating_mul(1 << attempts.saturating_sub(1).min(16))
+    }
+}
+
+/// Parse a `Retry-After` header given either in seconds or as an HTTP date.
+pub fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(date.duration_since(SystemTime::now()).unwrap_or_default())
+}
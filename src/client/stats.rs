@@ -0,0 +1,118 @@
+//! Counting how often requests reuse a pooled connection.
+//!
+//! The client does not report whether a response came over a pooled connection, so the counts
+//! are approximate. A connection is identified by its local and remote socket addresses: a
+//! response arriving over a pair seen recently was served by a reused connection, and any
+//! other one by a new connection that had to be opened (and handshaked) first. Pairs are
+//! forgotten once idle for longer than the pool keeps connections, so an ephemeral port reused
+//! by a later connection is not mistaken for reuse, and the set stays bounded.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use pyo3::prelude::*;
+
+/// How long the pool keeps idle connections when not configured otherwise.
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// The connection usage recorded by a client.
+pub struct ConnectionTracker {
+    /// When each connection was last seen.
+    seen: Mutex<HashMap<(SocketAddr, SocketAddr), Instant>>,
+    /// How long a connection may sit idle before the pool drops it.
+    idle_timeout: Duration,
+    requests: AtomicU64,
+    reused: AtomicU64,
+    new_connections: AtomicU64,
+}
+
+/// A snapshot of how a client's requests were served.
+#[derive(Clone, Copy)]
+#[pyclass(subclass, frozen, skip_from_py_object)]
+pub struct ConnectionStats {
+    requests: u64,
+    reused: u64,
+    new_connections: u64,
+}
+
+// ===== impl ConnectionTracker =====
+
+impl ConnectionTracker {
+    /// Create a tracker for a pool dropping connections idle for longer than `idle_timeout`.
+    pub fn new(idle_timeout: Option<Duration>) -> ConnectionTracker {
+        ConnectionTracker {
+            seen: Mutex::default(),
+            idle_timeout: idle_timeout.unwrap_or(DEFAULT_POOL_IDLE_TIMEOUT),
+            requests: AtomicU64::default(),
+            reused: AtomicU64::default(),
+            new_connections: AtomicU64::default(),
+        }
+    }
+
+    /// Record the connection a response arrived over.
+    ///
+    /// Responses without known addresses are only counted as requests.
+    pub fn record(&self, resp: &wreq::Response) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let (Some(local), Some(remote)) = (resp.local_addr(), resp.remote_addr()) else {
+            return;
+        };
+        let now = Instant::now();
+        let new = self.seen.lock().is_ok_and(|mut seen| {
+            // Connections idle past the pool's timeout are gone, whatever their ports.
+            seen.retain(|_, last| now.duration_since(*last) <= self.idle_timeout);
+            seen.insert((local, remote), now).is_none()
+        });
+        if new {
+            self.new_connections.fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.reused.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Take a snapshot of the counters.
+    pub fn snapshot(&self) -> ConnectionStats {
+        ConnectionStats {
+            requests: self.requests.load(Ordering::Relaxed),
+            reused: self.reused.load(Ordering::Relaxed),
+            new_connections: self.new_connections.load(Ordering::Relaxed),
+        }
+    }
+}
+
+// ===== impl ConnectionStats =====
+
+#[pymethods]
+impl ConnectionStats {
+    /// The number of responses received.
+    #[getter]
+    fn requests(&self) -> u64 {
+        self.requests
+    }
+
+    /// The number of responses served by a pooled connection.
+    #[getter]
+    fn reused(&self) -> u64 {
+        self.reused
+    }
+
+    /// The number of responses that required opening a new connection.
+    #[getter]
+    fn new_connections(&self) -> u64 {
+        self.new_connections
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ConnectionStats(requests={}, reused={}, new_connections={})",
+            self.requests, self.reused, self.new_connections
+        )
+    }
+}
@@ -1,8 +1,12 @@
-use std::{sync::Arc, time::SystemTime};
+use std::{net::IpAddr, path::PathBuf, sync::Arc, time::SystemTime};
 
 use bytes::Bytes;
-use cookie::{Cookie as RawCookie, Expiration, ParseError, time::Duration};
+use cookie::{
+    Cookie as RawCookie, Expiration, ParseError,
+    time::{Duration, OffsetDateTime},
+};
 use pyo3::{prelude::*, pybacked::PyBackedStr, types::PyDict};
+use serde::{Deserialize, Serialize};
 use wreq::header::{self, HeaderMap, HeaderValue};
 
 use crate::error::Error;
@@ -41,6 +45,24 @@ pub struct Cookies(pub Vec<HeaderValue>);
 #[pyclass(subclass, frozen, from_py_object)]
 pub struct Jar(pub Arc<wreq::cookie::Jar>);
 
+/// A cookie as persisted by [`Jar::save`].
+#[derive(Serialize, Deserialize)]
+struct StoredCookie {
+    /// The domain the cookie covers, or the host it is sent to alone when `host_only`.
+    domain: String,
+    /// Whether the cookie was set without a `Domain` attribute, so subdomains do not get it.
+    #[serde(default)]
+    host_only: bool,
+    path: String,
+    name: String,
+    value: String,
+    /// The expiry as a Unix timestamp in seconds, or `None` for a session cookie.
+    expires: Option<i64>,
+    secure: bool,
+    http_only: bool,
+    same_site: Option<String>,
+}
+
 // ===== impl Cookie =====
 
 #[pymethods]
@@ -174,6 +196,34 @@ impl Cookie {
             .collect()
     }
 
+    /// Parse the `Set-Cookie` cookies of a response to the given URI.
+    ///
+    /// Unlike [`Cookie::extract_headers_cookies`], a missing `Domain` or `Path` attribute is
+    /// filled in with the host and default path the cookie applies to (RFC 6265, section 5.3).
+    pub fn extract_set_cookies(headers: &HeaderMap, uri: &http::Uri) -> Vec<Cookie> {
+        let default_path = match uri.path().rfind('/') {
+            Some(0) | None => "/",
+            Some(index) => &uri.path()[..index],
+        };
+        headers
+            .get_all(header::SET_COOKIE)
+            .iter()
+            .map(Cookie::parse)
+            .flat_map(Result::ok)
+            .map(|mut cookie| {
+                if cookie.domain().is_none() {
+                    if let Some(host) = uri.host() {
+                        cookie.set_domain(host.to_owned());
+                    }
+                }
+                if cookie.path().is_none_or(|path| !path.starts_with('/')) {
+                    cookie.set_path(default_path.to_owned());
+                }
+                Cookie(cookie.into_owned())
+            })
+            .collect()
+    }
+
     fn parse<'a>(value: &'a HeaderValue) -> Result<RawCookie<'a>, ParseError> {
         std::str::from_utf8(value.as_bytes())
             .map_err(cookie::ParseError::from)
@@ -269,4 +319,111 @@ impl Jar {
     pub fn clear(&self, py: Python) {
         py.detach(|| self.0.clear())
     }
+
+    /// Save all cookies in this jar to a JSON file.
+    ///
+    /// Host-only cookies are saved as such, so they are not restored for subdomains too.
+    pub fn save(&self, py: Python, path: PathBuf) -> PyResult<()> {
+        py.detach(|| {
+            let cookies = self
+                .0
+                .get_all()
+                .map(RawCookie::from)
+                .map(|cookie| StoredCookie::from_cookie(&cookie, &self.0))
+                .collect::<Vec<_>>();
+            let json = serde_json::to_vec_pretty(&cookies).map_err(Error::Json)?;
+            std::fs::write(path, json).map_err(Error::from)?;
+            Ok(())
+        })
+    }
+
+    /// Load a jar from a JSON file written by [`Jar::save`], dropping expired cookies.
+    #[staticmethod]
+    pub fn load(py: Python, path: PathBuf) -> PyResult<Jar> {
+        py.detach(|| {
+            let json = std::fs::read(path).map_err(Error::from)?;
+            let cookies: Vec<StoredCookie> = serde_json::from_slice(&json).map_err(Error::Json)?;
+            let now = OffsetDateTime::now_utc().unix_timestamp();
+            let jar = Jar::new();
+            for cookie in cookies {
+                if cookie.expires.is_some_and(|expires| expires <= now) {
+                    continue;
+                }
+                let url = cookie.url();
+                jar.0.add(cookie.into_cookie(), url.as_str());
+            }
+            Ok(jar)
+        })
+    }
+}
+
+// ===== impl StoredCookie =====
+
+impl StoredCookie {
+    /// Capture a cookie from the jar.
+    ///
+    /// The jar reports the host a host-only cookie came from as its domain, so whether it is
+    /// host-only is told by asking the jar whether a subdomain would get this very cookie,
+    /// rather than another one of the same name.
+    fn from_cookie(cookie: &RawCookie<'_>, jar: &wreq::cookie::Jar) -> StoredCookie {
+        let expires = cookie
+            .expires_datetime()
+            .or_else(|| Some(OffsetDateTime::now_utc() + cookie.max_age()?))
+            .map(OffsetDateTime::unix_timestamp);
+        let domain = cookie.domain().unwrap_or_default().to_owned();
+        let path = cookie.path().unwrap_or("/").to_owned();
+        let secure = cookie.secure().unwrap_or(false);
+        let scheme = if secure { "https" } else { "http" };
+        let subdomain = format!("{scheme}://host-only-probe.{domain}{path}");
+        let shared = jar
+            .get(cookie.name(), subdomain.as_str())
+            .map(RawCookie::from)
+            .is_some_and(|probed| {
+                probed.value() == cookie.value()
+                    && probed.domain().unwrap_or_default() == domain
+                    && probed.path().unwrap_or("/") == path
+            });
+        StoredCookie {
+            host_only: domain.parse::<IpAddr>().is_ok() || !shared,
+            domain,
+            path,
+            name: cookie.name().to_owned(),
+            value: cookie.value().to_owned(),
+            expires,
+            secure,
+            http_only: cookie.http_only().unwrap_or(false),
+            same_site: cookie.same_site().map(|same_site| same_site.to_string()),
+        }
+    }
+
+    /// The URL the cookie is added under when loaded.
+    fn url(&self) -> String {
+        let scheme = if self.secure { "https" } else { "http" };
+        let host = self.domain.trim_start_matches('.');
+        format!("{scheme}://{host}{}", self.path)
+    }
+
+    fn into_cookie(self) -> RawCookie<'static> {
+        let mut cookie = RawCookie::new(self.name, self.value);
+        // A host-only cookie is added without a `Domain`, so the jar scopes it to the host.
+        if !self.host_only {
+            cookie.set_domain(self.domain);
+        }
+        cookie.set_path(self.path);
+        if let Some(expires) = self
+            .expires
+            .and_then(|expires| OffsetDateTime::from_unix_timestamp(expires).ok())
+        {
+            cookie.set_expires(expires);
+        }
+        cookie.set_secure(self.secure);
+        cookie.set_http_only(self.http_only);
+        cookie.set_same_site(match self.same_site.as_deref() {
+            Some("Strict") => Some(cookie::SameSite::Strict),
+            Some("Lax") => Some(cookie::SameSite::Lax),
+            Some("None") => Some(cookie::SameSite::None),
+            _ => None,
+        });
+        cookie
+    }
 }
@@ -1,17 +1,40 @@
 //! DNS resolution via the [hickory-resolver](https://github.com/hickory-dns/hickory-dns) crate
 
 use std::{
+    collections::HashMap,
+    io,
     net::{IpAddr, SocketAddr},
-    sync::{Arc, OnceLock},
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
 };
 
 use hickory_resolver::{
-    TokioResolver, config::ResolverConfig, lookup_ip::LookupIpIntoIter,
+    TokioResolver,
+    config::{NameServerConfig, ResolverConfig},
     name_server::TokioConnectionProvider,
+    proto::xfer::Protocol,
 };
-use pyo3::{prelude::*, pybacked::PyBackedStr};
+use indexmap::IndexMap;
+use pyo3::{exceptions::PyValueError, prelude::*, pybacked::PyBackedStr};
+use tokio::sync::OnceCell;
+use url::Url;
 use wreq::dns::{Addrs, Name, Resolve, Resolving};
 
+use crate::client::guard::{self, PrivateNetwork};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+tokio::task_local! {
+    /// The time spent on DNS lookups by the request currently in flight.
+    static LOOKUP_TIME: Arc<Mutex<Option<Duration>>>;
+
+    /// The port the request currently in flight is sent to.
+    static TARGET_PORT: Option<u16>;
+
+    /// The addresses pinned for host names by the request currently in flight.
+    static REQUEST_OVERRIDES: Option<Arc<HostOverrides>>;
+}
+
 define_enum!(
     /// The lookup ip strategy.
     const,
@@ -61,6 +84,147 @@ impl ResolverOptions {
     }
 }
 
+/// A DNS-over-HTTPS endpoint, optionally reached through fixed bootstrap addresses.
+#[derive(Clone)]
+#[pyclass(frozen, from_py_object)]
+pub struct DnsOverHttps {
+    endpoint: Url,
+    /// The addresses of the endpoint itself, used instead of looking its host name up.
+    bootstrap: Vec<IpAddr>,
+}
+
+#[pymethods]
+impl DnsOverHttps {
+    /// Create a new [`DnsOverHttps`] endpoint.
+    #[new]
+    #[pyo3(signature = (url, bootstrap = None))]
+    fn new(url: PyBackedStr, bootstrap: Option<Vec<IpAddr>>) -> PyResult<Self> {
+        let endpoint = parse_endpoint(&url).ok_or_else(|| {
+            PyValueError::new_err(format!("Invalid DNS-over-HTTPS endpoint: {}", &*url))
+        })?;
+        Ok(DnsOverHttps {
+            endpoint,
+            bootstrap: bootstrap.unwrap_or_default(),
+        })
+    }
+
+    /// Get the URL of the endpoint.
+    #[getter]
+    fn url(&self) -> &str {
+        self.endpoint.as_str()
+    }
+
+    /// Get the bootstrap addresses of the endpoint.
+    #[getter]
+    fn bootstrap(&self) -> Vec<IpAddr> {
+        self.bootstrap.clone()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "DnsOverHttps(url={:?}, bootstrap={:?})",
+            self.endpoint.as_str(),
+            self.bootstrap
+        )
+    }
+}
+
+/// Parse a DNS-over-HTTPS endpoint, which has to be an `https://` URL with a host.
+fn parse_endpoint(url: &str) -> Option<Url> {
+    Url::parse(url)
+        .ok()
+        .filter(|url| url.scheme() == "https" && url.host_str().is_some())
+}
+
+/// Where a client resolves host names, as given by its `dns` option.
+#[derive(Clone)]
+pub enum DnsMode {
+    /// The operating system resolver.
+    System,
+    /// Hickory, reading the system configuration.
+    Hickory,
+    /// Hickory, looking up IPv4 addresses only.
+    Ipv4,
+    /// Hickory, looking up IPv6 addresses only.
+    Ipv6,
+    /// A DNS-over-HTTPS endpoint.
+    Https(DnsOverHttps),
+}
+
+impl FromPyObject<'_, '_> for DnsMode {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<PyAny>) -> PyResult<Self> {
+        if let Ok(doh) = ob.extract::<DnsOverHttps>() {
+            return Ok(DnsMode::Https(doh));
+        }
+        let mode = ob.extract::<PyBackedStr>()?;
+        match &*mode {
+            "system" => Ok(DnsMode::System),
+            "hickory" => Ok(DnsMode::Hickory),
+            "ipv4" => Ok(DnsMode::Ipv4),
+            "ipv6" => Ok(DnsMode::Ipv6),
+            _ => parse_endpoint(&mode)
+                .map(|endpoint| {
+                    DnsMode::Https(DnsOverHttps {
+                        endpoint,
+                        bootstrap: Vec::new(),
+                    })
+                })
+                .ok_or_else(|| {
+                    PyValueError::new_err(format!(
+                        "Invalid dns option: {}, expected one of system, hickory, ipv4, ipv6 \
+                        or an https:// DNS-over-HTTPS endpoint",
+                        &*mode
+                    ))
+                }),
+        }
+    }
+}
+
+/// Static host-to-IP mappings checked before any lookup, like curl's `--resolve`.
+///
+/// Keys are host names, optionally scoped to a port as `host:port`.
+#[derive(Default)]
+pub struct HostOverrides(HashMap<String, Vec<(Option<u16>, IpAddr)>>);
+
+impl FromPyObject<'_, '_> for HostOverrides {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<PyAny>) -> PyResult<Self> {
+        let mut overrides = HostOverrides::default();
+        for (key, ip) in ob.extract::<IndexMap<PyBackedStr, IpAddr>>()? {
+            let (host, port) = match key.rsplit_once(':') {
+                Some((host, port)) if !host.contains(':') => {
+                    let port = port.parse::<u16>().map_err(|_| {
+                        PyValueError::new_err(format!("Invalid port in resolve key: {}", &*key))
+                    })?;
+                    (host, Some(port))
+                }
+                _ => (&*key, None),
+            };
+            overrides
+                .0
+                .entry(host.to_ascii_lowercase())
+                .or_default()
+                .push((port, ip));
+        }
+        Ok(overrides)
+    }
+}
+
+impl HostOverrides {
+    /// Find the address pinned for a host, preferring an entry scoped to the given port.
+    fn get(&self, host: &str, port: Option<u16>) -> Option<IpAddr> {
+        let entries = self.0.get(&host.to_ascii_lowercase())?;
+        entries
+            .iter()
+            .find(|(scope, _)| scope.is_some() && *scope == port)
+            .or_else(|| entries.iter().find(|(scope, _)| scope.is_none()))
+            .map(|(_, ip)| *ip)
+    }
+}
+
 // Static resolvers for each IP strategy, lazily initialized
 static RESOLVER_IPV4_ONLY: OnceLock<TokioResolver> = OnceLock::new();
 static RESOLVER_IPV6_ONLY: OnceLock<TokioResolver> = OnceLock::new();
@@ -71,8 +235,37 @@ static RESOLVER_IPV4_THEN_IPV6: OnceLock<TokioResolver> = OnceLock::new();
 /// Wrapper around an [`TokioResolver`], which implements the `Resolve` trait.
 #[derive(Clone)]
 pub struct HickoryDnsResolver {
-    /// Shared, lazily-initialized Tokio-based DNS resolver.
-    resolver: &'static TokioResolver,
+    /// The Tokio-based DNS resolver lookups go through.
+    resolver: Backend,
+    /// Whether to drop private network addresses from lookup results.
+    block_private_networks: bool,
+    /// The addresses pinned for host names, returned without a lookup.
+    overrides: Option<Arc<HostOverrides>>,
+}
+
+/// The resolver behind a [`HickoryDnsResolver`].
+#[derive(Clone)]
+enum Backend {
+    /// The operating system resolver (`getaddrinfo`), honoring `/etc/hosts` and NSS.
+    System(LookupIpStrategy),
+    /// Shared, lazily-initialized resolver reading the system configuration.
+    Hickory(&'static TokioResolver),
+    /// A resolver querying a DNS-over-HTTPS endpoint, built on first use.
+    Https(Arc<DohResolver>),
+    /// A Python callable returning the addresses of a host name.
+    Callback(Arc<Py<PyAny>>),
+}
+
+/// A DNS-over-HTTPS resolver, connected on the first lookup.
+///
+/// The endpoint's own host name is looked up with the system resolver at that point, unless
+/// bootstrap addresses were given, so an unreachable endpoint fails the first request rather
+/// than the client construction.
+struct DohResolver {
+    endpoint: Url,
+    bootstrap: Vec<IpAddr>,
+    strategy: LookupIpStrategy,
+    resolver: OnceCell<TokioResolver>,
 }
 
 impl HickoryDnsResolver {
@@ -90,7 +283,7 @@ impl HickoryDnsResolver {
         };
 
         HickoryDnsResolver {
-            resolver: cell.get_or_init(move || {
+            resolver: Backend::Hickory(cell.get_or_init(move || {
                 let mut builder = match TokioResolver::builder_tokio() {
                     Ok(resolver) => resolver,
                     Err(err) => {
@@ -103,33 +296,238 @@ impl HickoryDnsResolver {
                 };
                 builder.options_mut().ip_strategy = strategy.into_ffi();
                 builder.build()
-            }),
+            })),
+            block_private_networks: false,
+            overrides: None,
+        }
+    }
+
+    /// Create a new resolver going through the operating system resolver.
+    pub fn system(strategy: LookupIpStrategy) -> HickoryDnsResolver {
+        HickoryDnsResolver {
+            resolver: Backend::System(strategy),
+            block_private_networks: false,
+            overrides: None,
+        }
+    }
+
+    /// Create a new resolver querying the given DNS-over-HTTPS endpoint.
+    pub fn over_https(doh: DnsOverHttps, strategy: LookupIpStrategy) -> HickoryDnsResolver {
+        HickoryDnsResolver {
+            resolver: Backend::Https(Arc::new(DohResolver {
+                endpoint: doh.endpoint,
+                bootstrap: doh.bootstrap,
+                strategy,
+                resolver: OnceCell::new(),
+            })),
+            block_private_networks: false,
+            overrides: None,
+        }
+    }
+
+    /// Create a new resolver calling back into Python for every lookup.
+    pub fn callback(callback: Py<PyAny>) -> HickoryDnsResolver {
+        HickoryDnsResolver {
+            resolver: Backend::Callback(Arc::new(callback)),
+            block_private_networks: false,
+            overrides: None,
+        }
+    }
+
+    /// Drop loopback, private and link-local addresses from lookup results, failing the
+    /// lookup if nothing else is left. Filtering the resolved addresses themselves keeps
+    /// DNS rebinding from slipping a private address past an earlier check.
+    pub fn block_private_networks(mut self, enabled: bool) -> HickoryDnsResolver {
+        self.block_private_networks = enabled;
+        self
+    }
+
+    /// Answer the given host names with their pinned addresses instead of looking them up.
+    ///
+    /// Pinned addresses are trusted as given, so they are not subject to
+    /// [`block_private_networks`](Self::block_private_networks).
+    pub fn overrides(mut self, overrides: Option<HostOverrides>) -> HickoryDnsResolver {
+        self.overrides = overrides.map(Arc::new);
+        self
+    }
+}
+
+// ===== impl Backend =====
+
+impl Backend {
+    async fn lookup_ip(&self, name: &str) -> Result<Vec<IpAddr>, BoxError> {
+        match self {
+            Backend::System(strategy) => {
+                let addrs = tokio::net::lookup_host((name, 0)).await?;
+                let ips = filter_strategy(*strategy, addrs.map(|addr| addr.ip()).collect());
+                if ips.is_empty() {
+                    return Err(Box::new(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("no addresses found for {name}"),
+                    )));
+                }
+                Ok(ips)
+            }
+            Backend::Hickory(resolver) => Ok(resolver.lookup_ip(name).await?.into_iter().collect()),
+            Backend::Https(doh) => {
+                let resolver = doh.resolver().await?;
+                let lookup = resolver.lookup_ip(name).await;
+                Ok(lookup.map_err(|err| doh.error(err))?.into_iter().collect())
+            }
+            Backend::Callback(callback) => {
+                // The callback may block, so keep it off the runtime's worker threads.
+                let (callback, host) = (callback.clone(), name.to_owned());
+                let ips = tokio::task::spawn_blocking(move || {
+                    Python::attach(|py| callback.call1(py, (host,))?.extract::<Vec<IpAddr>>(py))
+                })
+                .await
+                .map_err(io::Error::other)?
+                .map_err(|err| {
+                    io::Error::other(format!("DNS resolver callback failed for {name}: {err}"))
+                })?;
+                if ips.is_empty() {
+                    return Err(Box::new(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("DNS resolver callback returned no addresses for {name}"),
+                    )));
+                }
+                Ok(ips)
+            }
         }
     }
 }
 
-struct SocketAddrs {
-    iter: LookupIpIntoIter,
+/// Keep the addresses a lookup strategy asks for, as hickory does for its own lookups.
+fn filter_strategy(strategy: LookupIpStrategy, ips: Vec<IpAddr>) -> Vec<IpAddr> {
+    let (v4, v6): (Vec<IpAddr>, Vec<IpAddr>) = ips.iter().partition(|ip| ip.is_ipv4());
+    match strategy {
+        LookupIpStrategy::IPV4_AND_IPV6 => ips,
+        LookupIpStrategy::IPV4_ONLY => v4,
+        LookupIpStrategy::IPV6_ONLY => v6,
+        LookupIpStrategy::IPV4_THEN_IPV6 if v4.is_empty() => v6,
+        LookupIpStrategy::IPV4_THEN_IPV6 => v4,
+        LookupIpStrategy::IPV6_THEN_IPV4 if v6.is_empty() => v4,
+        LookupIpStrategy::IPV6_THEN_IPV4 => v6,
+    }
+}
+
+// ===== impl DohResolver =====
+
+impl DohResolver {
+    /// Get the resolver, bootstrapping the connection to the endpoint on first use.
+    async fn resolver(&self) -> io::Result<&TokioResolver> {
+        self.resolver
+            .get_or_try_init(|| async {
+                let host = self.endpoint.host_str().unwrap_or_default();
+                let port = self.endpoint.port_or_known_default().unwrap_or(443);
+                let addrs: Vec<SocketAddr> = if self.bootstrap.is_empty() {
+                    tokio::net::lookup_host((host, port))
+                        .await
+                        .map_err(|err| self.error(err))?
+                        .collect()
+                } else {
+                    self.bootstrap
+                        .iter()
+                        .map(|ip| SocketAddr::new(*ip, port))
+                        .collect()
+                };
+
+                let mut config = ResolverConfig::new();
+                for addr in addrs {
+                    let mut server = NameServerConfig::new(addr, Protocol::Https);
+                    server.tls_dns_name = Some(host.trim_matches(['[', ']']).to_owned());
+                    server.http_endpoint = Some(self.endpoint.path().to_owned());
+                    config.add_name_server(server);
+                }
+                let mut builder =
+                    TokioResolver::builder_with_config(config, TokioConnectionProvider::default());
+                builder.options_mut().ip_strategy = self.strategy.into_ffi();
+                Ok(builder.build())
+            })
+            .await
+    }
+
+    /// Describe a failed lookup through the endpoint.
+    fn error(&self, err: impl std::fmt::Display) -> io::Error {
+        io::Error::other(format!(
+            "DNS-over-HTTPS lookup via {} failed: {err}",
+            self.endpoint
+        ))
+    }
 }
 
 impl Resolve for HickoryDnsResolver {
     fn resolve(&self, name: Name) -> Resolving {
         let resolver = self.clone();
         Box::pin(async move {
-            let lookup = resolver.resolver.lookup_ip(name.as_str()).await?;
-            let addrs: Addrs = Box::new(SocketAddrs {
-                iter: lookup.into_iter(),
-            });
+            // The request's own pinned addresses take precedence over the client's.
+            let port = TARGET_PORT.try_with(|port| *port).ok().flatten();
+            let request_overrides = REQUEST_OVERRIDES.try_with(Clone::clone).ok().flatten();
+            let pinned = request_overrides
+                .iter()
+                .chain(&resolver.overrides)
+                .find_map(|overrides| overrides.get(name.as_str(), port));
+            if let Some(ip) = pinned {
+                let addrs: Addrs = Box::new(std::iter::once(SocketAddr::new(ip, 0)));
+                return Ok(addrs);
+            }
+
+            let start = Instant::now();
+            let lookup = resolver.resolver.lookup_ip(name.as_str()).await;
+            record_lookup(start.elapsed());
+            let lookup = lookup?;
+            if resolver.block_private_networks {
+                let (blocked, allowed): (Vec<_>, Vec<_>) =
+                    lookup.into_iter().partition(|ip| guard::is_private(*ip));
+                return match (allowed.is_empty(), blocked.first()) {
+                    (true, Some(ip)) => Err(Box::new(PrivateNetwork(*ip)) as _),
+                    _ => {
+                        let addrs: Addrs = Box::new(
+                            allowed
+                                .into_iter()
+                                .map(|ip_addr| SocketAddr::new(ip_addr, 0)),
+                        );
+                        Ok(addrs)
+                    }
+                };
+            }
+            let addrs: Addrs = Box::new(
+                lookup
+                    .into_iter()
+                    .map(|ip_addr| SocketAddr::new(ip_addr, 0)),
+            );
             Ok(addrs)
         })
     }
 }
 
-impl Iterator for SocketAddrs {
-    type Item = SocketAddr;
+/// Add the duration of a lookup to the time of the request in flight, if it is being timed.
+fn record_lookup(elapsed: Duration) {
+    let _ = LOOKUP_TIME.try_with(|time| {
+        if let Ok(mut time) = time.lock() {
+            *time = Some(time.unwrap_or_default() + elapsed);
+        }
+    });
+}
 
-    #[inline(always)]
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|ip_addr| SocketAddr::new(ip_addr, 0))
-    }
+/// Drive a request future, returning its output along with the time spent on DNS lookups.
+///
+/// The time is `None` when no lookup was made, e.g. when a pooled connection was reused.
+pub async fn timed<F: Future>(fut: F) -> (F::Output, Option<Duration>) {
+    let time = Arc::new(Mutex::new(None));
+    let output = LOOKUP_TIME.scope(time.clone(), fut).await;
+    let time = time.lock().ok().and_then(|time| *time);
+    (output, time)
+}
+
+/// Drive a request future sent to the given port, so port-scoped [`HostOverrides`] apply,
+/// with the addresses pinned by the request itself.
+pub async fn target<F: Future>(
+    port: Option<u16>,
+    overrides: Option<Arc<HostOverrides>>,
+    fut: F,
+) -> F::Output {
+    TARGET_PORT
+        .scope(port, REQUEST_OVERRIDES.scope(overrides, fut))
+        .await
 }
@@ -1,4 +1,12 @@
-use pyo3::prelude::*;
+use std::sync::Arc;
+
+use pyo3::{exceptions::PyRuntimeError, prelude::*, pybacked::PyBackedStr, types::PyDict};
+
+use crate::{
+    header::HeaderMap,
+    http::StatusCode,
+    tls::{AlpnProtocol, TlsOptions, TlsVersion},
+};
 
 define_enum!(
     /// Selects which client profile the request should look like.
@@ -159,11 +167,16 @@ define_enum!(
 /// Represents the configuration options for emulating a client profile and platform.
 ///
 /// The `Emulation` struct allows you to configure various aspects of profile and platform
-/// emulation, including the profile, platform, and whether to enable certain features
-/// like HTTP/2 or headers.
+/// emulation, including the profile, platform, and which layers (TLS, HTTP/2, headers) of the
+/// profile are applied.
 #[derive(Clone)]
 #[pyclass(subclass, from_py_object)]
-pub struct Emulation(pub wreq_util::Emulation);
+pub struct Emulation {
+    inner: wreq_util::Emulation,
+    profile: Option<Profile>,
+    platform: Option<Platform>,
+    tls: bool,
+}
 
 #[pymethods]
 impl Emulation {
@@ -173,37 +186,236 @@ impl Emulation {
         profile = Profile::Chrome100,
         platform = Platform::MacOS,
         http2 = true,
-        headers = true
+        headers = true,
+        tls = true
     ))]
-    fn new(profile: Profile, platform: Platform, http2: bool, headers: bool) -> Self {
-        let emulation = wreq_util::Emulation::builder()
+    fn new(profile: Profile, platform: Platform, http2: bool, headers: bool, tls: bool) -> Self {
+        let inner = wreq_util::Emulation::builder()
             .profile(profile.into_ffi())
             .platform(platform.into_ffi())
             .http2(http2)
             .headers(headers)
             .build();
-        Self(emulation)
+        Self {
+            inner,
+            profile: Some(profile),
+            platform: Some(platform),
+            tls,
+        }
     }
 
     /// Creates a new random Emulation option instance.
     #[staticmethod]
     fn random() -> Self {
-        Self(wreq_util::Emulation::random())
+        Self {
+            inner: wreq_util::Emulation::random(),
+            profile: None,
+            platform: None,
+            tls: true,
+        }
+    }
+}
+
+impl wreq::IntoEmulation for Emulation {
+    fn into_emulation(self) -> wreq::Emulation {
+        let mut emulation = self.inner.into_emulation();
+        if !self.tls {
+            emulation.tls_options_mut().take();
+        }
+        emulation
     }
 }
 
 /// A helper enum to allow accepting either a Profile or an Emulation in the same parameter.
-#[derive(FromPyObject)]
+#[derive(Clone, FromPyObject)]
 pub enum EmulationLike {
     Profile(Profile),
     Emulation(Emulation),
 }
 
+impl EmulationLike {
+    /// Whether the emulation sets TLS options, which make up the TLS fingerprint.
+    pub fn has_tls(&self) -> bool {
+        wreq::IntoEmulation::into_emulation(self.clone())
+            .tls_options()
+            .is_some()
+    }
+
+    /// Whether the emulation sends an `Accept-Encoding` header of its own.
+    pub fn sets_accept_encoding(&self) -> bool {
+        wreq::IntoEmulation::into_emulation(self.clone())
+            .headers()
+            .contains_key(http::header::ACCEPT_ENCODING)
+    }
+
+    /// Whether the emulation sets HTTP/2 options, which make up the HTTP/2 fingerprint.
+    pub fn has_http2(&self) -> bool {
+        wreq::IntoEmulation::into_emulation(self.clone())
+            .http2_options()
+            .is_some()
+    }
+}
+
 impl wreq::IntoEmulation for EmulationLike {
     fn into_emulation(self) -> wreq::Emulation {
         match self {
             EmulationLike::Profile(profile) => profile.into_ffi().into_emulation(),
-            EmulationLike::Emulation(inner) => inner.0.into_emulation(),
+            EmulationLike::Emulation(emulation) => emulation.into_emulation(),
+        }
+    }
+}
+
+/// A summary of the emulation profile configured on a client.
+///
+/// The profile and platform are `None` when they are not known up front, e.g. for
+/// `Emulation.random()`. TLS details reflect any `tls_options` set on the client.
+#[derive(Clone, Default)]
+#[pyclass(subclass, frozen, skip_from_py_object)]
+pub struct EmulationInfo {
+    #[pyo3(get)]
+    profile: Option<Profile>,
+
+    #[pyo3(get)]
+    platform: Option<Platform>,
+
+    #[pyo3(get)]
+    min_tls_version: Option<TlsVersion>,
+
+    #[pyo3(get)]
+    max_tls_version: Option<TlsVersion>,
+
+    #[pyo3(get)]
+    alpn_protocols: Option<Vec<AlpnProtocol>>,
+}
+
+impl EmulationInfo {
+    /// Summarize the given client emulation and TLS options.
+    pub fn new(emulation: Option<&EmulationLike>, tls_options: Option<&TlsOptions>) -> Self {
+        let mut info = EmulationInfo::default();
+        if let Some(emulation) = emulation {
+            (info.profile, info.platform) = match emulation {
+                EmulationLike::Profile(profile) => (Some(*profile), None),
+                EmulationLike::Emulation(emulation) => (emulation.profile, emulation.platform),
+            };
+            let emulation = wreq::IntoEmulation::into_emulation(emulation.clone());
+            if let Some(tls_options) = emulation.tls_options() {
+                info.apply_tls(tls_options);
+            }
+        }
+        if let Some(tls_options) = tls_options {
+            info.apply_tls(&tls_options.0);
         }
+        info
+    }
+
+    fn apply_tls(&mut self, tls_options: &wreq::tls::TlsOptions) {
+        self.min_tls_version = tls_options.min_tls_version().map(TlsVersion::from_ffi);
+        self.max_tls_version = tls_options.max_tls_version().map(TlsVersion::from_ffi);
+        self.alpn_protocols = tls_options.alpn_protocols().map(|protocols| {
+            protocols
+                .iter()
+                .copied()
+                .map(AlpnProtocol::from_ffi)
+                .collect()
+        });
+    }
+}
+
+#[pymethods]
+impl EmulationInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "EmulationInfo(profile={:?}, platform={:?}, min_tls_version={:?}, \
+             max_tls_version={:?}, alpn_protocols={:?})",
+            self.profile,
+            self.platform,
+            self.min_tls_version,
+            self.max_tls_version,
+            self.alpn_protocols,
+        )
+    }
+}
+
+/// Emulation settings keyed by host pattern, selected per request from the target host.
+///
+/// A pattern is either an exact host (`example.com`) or a wildcard (`*.example.com`) matching
+/// any subdomain. Exact matches win over wildcards, and longer wildcards win over shorter ones.
+#[derive(Clone, Default)]
+pub struct HostEmulation(Vec<(String, EmulationLike)>);
+
+impl HostEmulation {
+    /// Find the emulation configured for the given host, if any.
+    pub fn get(&self, host: &str) -> Option<EmulationLike> {
+        let host = host.trim_end_matches('.').to_ascii_lowercase();
+        self.0
+            .iter()
+            .filter_map(|(pattern, emulation)| {
+                let rank = match pattern.strip_prefix("*.") {
+                    Some(suffix) => host
+                        .strip_suffix(suffix)
+                        .filter(|rest| rest.ends_with('.'))
+                        .map(|_| suffix.len()),
+                    None => (*pattern == host).then_some(usize::MAX),
+                };
+                rank.map(|rank| (rank, emulation))
+            })
+            .max_by_key(|(rank, _)| *rank)
+            .map(|(_, emulation)| emulation.clone())
+    }
+}
+
+impl FromPyObject<'_, '_> for HostEmulation {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<PyAny>) -> PyResult<Self> {
+        let dict = ob.cast::<PyDict>()?;
+        dict.iter()
+            .map(|(pattern, emulation)| {
+                let pattern = pattern.extract::<PyBackedStr>()?;
+                Ok((
+                    pattern.trim_end_matches('.').to_ascii_lowercase(),
+                    emulation.extract::<EmulationLike>()?,
+                ))
+            })
+            .collect::<PyResult<_>>()
+            .map(HostEmulation)
+    }
+}
+
+/// Emulations a blocked request is re-sent with, in order, once each.
+pub struct EmulationFallback {
+    profiles: Vec<EmulationLike>,
+    /// Decides whether a response was blocked from its status and headers, instead of
+    /// treating `403 Forbidden` as blocked.
+    detector: Option<Arc<Py<PyAny>>>,
+}
+
+impl EmulationFallback {
+    /// Create an [`EmulationFallback`] trying `profiles` in order.
+    pub fn new(profiles: Vec<EmulationLike>, detector: Option<Py<PyAny>>) -> Self {
+        EmulationFallback {
+            profiles,
+            detector: detector.map(Arc::new),
+        }
+    }
+
+    /// Get the emulation for the given fallback attempt, if the list is not exhausted.
+    pub fn get(&self, attempt: usize) -> Option<EmulationLike> {
+        self.profiles.get(attempt).cloned()
+    }
+
+    /// Whether the response was blocked and the request should be re-sent.
+    pub async fn is_blocked(&self, response: &wreq::Response) -> PyResult<bool> {
+        let Some(detector) = self.detector.clone() else {
+            return Ok(response.status() == wreq::StatusCode::FORBIDDEN);
+        };
+        let status = StatusCode(response.status());
+        let headers = HeaderMap(response.headers().clone());
+        // The detector may block, so keep it off the runtime's worker threads.
+        tokio::task::spawn_blocking(move || {
+            Python::attach(|py| detector.call1(py, (status, headers))?.bind(py).is_truthy())
+        })
+        .await
+        .map_err(|err| PyRuntimeError::new_err(err.to_string()))?
     }
 }
@@ -1,9 +1,19 @@
+use std::net::IpAddr;
+
 use pyo3::{
-    PyErr, create_exception,
+    Bound, Py, PyAny, PyErr, PyErrArguments, Python, create_exception,
     exceptions::{PyException, PyRuntimeError, PyStopAsyncIteration, PyStopIteration},
+    intern,
+    sync::PyOnceLock,
+    types::{PyAnyMethods, PyDict, PyDictMethods, PyTuple, PyType},
 };
 use wreq::header;
 
+use crate::{
+    client::{guard, resp::limit},
+    redirect::{self, History},
+};
+
 const RACE_CONDITION_ERROR_MSG: &str = r#"Due to Rust's memory management with borrowing,
 you cannot use certain instances multiple times as they may be consumed.
 
@@ -18,31 +28,86 @@ Potential solutions:
 3) Change the order of operations to reference the instance before borrowing it.
 "#;
 
+// The base of every error raised by the client
+create_exception!(exceptions, RnetError, PyException);
+
 // System-level and runtime errors
-create_exception!(exceptions, RustPanic, PyException);
+create_exception!(exceptions, RustPanic, RnetError);
 
 // Network connection errors
-create_exception!(exceptions, ConnectionError, PyException);
-create_exception!(exceptions, ProxyConnectionError, PyException);
-create_exception!(exceptions, ConnectionResetError, PyException);
-create_exception!(exceptions, TlsError, PyException);
+create_exception!(exceptions, ConnectionError, RnetError);
+create_exception!(exceptions, ProxyConnectionError, RnetError);
+create_exception!(exceptions, ConnectionResetError, RnetError);
+create_exception!(exceptions, TlsError, RnetError);
 
 // HTTP protocol and request/response errors
-create_exception!(exceptions, RequestError, PyException);
-create_exception!(exceptions, StatusError, PyException);
-create_exception!(exceptions, RedirectError, PyException);
-create_exception!(exceptions, TimeoutError, PyException);
+create_exception!(exceptions, RequestError, RnetError);
+create_exception!(exceptions, HostNotAllowed, RnetError);
+create_exception!(exceptions, StatusError, RnetError);
+create_exception!(exceptions, RedirectError, RnetError);
+create_exception!(exceptions, TimeoutError, RnetError);
 
 // Data processing and encoding errors
-create_exception!(exceptions, BodyError, PyException);
-create_exception!(exceptions, DecodingError, PyException);
+create_exception!(exceptions, BodyError, RnetError);
+create_exception!(exceptions, BodyTooLarge, BodyError);
+create_exception!(exceptions, DecodingError, RnetError);
+create_exception!(exceptions, DigestMismatch, RnetError);
+create_exception!(exceptions, ValidationError, RnetError);
 
 // Configuration and builder errors
-create_exception!(exceptions, BuilderError, PyException);
+create_exception!(exceptions, BuilderError, RnetError);
 
 // Protocol upgrade and WebSocket errors
-create_exception!(exceptions, UpgradeError, PyException);
-create_exception!(exceptions, WebSocketError, PyException);
+create_exception!(exceptions, UpgradeError, RnetError);
+create_exception!(exceptions, WebSocketError, RnetError);
+
+/// Declares an exception deriving from both `$base` and `RuntimeError`, for errors that were
+/// raised as a bare `RuntimeError` before, so `except RuntimeError` keeps catching them.
+macro_rules! runtime_exception {
+    ($name:ident, $base:ty) => {
+        pub struct $name;
+
+        impl $name {
+            /// Get the exception type, creating it on first use.
+            pub fn type_object(py: Python<'_>) -> &Bound<'_, PyType> {
+                static TYPE_OBJECT: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+                TYPE_OBJECT
+                    .get_or_init(py, || {
+                        let bases = PyTuple::new(
+                            py,
+                            [py.get_type::<$base>(), py.get_type::<PyRuntimeError>()],
+                        )
+                        .expect("failed to create the exception bases");
+                        let dict = PyDict::new(py);
+                        dict.set_item(intern!(py, "__module__"), intern!(py, "exceptions"))
+                            .expect("failed to set the exception module");
+                        py.get_type::<PyType>()
+                            .call1((stringify!($name), bases, dict))
+                            .and_then(|ty| ty.cast_into::<PyType>().map_err(Into::into))
+                            .expect(concat!("failed to create ", stringify!($name)))
+                            .unbind()
+                    })
+                    .bind(py)
+            }
+
+            /// Creates a new instance of the exception.
+            pub fn new_err<A>(args: A) -> PyErr
+            where
+                A: PyErrArguments + Send + Sync + 'static,
+            {
+                Python::attach(|py| PyErr::from_type(Self::type_object(py).clone(), args))
+            }
+        }
+    };
+}
+
+// Errors that also derive from `RuntimeError`
+runtime_exception!(ClientClosedError, RnetError);
+runtime_exception!(ConsumedError, RnetError);
+runtime_exception!(IoError, RnetError);
+runtime_exception!(SerializationError, RnetError);
+runtime_exception!(InvalidHeaderError, RnetError);
+runtime_exception!(WebSocketClosedError, WebSocketError);
 
 macro_rules! wrap_error {
     ($error:expr, $($variant:ident => $exception:ident),*) => {
@@ -64,54 +129,132 @@ pub enum Error {
     StopIteration,
     StopAsyncIteration,
     WebSocketDisconnected,
+    ClientClosed,
+    DigestMismatch { expected: String, actual: String },
+    HostNotAllowed(String),
+    PrivateNetwork(IpAddr),
+    BodyTooLarge(u64),
+    LineTooLong(usize),
     InvalidHeaderName(header::InvalidHeaderName),
     InvalidHeaderValue(header::InvalidHeaderValue),
     Timeout(tokio::time::error::Elapsed),
     Builder(http::Error),
+    Url(url::ParseError),
     IO(std::io::Error),
     Decode(cookie::ParseError),
     Json(serde_json::Error),
     Form(serde_urlencoded::ser::Error),
     Library(wreq::Error),
+    Redirect(wreq::Error, Vec<History>),
+    Status(String, http::StatusCode, Option<Py<PyAny>>),
 }
 
 impl From<Error> for PyErr {
     fn from(err: Error) -> Self {
         match err {
-            Error::Memory => PyRuntimeError::new_err(RACE_CONDITION_ERROR_MSG),
+            Error::Memory => ConsumedError::new_err(RACE_CONDITION_ERROR_MSG),
             Error::StopIteration => PyStopIteration::new_err("The iterator is exhausted"),
             Error::StopAsyncIteration => {
                 PyStopAsyncIteration::new_err("The async iterator is exhausted")
             }
             Error::WebSocketDisconnected => {
-                PyRuntimeError::new_err("The WebSocket has been disconnected")
+                WebSocketClosedError::new_err("The WebSocket has been disconnected")
+            }
+            Error::ClientClosed => ClientClosedError::new_err("The client has been closed"),
+            Error::DigestMismatch { expected, actual } => DigestMismatch::new_err(format!(
+                "Digest mismatch: expected {expected}, got {actual}"
+            )),
+            Error::HostNotAllowed(host) => {
+                HostNotAllowed::new_err(format!("Host is not allowed: {host}"))
+            }
+            Error::PrivateNetwork(ip) => {
+                HostNotAllowed::new_err(format!("Private network address is not allowed: {ip}"))
+            }
+            Error::BodyTooLarge(limit) => {
+                BodyTooLarge::new_err(format!("Response body exceeds the limit of {limit} bytes"))
+            }
+            Error::LineTooLong(limit) => {
+                BodyTooLarge::new_err(format!("Response line exceeds the limit of {limit} bytes"))
             }
             Error::InvalidHeaderName(err) => {
-                PyRuntimeError::new_err(format!("Invalid header name: {err:?}"))
+                InvalidHeaderError::new_err(format!("Invalid header name: {err:?}"))
             }
             Error::InvalidHeaderValue(err) => {
-                PyRuntimeError::new_err(format!("Invalid header value: {err:?}"))
+                InvalidHeaderError::new_err(format!("Invalid header value: {err:?}"))
             }
             Error::Timeout(err) => TimeoutError::new_err(format!("Timeout error: {err:?}")),
-            Error::IO(err) => PyRuntimeError::new_err(format!("IO error: {err:?}")),
+            Error::IO(err) => IoError::new_err(format!("IO error: {err:?}")),
             Error::Decode(err) => DecodingError::new_err(format!("Decode error: {err:?}")),
             Error::Builder(err) => BuilderError::new_err(format!("Builder error: {err:?}")),
-            Error::Json(err) => PyRuntimeError::new_err(format!("JSON error: {err:?}")),
-            Error::Form(err) => PyRuntimeError::new_err(format!("Form error: {err:?}")),
-            Error::Library(err) => wrap_error!(err,
-                is_body => BodyError,
-                is_tls => TlsError,
-                is_websocket => WebSocketError,
-                is_connect => ConnectionError,
-                is_proxy_connect => ProxyConnectionError,
-                is_connection_reset => ConnectionResetError,
-                is_decode => DecodingError,
-                is_redirect => RedirectError,
-                is_timeout => TimeoutError,
-                is_status => StatusError,
-                is_request => RequestError,
-                is_builder => BuilderError
-            ),
+            Error::Url(err) => BuilderError::new_err(format!("Builder error: invalid URL: {err}")),
+            Error::Json(err) => SerializationError::new_err(format!("JSON error: {err:?}")),
+            Error::Form(err) => SerializationError::new_err(format!("Form error: {err:?}")),
+            Error::Redirect(err, history) => {
+                let exc = RedirectError::new_err(format!("is_redirect error: {err:?}"));
+                Python::attach(|py| {
+                    let _ = exc.value(py).setattr(intern!(py, "history"), history);
+                    if let Some(cause) = redirect::raised(&err) {
+                        exc.set_cause(py, Some(cause.clone_ref(py)));
+                    }
+                });
+                exc
+            }
+            Error::Status(url, status, response) => {
+                let kind = if status.is_client_error() {
+                    "client"
+                } else {
+                    "server"
+                };
+                let exc = StatusError::new_err(format!(
+                    "HTTP status {kind} error ({status}) for url ({url})"
+                ));
+                Python::attach(|py| {
+                    let value = exc.value(py);
+                    let _ = value.setattr(intern!(py, "url"), url);
+                    let _ = value.setattr(intern!(py, "status"), status.as_u16());
+                    let _ = value.setattr(intern!(py, "reason"), status.canonical_reason());
+                    let _ = value.setattr(intern!(py, "response"), response);
+                });
+                exc
+            }
+            Error::Library(err) => match limit::exceeded(&err) {
+                Some(limit) => Error::BodyTooLarge(limit).into(),
+                None => {
+                    let err = limit::passed_through(&err);
+                    wrap_error!(err,
+                        is_body => BodyError,
+                        is_tls => TlsError,
+                        is_websocket => WebSocketError,
+                        is_connect => ConnectionError,
+                        is_proxy_connect => ProxyConnectionError,
+                        is_connection_reset => ConnectionResetError,
+                        is_decode => DecodingError,
+                        is_redirect => RedirectError,
+                        is_timeout => TimeoutError,
+                        is_status => StatusError,
+                        is_request => RequestError,
+                        is_builder => BuilderError
+                    )
+                }
+            },
+        }
+    }
+}
+
+impl Error {
+    /// Fail with [`Error::Status`] if the status is a client or server error.
+    pub fn for_status(url: &http::Uri, status: http::StatusCode) -> Result<(), Error> {
+        if status.is_client_error() || status.is_server_error() {
+            return Err(Error::Status(url.to_string(), status, None));
+        }
+        Ok(())
+    }
+
+    /// Attach the response that failed a status check, exposed as `exc.response`.
+    pub fn with_response(self, response: Py<PyAny>) -> Error {
+        match self {
+            Error::Status(url, status, _) => Error::Status(url, status, Some(response)),
+            err => err,
         }
     }
 }
@@ -136,7 +279,7 @@ impl From<std::io::Error> for Error {
 
 impl From<wreq::Error> for Error {
     fn from(err: wreq::Error) -> Self {
-        Error::Library(err)
+        guard::refusal(&err).unwrap_or(Error::Library(err))
     }
 }
 
@@ -18,6 +18,9 @@ pub struct HeaderMap(pub header::HeaderMap);
 #[pyclass(subclass, str, skip_from_py_object)]
 pub struct OrigHeaderMap(pub header::OrigHeaderMap);
 
+/// An `Accept` header value, accepting shorthand aliases for common media types.
+pub struct Accept(pub HeaderValue);
+
 // ===== impl HeaderMap =====
 
 #[pymethods]
@@ -364,3 +367,33 @@ impl FromPyObject<'_, '_> for OrigHeaderMap {
             .map(Self)
     }
 }
+
+// ===== impl Accept =====
+
+impl Accept {
+    /// Insert the `Accept` header into `headers` unless one was set explicitly.
+    pub fn apply_default(self, headers: &mut Option<HeaderMap>) {
+        headers
+            .get_or_insert_with(|| HeaderMap(header::HeaderMap::with_capacity(1)))
+            .0
+            .entry(header::ACCEPT)
+            .or_insert(self.0);
+    }
+}
+
+impl FromPyObject<'_, '_> for Accept {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<PyAny>) -> PyResult<Self> {
+        let value = ob.extract::<PyBackedStr>()?;
+        let value = match value.to_ascii_lowercase().as_str() {
+            "json" => HeaderValue::from_static("application/json"),
+            "html" => HeaderValue::from_static("text/html"),
+            "text" => HeaderValue::from_static("text/plain"),
+            "xml" => HeaderValue::from_static("application/xml"),
+            "*" => HeaderValue::from_static("*/*"),
+            _ => HeaderValue::from_maybe_shared(Bytes::from_owner(value)).map_err(Error::from)?,
+        };
+        Ok(Accept(value))
+    }
+}
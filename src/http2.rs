@@ -67,7 +67,7 @@ pub struct StreamId(wreq::http2::StreamId);
 /// determine the relative order in which streams should be processed.
 #[derive(Clone)]
 #[pyclass(frozen, str, from_py_object)]
-pub struct StreamDependency(wreq::http2::StreamDependency);
+pub struct StreamDependency(pub wreq::http2::StreamDependency);
 
 /// The PRIORITY frame (type=0x2) specifies the sender-advised priority
 /// of a stream [Section 5.3].  It can be sent in any stream state,
@@ -26,12 +26,17 @@ use client::{
         Streamer,
         multipart::{Multipart, Part},
     },
-    req::WebSocketRequest,
-    resp::{BlockingResponse, BlockingWebSocket, Message, Response, WebSocket},
+    req::{SentRequest, WebSocketRequest},
+    resp::{
+        BlockingResponse, BlockingWebSocket, CacheControl, CacheInfo, EventSource, JsonPages,
+        Message, MessageRecords, Response, ServerSentEvent, Timings, WebSocket,
+    },
+    retry::Retry,
+    stats::ConnectionStats,
 };
 use cookie::{Cookie, Jar, SameSite};
-use dns::{LookupIpStrategy, ResolverOptions};
-use emulate::{Emulation, Platform, Profile};
+use dns::{DnsOverHttps, LookupIpStrategy, ResolverOptions};
+use emulate::{Emulation, EmulationInfo, Platform, Profile};
 use error::*;
 use header::{HeaderMap, OrigHeaderMap};
 use http::{Method, StatusCode, Version};
@@ -80,122 +85,138 @@ mod r#async {
     /// Make a GET request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn get(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::GET, url, kwds).await
+        request(cancel, Method::GET, url, client, kwds).await
     }
 
     /// Make a POST request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn post(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::POST, url, kwds).await
+        request(cancel, Method::POST, url, client, kwds).await
     }
 
     /// Make a PUT request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn put(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::PUT, url, kwds).await
+        request(cancel, Method::PUT, url, client, kwds).await
     }
 
     /// Make a PATCH request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn patch(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::PATCH, url, kwds).await
+        request(cancel, Method::PATCH, url, client, kwds).await
     }
 
     /// Make a DELETE request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn delete(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::DELETE, url, kwds).await
+        request(cancel, Method::DELETE, url, client, kwds).await
     }
 
     /// Make a HEAD request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn head(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::HEAD, url, kwds).await
+        request(cancel, Method::HEAD, url, client, kwds).await
     }
 
     /// Make a OPTIONS request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn options(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::OPTIONS, url, kwds).await
+        request(cancel, Method::OPTIONS, url, client, kwds).await
     }
 
     /// Make a TRACE request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn trace(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        request(cancel, Method::TRACE, url, kwds).await
+        request(cancel, Method::TRACE, url, client, kwds).await
     }
 
     /// Make a request with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (method, url, **kwds))]
+    #[pyo3(signature = (method, url, *, client = None, **kwds))]
     pub async fn request(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         method: Method,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<Request>,
     ) -> PyResult<Response> {
-        Client::default().request(cancel, method, url, kwds).await
+        client
+            .map_or_else(Client::default, |client| client.get().clone())
+            .request(cancel, method, url, kwds)
+            .await
     }
 
     /// Make a WebSocket connection with the given parameters.
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub async fn websocket(
         #[pyo3(cancel_handle)] cancel: CancelHandle,
         url: PyBackedStr,
+        client: Option<Py<Client>>,
         kwds: Option<WebSocketRequest>,
     ) -> PyResult<WebSocket> {
-        Client::default().websocket(cancel, url, kwds).await
+        client
+            .map_or_else(Client::default, |client| client.get().clone())
+            .websocket(cancel, url, kwds)
+            .await
     }
 }
 
@@ -214,106 +235,138 @@ mod blocking {
     /// Make a GET request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
-    pub fn get(py: Python, url: PyBackedStr, kwds: Option<Request>) -> PyResult<BlockingResponse> {
-        request(py, Method::GET, url, kwds)
+    #[pyo3(signature = (url, *, client = None, **kwds))]
+    pub fn get(
+        py: Python,
+        url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
+        kwds: Option<Request>,
+    ) -> PyResult<BlockingResponse> {
+        request(py, Method::GET, url, client, kwds)
     }
 
     /// Make a POST request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
-    pub fn post(py: Python, url: PyBackedStr, kwds: Option<Request>) -> PyResult<BlockingResponse> {
-        request(py, Method::POST, url, kwds)
+    #[pyo3(signature = (url, *, client = None, **kwds))]
+    pub fn post(
+        py: Python,
+        url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
+        kwds: Option<Request>,
+    ) -> PyResult<BlockingResponse> {
+        request(py, Method::POST, url, client, kwds)
     }
 
     /// Make a PUT request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
-    pub fn put(py: Python, url: PyBackedStr, kwds: Option<Request>) -> PyResult<BlockingResponse> {
-        request(py, Method::PUT, url, kwds)
+    #[pyo3(signature = (url, *, client = None, **kwds))]
+    pub fn put(
+        py: Python,
+        url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
+        kwds: Option<Request>,
+    ) -> PyResult<BlockingResponse> {
+        request(py, Method::PUT, url, client, kwds)
     }
 
     /// Make a PATCH request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub fn patch(
         py: Python,
         url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
         kwds: Option<Request>,
     ) -> PyResult<BlockingResponse> {
-        request(py, Method::PATCH, url, kwds)
+        request(py, Method::PATCH, url, client, kwds)
     }
 
     /// Make a DELETE request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub fn delete(
         py: Python,
         url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
         kwds: Option<Request>,
     ) -> PyResult<BlockingResponse> {
-        request(py, Method::DELETE, url, kwds)
+        request(py, Method::DELETE, url, client, kwds)
     }
 
     /// Make a HEAD request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
-    pub fn head(py: Python, url: PyBackedStr, kwds: Option<Request>) -> PyResult<BlockingResponse> {
-        request(py, Method::HEAD, url, kwds)
+    #[pyo3(signature = (url, *, client = None, **kwds))]
+    pub fn head(
+        py: Python,
+        url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
+        kwds: Option<Request>,
+    ) -> PyResult<BlockingResponse> {
+        request(py, Method::HEAD, url, client, kwds)
     }
 
     /// Make a OPTIONS request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub fn options(
         py: Python,
         url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
         kwds: Option<Request>,
     ) -> PyResult<BlockingResponse> {
-        request(py, Method::OPTIONS, url, kwds)
+        request(py, Method::OPTIONS, url, client, kwds)
     }
 
     /// Make a TRACE request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub fn trace(
         py: Python,
         url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
         kwds: Option<Request>,
     ) -> PyResult<BlockingResponse> {
-        request(py, Method::TRACE, url, kwds)
+        request(py, Method::TRACE, url, client, kwds)
     }
 
     /// Make a request with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (method, url, **kwds))]
+    #[pyo3(signature = (method, url, *, client = None, **kwds))]
     pub fn request(
         py: Python,
         method: Method,
         url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
         kwds: Option<Request>,
     ) -> PyResult<BlockingResponse> {
-        BlockingClient::default().request(py, method, url, kwds)
+        match client {
+            Some(client) => client.request(py, method, url, kwds),
+            None => BlockingClient::default().request(py, method, url, kwds),
+        }
     }
 
     /// Make a WebSocket connection with the given parameters (blocking).
     #[inline]
     #[pyfunction]
-    #[pyo3(signature = (url, **kwds))]
+    #[pyo3(signature = (url, *, client = None, **kwds))]
     pub fn websocket(
         py: Python,
         url: PyBackedStr,
+        client: Option<PyRef<BlockingClient>>,
         kwds: Option<WebSocketRequest>,
     ) -> PyResult<BlockingWebSocket> {
-        BlockingClient::default().websocket(py, url, kwds)
+        match client {
+            Some(client) => client.websocket(py, url, kwds),
+            None => BlockingClient::default().websocket(py, url, kwds),
+        }
     }
 }
 
@@ -342,8 +395,18 @@ fn wreq(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Multipart>()?;
     m.add_class::<Client>()?;
     m.add_class::<Response>()?;
+    m.add_class::<SentRequest>()?;
+    m.add_class::<Retry>()?;
+    m.add_class::<ConnectionStats>()?;
+    m.add_class::<CacheInfo>()?;
+    m.add_class::<CacheControl>()?;
     m.add_class::<WebSocket>()?;
+    m.add_class::<MessageRecords>()?;
     m.add_class::<Streamer>()?;
+    m.add_class::<JsonPages>()?;
+    m.add_class::<EventSource>()?;
+    m.add_class::<ServerSentEvent>()?;
+    m.add_class::<Timings>()?;
     m.add_class::<Method>()?;
     m.add_class::<Version>()?;
 
@@ -414,6 +477,7 @@ fn proxy_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
 fn dns_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<LookupIpStrategy>()?;
     m.add_class::<ResolverOptions>()?;
+    m.add_class::<DnsOverHttps>()?;
     Ok(())
 }
 
@@ -471,6 +535,7 @@ fn cookie_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
 #[pymodule(gil_used = false, name = "emulation")]
 fn emulation_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Emulation>()?;
+    m.add_class::<EmulationInfo>()?;
     m.add_class::<Profile>()?;
     m.add_class::<Platform>()?;
     Ok(())
@@ -508,8 +573,10 @@ fn blocking_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
 #[pymodule(gil_used = false, name = "exceptions")]
 fn exceptions_module(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add(intern!(py, "RnetError"), py.get_type::<RnetError>())?;
     m.add(intern!(py, "TlsError"), py.get_type::<TlsError>())?;
     m.add(intern!(py, "BodyError"), py.get_type::<BodyError>())?;
+    m.add(intern!(py, "BodyTooLarge"), py.get_type::<BodyTooLarge>())?;
     m.add(intern!(py, "BuilderError"), py.get_type::<BuilderError>())?;
     m.add(
         intern!(py, "ConnectionError"),
@@ -524,15 +591,45 @@ fn exceptions_module(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
         py.get_type::<ConnectionResetError>(),
     )?;
     m.add(intern!(py, "DecodingError"), py.get_type::<DecodingError>())?;
+    m.add(
+        intern!(py, "DigestMismatch"),
+        py.get_type::<DigestMismatch>(),
+    )?;
+    m.add(
+        intern!(py, "ValidationError"),
+        py.get_type::<ValidationError>(),
+    )?;
     m.add(intern!(py, "RedirectError"), py.get_type::<RedirectError>())?;
     m.add(intern!(py, "TimeoutError"), py.get_type::<TimeoutError>())?;
     m.add(intern!(py, "StatusError"), py.get_type::<StatusError>())?;
     m.add(intern!(py, "RequestError"), py.get_type::<RequestError>())?;
+    m.add(
+        intern!(py, "HostNotAllowed"),
+        py.get_type::<HostNotAllowed>(),
+    )?;
     m.add(intern!(py, "UpgradeError"), py.get_type::<UpgradeError>())?;
     m.add(
         intern!(py, "WebSocketError"),
         py.get_type::<WebSocketError>(),
     )?;
     m.add(intern!(py, "RustPanic"), py.get_type::<RustPanic>())?;
+    m.add(
+        intern!(py, "ClientClosedError"),
+        ClientClosedError::type_object(py),
+    )?;
+    m.add(intern!(py, "ConsumedError"), ConsumedError::type_object(py))?;
+    m.add(intern!(py, "IoError"), IoError::type_object(py))?;
+    m.add(
+        intern!(py, "SerializationError"),
+        SerializationError::type_object(py),
+    )?;
+    m.add(
+        intern!(py, "InvalidHeaderError"),
+        InvalidHeaderError::type_object(py),
+    )?;
+    m.add(
+        intern!(py, "WebSocketClosedError"),
+        WebSocketClosedError::type_object(py),
+    )?;
     Ok(())
 }
@@ -1,5 +1,6 @@
 use bytes::Bytes;
-use pyo3::{prelude::*, pybacked::PyBackedStr};
+use pyo3::{exceptions::PyValueError, prelude::*, pybacked::PyBackedStr};
+use url::Url;
 use wreq::header::HeaderValue;
 
 use crate::{error::Error, header::HeaderMap};
@@ -27,7 +28,17 @@ struct Builder {
 /// Supports HTTP, HTTPS, SOCKS4, SOCKS4a, SOCKS5, and SOCKS5h protocols.
 #[derive(Clone)]
 #[pyclass(subclass, frozen, str, from_py_object)]
-pub struct Proxy(pub wreq::Proxy);
+pub struct Proxy(pub wreq::Proxy, pub ProxyTarget);
+
+/// Where a proxy lives, kept for describing it outside of wreq (e.g. as `curl` flags).
+#[derive(Clone)]
+pub enum ProxyTarget {
+    Url {
+        url: String,
+        basic_auth: Option<(String, String)>,
+    },
+    Unix(String),
+}
 
 // ===== impl Builder =====
 
@@ -55,7 +66,7 @@ impl Proxy {
     #[staticmethod]
     #[pyo3(signature = (url, **kwds))]
     fn http(py: Python, url: &str, kwds: Option<Builder>) -> PyResult<Self> {
-        create_proxy(py, wreq::Proxy::http, url, kwds)
+        create_proxy(py, wreq::Proxy::http, url, false, kwds)
     }
 
     /// Creates a new HTTPS proxy.
@@ -64,7 +75,7 @@ impl Proxy {
     #[staticmethod]
     #[pyo3(signature = (url, **kwds))]
     fn https(py: Python, url: &str, kwds: Option<Builder>) -> PyResult<Self> {
-        create_proxy(py, wreq::Proxy::https, url, kwds)
+        create_proxy(py, wreq::Proxy::https, url, false, kwds)
     }
 
     /// Creates a new proxy for all protocols.
@@ -73,7 +84,27 @@ impl Proxy {
     #[staticmethod]
     #[pyo3(signature = (url, **kwds))]
     fn all(py: Python, url: &str, kwds: Option<Builder>) -> PyResult<Self> {
-        create_proxy(py, wreq::Proxy::all, url, kwds)
+        create_proxy(py, wreq::Proxy::all, url, false, kwds)
+    }
+
+    /// Creates a new SOCKS5 proxy for all protocols, resolving host names locally.
+    ///
+    /// Credentials given in the URL or as `username`/`password` are used for the SOCKS5
+    /// handshake.
+    #[staticmethod]
+    #[pyo3(signature = (url, **kwds))]
+    fn socks5(py: Python, url: &str, kwds: Option<Builder>) -> PyResult<Self> {
+        create_socks5_proxy(py, "socks5", url, kwds)
+    }
+
+    /// Creates a new SOCKS5 proxy for all protocols, letting the proxy resolve host names.
+    ///
+    /// Credentials given in the URL or as `username`/`password` are used for the SOCKS5
+    /// handshake.
+    #[staticmethod]
+    #[pyo3(signature = (url, **kwds))]
+    fn socks5h(py: Python, url: &str, kwds: Option<Builder>) -> PyResult<Self> {
+        create_socks5_proxy(py, "socks5h", url, kwds)
     }
 
     /// Creates a new UNIX domain socket proxy.
@@ -89,26 +120,64 @@ impl Proxy {
         }
 
         #[cfg(unix)]
-        create_proxy(py, wreq::Proxy::unix, path, kwds)
+        create_proxy(py, wreq::Proxy::unix, path, true, kwds)
     }
 }
 
 impl_print_str!(Debug, Proxy);
 
+/// Create a SOCKS5 proxy, moving any `username`/`password` into the URL so they are sent in
+/// the SOCKS5 handshake rather than as an HTTP header.
+fn create_socks5_proxy(
+    py: Python,
+    scheme: &str,
+    url: &str,
+    mut builder: Option<Builder>,
+) -> PyResult<Proxy> {
+    let address = match url.split_once("://") {
+        Some((given, address))
+            if given.eq_ignore_ascii_case("socks5") || given.eq_ignore_ascii_case("socks5h") =>
+        {
+            address
+        }
+        Some((given, _)) => {
+            return Err(PyValueError::new_err(format!(
+                "Expected a SOCKS5 proxy URL, got scheme `{given}`"
+            )));
+        }
+        None => url,
+    };
+    let mut url = Url::parse(&format!("{scheme}://{address}"))
+        .map_err(|err| PyValueError::new_err(format!("Invalid SOCKS5 proxy URL: {err}")))?;
+
+    if let Some(builder) = builder.as_mut() {
+        if let Some((username, password)) = builder.username.take().zip(builder.password.take()) {
+            url.set_username(&username)
+                .and_then(|_| url.set_password(Some(&password)))
+                .map_err(|_| PyValueError::new_err("Invalid SOCKS5 proxy credentials"))?;
+        }
+    }
+
+    create_proxy(py, wreq::Proxy::all, url.as_str(), false, builder)
+}
+
 fn create_proxy<'py>(
     py: Python<'py>,
     proxy_fn: fn(&'py str) -> wreq::Result<wreq::Proxy>,
     url: &'py str,
+    unix: bool,
     builder: Option<Builder>,
 ) -> PyResult<Proxy> {
     py.detach(|| {
         // Create base proxy using the provided constructor (http, https, all)
         let mut proxy = proxy_fn(url).map_err(Error::Library)?;
+        let mut basic_auth = None;
 
         if let Some(builder) = builder {
             // Convert the username and password to a basic auth header value.
             if let Some((username, password)) = builder.username.zip(builder.password) {
                 proxy = proxy.basic_auth(username.as_ref(), password.as_ref());
+                basic_auth = Some((username.to_string(), password.to_string()));
             }
 
             // Convert the custom HTTP auth string to a header value.
@@ -131,6 +200,14 @@ fn create_proxy<'py>(
             }
         }
 
-        Ok(Proxy(proxy))
+        let target = if unix {
+            ProxyTarget::Unix(url.to_owned())
+        } else {
+            ProxyTarget::Url {
+                url: url.to_owned(),
+                basic_auth,
+            }
+        };
+        Ok(Proxy(proxy, target))
     })
 }
@@ -1,12 +1,34 @@
-use std::{fmt::Display, sync::Arc};
+use std::{
+    fmt::Display,
+    sync::{Arc, Mutex},
+};
 
-use pyo3::prelude::*;
+use http::header::{
+    AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, COOKIE, HeaderName, PROXY_AUTHORIZATION,
+    TRANSFER_ENCODING, WWW_AUTHENTICATE,
+};
+use pyo3::{exceptions::PyTypeError, prelude::*};
 
-use crate::{header::HeaderMap, http::StatusCode};
+use crate::{client::guard, error::Error, header::HeaderMap, http::StatusCode};
+
+/// The default maximum number of redirects followed by [`Policy::limited`].
+const DEFAULT_MAX_REDIRECTS: usize = 10;
+
+/// The headers dropped from a request redirected to another origin.
+const SENSITIVE_HEADERS: [HeaderName; 4] =
+    [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE, WWW_AUTHENTICATE];
+
+tokio::task_local! {
+    /// The redirect hops followed by the request currently in flight.
+    static TRAIL: Arc<Mutex<Vec<History>>>;
+}
 
 /// Represents the redirect policy for HTTP requests.
+///
+/// Options taking a policy also accept a callable `(url, previous_url, status) -> bool`,
+/// see [`Policy::decide`].
 #[derive(Clone)]
-#[pyclass(frozen, str, from_py_object)]
+#[pyclass(frozen, str, skip_from_py_object)]
 pub struct Policy(pub wreq::redirect::Policy);
 
 /// A type that holds information on the next request and previous requests
@@ -41,33 +63,57 @@ enum ActionKind {
 }
 
 /// An entry in the redirect history.
-#[pyclass(subclass, str, frozen)]
-pub struct History(pub wreq::redirect::HistoryEntry);
+#[derive(Clone, Debug)]
+#[pyclass(subclass, str, frozen, skip_from_py_object)]
+pub struct History {
+    status: http::StatusCode,
+    /// The method of the request the redirect response answered.
+    method: http::Method,
+    uri: String,
+    previous: String,
+    headers: http::HeaderMap,
+}
+
+/// The hops followed before a redirect chain was resumed from a failed hop, kept in the
+/// extensions of the final response.
+#[derive(Clone)]
+pub struct Resumed(pub Vec<History>);
+
+/// The error raised when a redirect chain exceeds a [`Policy::limited`] maximum, either in
+/// total or for the given host.
+#[derive(Debug)]
+struct TooManyRedirects(Option<String>);
 
 #[pymethods]
 impl History {
     /// Get the status code of the redirect response.
     #[getter]
     fn status(&self) -> u16 {
-        self.0.status.as_u16()
+        self.status.as_u16()
+    }
+
+    /// Get the method of the request the redirect response answered.
+    #[getter]
+    fn method(&self) -> &str {
+        self.method.as_str()
     }
 
     /// Get the URL of the redirect response.
     #[getter]
     fn url(&self) -> String {
-        self.0.uri.to_string()
+        self.uri.clone()
     }
 
     /// Get the previous URL before the redirect response.
     #[getter]
     fn previous(&self) -> String {
-        self.0.previous.to_string()
+        self.previous.clone()
     }
 
     /// Get the headers of the redirect response.
     #[getter]
     fn headers(&self) -> HeaderMap {
-        HeaderMap(self.0.headers.clone())
+        HeaderMap(self.headers.clone())
     }
 }
 
@@ -77,14 +123,32 @@ impl History {
 impl Policy {
     /// Create a [`Policy`] with a maximum number of redirects.
     ///
-    /// An `Error` will be returned if the max is reached.
+    /// An `Error` will be returned if the max is reached. When `max_per_host` is set, an
+    /// `Error` is also returned once a single host has been redirected to more than that many
+    /// times, which breaks loops bouncing between URLs on the same host early.
     #[staticmethod]
-    #[pyo3(signature = (max=None))]
-    pub fn limited(max: Option<usize>) -> Self {
-        Self(max.map_or_else(
-            wreq::redirect::Policy::default,
-            wreq::redirect::Policy::limited,
-        ))
+    #[pyo3(signature = (max=None, max_per_host=None))]
+    pub fn limited(max: Option<usize>, max_per_host: Option<usize>) -> Self {
+        let max = max.unwrap_or(DEFAULT_MAX_REDIRECTS);
+        Self(wreq::redirect::Policy::custom(move |attempt| {
+            let targets = record(&attempt);
+            if let Err(err) = guard::check_redirect(&attempt.uri) {
+                return attempt.error(err);
+            }
+            if targets.len() > max {
+                return attempt.error(TooManyRedirects(None));
+            }
+            if let (Some(max_per_host), Some(host)) = (max_per_host, attempt.uri.host()) {
+                let hops = targets[..targets.len() - 1]
+                    .iter()
+                    .filter(|uri| uri.host() == Some(host))
+                    .count();
+                if hops >= max_per_host {
+                    return attempt.error(TooManyRedirects(Some(host.to_owned())));
+                }
+            }
+            attempt.follow()
+        }))
     }
 
     /// Create a [`Policy`] that does not follow any redirect.
@@ -99,6 +163,10 @@ impl Policy {
     pub fn custom(callback: Py<PyAny>) -> Self {
         let callback = Arc::new(callback);
         let polciy = wreq::redirect::Policy::custom(move |attempt| {
+            record(&attempt);
+            if let Err(err) = guard::check_redirect(&attempt.uri) {
+                return attempt.error(err);
+            }
             let callback = callback.clone();
             attempt.pending(|attempt| async move {
                 let args = Attempt::from(&attempt);
@@ -126,6 +194,74 @@ impl Policy {
     }
 }
 
+impl Policy {
+    /// Create a [`Policy`] asking `callback` whether to follow each redirect.
+    ///
+    /// The callback is called as `callback(url, previous_url, status)` on the blocking thread
+    /// pool, so it never holds up the runtime. Returning false stops at the redirect response,
+    /// and raising fails the request with a `RedirectError` whose `__cause__` is the raised
+    /// exception. At most [`DEFAULT_MAX_REDIRECTS`] redirects are followed.
+    ///
+    /// The request waits on the callback at every hop, with the GIL held while it runs, so it
+    /// should decide quickly and leave any I/O out.
+    pub fn decide(callback: Py<PyAny>) -> Self {
+        let callback = Arc::new(callback);
+        let policy = wreq::redirect::Policy::custom(move |attempt| {
+            let targets = record(&attempt);
+            if let Err(err) = guard::check_redirect(&attempt.uri) {
+                return attempt.error(err);
+            }
+            if targets.len() > DEFAULT_MAX_REDIRECTS {
+                return attempt.error(TooManyRedirects(None));
+            }
+            let callback = callback.clone();
+            attempt.pending(|attempt| async move {
+                let url = attempt.uri.to_string();
+                let previous = attempt
+                    .previous
+                    .last()
+                    .map(ToString::to_string)
+                    .unwrap_or_default();
+                let status = attempt.status.as_u16();
+                let follow = tokio::task::spawn_blocking(move || {
+                    Python::attach(|py| {
+                        callback
+                            .call1(py, (url, previous, status))?
+                            .bind(py)
+                            .is_truthy()
+                    })
+                })
+                .await;
+
+                match follow {
+                    Ok(Ok(true)) => attempt.follow(),
+                    Ok(Ok(false)) => attempt.stop(),
+                    Ok(Err(err)) => attempt.error(err),
+                    Err(err) => attempt.error(err.to_string()),
+                }
+            })
+        });
+
+        Self(policy)
+    }
+}
+
+impl FromPyObject<'_, '_> for Policy {
+    type Error = PyErr;
+
+    fn extract(ob: Borrowed<PyAny>) -> PyResult<Self> {
+        if let Ok(policy) = ob.cast::<Policy>() {
+            return Ok(policy.get().clone());
+        }
+        if ob.is_callable() {
+            return Ok(Policy::decide(ob.to_owned().unbind()));
+        }
+        Err(PyTypeError::new_err(
+            "Expected a redirect.Policy or a callable (url, previous_url, status) -> bool",
+        ))
+    }
+}
+
 impl_print_str!(Debug, Policy);
 
 // ===== impl Attempt =====
@@ -196,4 +332,188 @@ impl Display for Action {
 
 // ===== impl History =====
 
-impl_print_str!(Debug, History);
+impl From<&wreq::redirect::HistoryEntry> for History {
+    fn from(entry: &wreq::redirect::HistoryEntry) -> Self {
+        History {
+            status: entry.status,
+            method: http::Method::GET,
+            uri: entry.uri.to_string(),
+            previous: entry.previous.to_string(),
+            headers: entry.headers.clone(),
+        }
+    }
+}
+
+impl From<&wreq::redirect::Attempt<'static, false>> for History {
+    fn from(attempt: &wreq::redirect::Attempt<'static, false>) -> Self {
+        History {
+            status: attempt.status,
+            method: http::Method::GET,
+            uri: attempt.uri.to_string(),
+            previous: attempt
+                .previous
+                .last()
+                .map(ToString::to_string)
+                .unwrap_or_default(),
+            headers: attempt.headers.clone().into_owned(),
+        }
+    }
+}
+
+impl History {
+    /// Fill in the method of every hop of a redirect chain, given that of the request that
+    /// started it.
+    pub fn chain(mut hops: Vec<History>, method: &http::Method) -> Vec<History> {
+        let mut method = method.clone();
+        for hop in &mut hops {
+            hop.method = method.clone();
+            if redirects_to_get(&method, hop.status) {
+                method = http::Method::GET;
+            }
+        }
+        hops
+    }
+}
+
+impl Display for History {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Debug::fmt(self, f)
+    }
+}
+
+// ===== impl TooManyRedirects =====
+
+impl Display for TooManyRedirects {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(host) => write!(f, "too many redirects to host {host}"),
+            None => f.write_str("too many redirects"),
+        }
+    }
+}
+
+impl std::error::Error for TooManyRedirects {}
+
+// ===== tracking =====
+
+/// Record a redirect hop for the request currently in flight, if it is being tracked, and
+/// get the target of every hop followed so far, this one included.
+///
+/// A chain resumed from a failed hop counts the hops followed before it too, so redirect
+/// limits apply to the chain as a whole.
+fn record(attempt: &wreq::redirect::Attempt<'static, false>) -> Vec<http::Uri> {
+    TRAIL
+        .try_with(|trail| {
+            let mut trail = trail.lock().ok()?;
+            trail.push(History::from(attempt));
+            Some(
+                trail
+                    .iter()
+                    .filter_map(|hop| hop.uri.parse().ok())
+                    .collect(),
+            )
+        })
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| {
+            attempt
+                .previous
+                .iter()
+                .skip(1)
+                .chain([&attempt.uri])
+                .cloned()
+                .collect()
+        })
+}
+
+/// Drive a request future while recording the redirect hops it follows.
+///
+/// Redirect errors are returned as [`Error::Redirect`] carrying the recorded chain, so a
+/// redirect loop can be read straight off the raised exception. Any other error comes with
+/// the hops followed before it, empty if the request was not redirected at all. `method` is
+/// the method the chain started with, from which the method of every hop is worked out, and
+/// `earlier` holds the hops followed before the chain was resumed, if it was.
+pub async fn track<F, T>(
+    method: &http::Method,
+    earlier: Vec<History>,
+    fut: F,
+) -> Result<T, (Error, Vec<History>)>
+where
+    F: Future<Output = Result<T, wreq::Error>>,
+{
+    let trail = Arc::new(Mutex::new(earlier));
+    TRAIL.scope(trail.clone(), fut).await.map_err(|err| {
+        let trail = trail
+            .lock()
+            .map(|mut trail| std::mem::take(&mut *trail))
+            .unwrap_or_default();
+        let trail = History::chain(trail, method);
+        if err.is_redirect() && guard::refusal(&err).is_none() {
+            (Error::Redirect(err, trail), Vec::new())
+        } else {
+            (Error::from(err), trail)
+        }
+    })
+}
+
+/// Find the exception a redirect callback raised behind the given error, if any.
+pub fn raised(err: &(dyn std::error::Error + 'static)) -> Option<&PyErr> {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(err) = err.downcast_ref::<PyErr>() {
+            return Some(err);
+        }
+        source = err.source();
+    }
+    None
+}
+
+/// Point a request at the target of the last of the given redirect hops, as the client does
+/// when following each of them in turn.
+///
+/// `303 See Other`, and `301`/`302` answering a `POST`, turn the request into a `GET`
+/// without a body, and credentials are dropped once a hop leaves the origin it came from.
+pub fn retarget(request: &mut wreq::Request, hops: &[History]) -> Result<(), Error> {
+    for hop in hops {
+        let uri = hop
+            .uri
+            .parse::<http::Uri>()
+            .map_err(|err| Error::Builder(err.into()))?;
+        if !same_origin(&uri, request.uri()) {
+            for name in SENSITIVE_HEADERS {
+                request.headers_mut().remove(name);
+            }
+        }
+        *request.uri_mut() = uri;
+
+        if redirects_to_get(request.method(), hop.status) {
+            *request.method_mut() = http::Method::GET;
+            *request.body_mut() = None;
+            for name in [CONTENT_TYPE, CONTENT_LENGTH, TRANSFER_ENCODING] {
+                request.headers_mut().remove(name);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether following a redirect with the given status turns a request into a `GET`.
+fn redirects_to_get(method: &http::Method, status: http::StatusCode) -> bool {
+    (status == http::StatusCode::SEE_OTHER && method != http::Method::HEAD)
+        || (matches!(
+            status,
+            http::StatusCode::MOVED_PERMANENTLY | http::StatusCode::FOUND
+        ) && method == http::Method::POST)
+}
+
+/// Whether two URIs share scheme, host and port, with default ports filled in.
+fn same_origin(a: &http::Uri, b: &http::Uri) -> bool {
+    let port = |uri: &http::Uri| {
+        uri.port_u16().or(match uri.scheme_str() {
+            Some("https" | "wss") => Some(443),
+            Some("http" | "ws") => Some(80),
+            _ => None,
+        })
+    };
+    a.scheme() == b.scheme() && a.host() == b.host() && port(a) == port(b)
+}
@@ -2,11 +2,15 @@ mod identity;
 mod keylog;
 mod store;
 
-use pyo3::prelude::*;
+use pyo3::{prelude::*, pybacked::PyBackedBytes};
 use wreq::tls::compress::CertificateCompressor;
 use wreq_util::emulate::compress;
 
-pub use self::{identity::Identity, keylog::KeyLog, store::CertStore};
+pub use self::{
+    identity::Identity,
+    keylog::{KeyLog, KeyLogLike},
+    store::CertStore,
+};
 use crate::buffer::PyBuffer;
 
 define_enum!(
@@ -23,10 +27,24 @@ define_enum!(
 #[derive(FromPyObject)]
 pub enum TlsVerify {
     Verification(bool),
+    // Tried before paths, which bytes would otherwise be taken for.
+    CertificateBytes(PyBackedBytes),
     CertificatePath(std::path::PathBuf),
     CertificateStore(CertStore),
 }
 
+impl TlsVerify {
+    /// Build a certificate store from root certificates in memory, either a PEM stack or a
+    /// single DER certificate.
+    pub fn cert_store(certs: &[u8]) -> wreq::Result<wreq::tls::trust::CertStore> {
+        if memchr::memmem::find(certs, b"-----BEGIN").is_some() {
+            wreq::tls::trust::CertStore::from_pem_stack(certs)
+        } else {
+            wreq::tls::trust::CertStore::from_der_certs(&[certs])
+        }
+    }
+}
+
 define_enum!(
     /// A TLS ALPN protocol.
     const,
@@ -49,4 +49,19 @@ impl Identity {
             .map_err(Error::Library)
             .map_err(Into::into)
     }
+
+    /// Parses a PKCS #12 archive decrypted with `password`, as [`Identity::from_pkcs12_der`].
+    #[staticmethod]
+    #[pyo3(signature = (data, password))]
+    pub fn from_pkcs12(data: PyBackedBytes, password: PyBackedStr) -> PyResult<Identity> {
+        Self::from_pkcs12_der(data, password)
+    }
+
+    /// Parses a PEM encoded certificate chain and its PKCS #8 private key, as
+    /// [`Identity::from_pkcs8_pem`].
+    #[staticmethod]
+    #[pyo3(signature = (cert, key))]
+    pub fn from_pem(cert: PyBackedBytes, key: PyBackedBytes) -> PyResult<Identity> {
+        Self::from_pkcs8_pem(cert, key)
+    }
 }
@@ -1,6 +1,6 @@
 use std::path::PathBuf;
 
-use pyo3::{pyclass, pymethods};
+use pyo3::{FromPyObject, pyclass, pymethods};
 
 /// Specifies the intent for a (TLS) keylogger to be used in a client or server configuration.
 ///
@@ -26,3 +26,24 @@ impl KeyLog {
         KeyLog(wreq::tls::keylog::KeyLog::from_file(path))
     }
 }
+
+/// A key logging policy given as a [`KeyLog`], a file path, or `True` to log to the file
+/// named by `SSLKEYLOGFILE`.
+#[derive(FromPyObject)]
+pub enum KeyLogLike {
+    KeyLog(KeyLog),
+    Environment(bool),
+    File(PathBuf),
+}
+
+impl KeyLogLike {
+    /// Get the key logging policy, or `None` when it was turned off with `False`.
+    pub fn into_inner(self) -> Option<wreq::tls::keylog::KeyLog> {
+        match self {
+            KeyLogLike::KeyLog(keylog) => Some(keylog.0),
+            KeyLogLike::Environment(true) => Some(wreq::tls::keylog::KeyLog::from_env()),
+            KeyLogLike::Environment(false) => None,
+            KeyLogLike::File(path) => Some(wreq::tls::keylog::KeyLog::from_file(path)),
+        }
+    }
+}
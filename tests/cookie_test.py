@@ -1,3 +1,5 @@
+import json
+
 import pytest
 import wreq
 from wreq.cookie import Cookie
@@ -247,3 +249,40 @@ async def test_cookie_value_update_from_server():
         len(user_id_cookies) == 1
     ), "Should have exactly one user_id cookie after update"
     assert user_id_cookies[0].value == "updated_value"
+
+
+def test_jar_save_load(tmp_path):
+    path = tmp_path / "cookies.json"
+    url = "http://localhost:8080/cookies"
+    jar = wreq.Jar()
+    jar.add("session=abc; Path=/; Domain=localhost; HttpOnly", url)
+    jar.add("stale=1; Path=/; Domain=localhost; Max-Age=-1", url)
+    jar.save(path)
+
+    jar = wreq.Jar.load(path)
+    cookie = jar.get("session", url)
+    assert cookie is not None
+    assert cookie.value == "abc"
+    assert cookie.http_only
+    assert jar.get("stale", url) is None
+
+    # Host-only cookies are not restored for subdomains.
+    jar = wreq.Jar()
+    jar.add("host=1; Path=/", "http://example.com/")
+    jar.add("shared=1; Path=/; Domain=example.com", "http://example.com/")
+    jar.save(path)
+    saved = {cookie["name"]: cookie["host_only"] for cookie in json.loads(path.read_text())}
+    assert saved == {"host": True, "shared": False}
+
+    jar = wreq.Jar.load(path)
+    assert jar.get("host", "http://example.com/") is not None
+    assert jar.get("host", "http://www.example.com/") is None
+    assert jar.get("shared", "http://www.example.com/") is not None
+
+    # A domain cookie of the same name does not make a host-only one look shared.
+    jar = wreq.Jar()
+    jar.add("id=host; Path=/app", "http://example.com/app")
+    jar.add("id=shared; Path=/; Domain=example.com", "http://example.com/")
+    jar.save(path)
+    saved = {cookie["value"]: cookie["host_only"] for cookie in json.loads(path.read_text())}
+    assert saved == {"host": True, "shared": False}
@@ -1,8 +1,8 @@
 from ipaddress import IPv4Address
 import pytest
 from wreq import Client
-from wreq.exceptions import ConnectionError
-from wreq.dns import ResolverOptions, LookupIpStrategy
+from wreq.exceptions import ConnectionError, TimeoutError
+from wreq.dns import DnsOverHttps, ResolverOptions, LookupIpStrategy
 
 
 @pytest.mark.asyncio
@@ -19,3 +19,113 @@ async def test_dns_resolve_override():
         assert False, "ConnectionError was expected"
     except ConnectionError:
         pass
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_dns_over_https_unreachable():
+    client = Client(dns="https://127.0.0.1:1/dns-query")
+    with pytest.raises(ConnectionError):
+        await client.get("https://www.google.com")
+
+    with pytest.raises(ValueError):
+        Client(dns="tcp://127.0.0.1")
+
+    doh = DnsOverHttps("https://dns.invalid:1/dns-query", bootstrap=["127.0.0.1"])
+    assert doh.url == "https://dns.invalid:1/dns-query"
+    client = Client(dns=doh)
+    with pytest.raises(ConnectionError):
+        await client.get("https://www.google.com")
+
+    with pytest.raises(ValueError):
+        DnsOverHttps("http://127.0.0.1/dns-query")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_dns_modes():
+    for mode in ("system", "hickory"):
+        client = Client(dns=mode)
+        resp = await client.get("http://localhost:8080/get")
+        async with resp:
+            assert resp.status.is_success()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_dns_resolver_callback():
+    lookups = []
+
+    def resolver(host):
+        lookups.append(host)
+        if host == "broken.invalid":
+            raise RuntimeError("no such host")
+        return ["127.0.0.1"]
+
+    client = Client(dns_resolver=resolver)
+    resp = await client.get("http://stub.invalid:8080/get")
+    async with resp:
+        assert (await resp.json())["headers"]["Host"] == "stub.invalid:8080"
+    assert lookups == ["stub.invalid"]
+
+    with pytest.raises(ConnectionError):
+        await client.get("http://broken.invalid:8080/get")
+
+    with pytest.raises(ValueError):
+        Client(dns="system", dns_resolver=resolver)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_request_resolve_overrides():
+    client = Client(resolve={"pinned.invalid": "10.255.255.1"})
+    resp = await client.get(
+        "http://pinned.invalid:8080/get", resolve={"pinned.invalid:8080": "127.0.0.1"}
+    )
+    async with resp:
+        assert (await resp.json())["headers"]["Host"] == "pinned.invalid:8080"
+        assert str(resp.remote_addr.ip()) == "127.0.0.1"
+
+    with pytest.raises(ConnectionError):
+        await client.get("http://unpinned.invalid:8080/get")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_request_resolve_skips_pool():
+    client = Client()
+    url = "http://localhost:8080/get"
+    resp = await client.get(url)
+    async with resp:
+        assert resp.status.is_success()
+
+    # The pooled connection to localhost must not stand in for the pinned address.
+    with pytest.raises((ConnectionError, TimeoutError)):
+        await client.get(url, resolve={"localhost": "10.255.255.1"}, timeout=1)
+
+    resp = await client.get(url, resolve={"localhost": "127.0.0.1"})
+    async with resp:
+        assert str(resp.remote_addr.ip()) == "127.0.0.1"
+    resp = await client.get(url)
+    async with resp:
+        assert resp.status.is_success()
+
+    resp = await client.get(url, resolve={"localhost": "127.0.0.1"}, tls_verify=False)
+    async with resp:
+        assert str(resp.remote_addr.ip()) == "127.0.0.1"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_resolve_overrides():
+    client = Client(
+        resolve={"staging.invalid": "127.0.0.1", "scoped.invalid:9999": "127.0.0.1"}
+    )
+    resp = await client.get("http://staging.invalid:8080/get")
+    async with resp:
+        json = await resp.json()
+        assert json["headers"]["Host"] == "staging.invalid:8080"
+
+    # The scoped entry does not cover other ports.
+    with pytest.raises(ConnectionError):
+        await client.get("http://scoped.invalid:8080/get")
@@ -19,3 +19,42 @@ async def test_proxy_connection_error():
         for url in target_urls:
             with pytest.raises(exceptions.ProxyConnectionError):
                 await wreq.get(url, proxy=wreq.Proxy.all(proxy))
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_socks5h_resolves_remotely():
+    # Resolving `localhost` locally would be refused as a private network address, so
+    # reaching the (absent) proxy shows the name was left to the proxy to resolve.
+    guarded = wreq.Client(block_private_networks=True)
+    proxy = wreq.Proxy.socks5h("127.0.0.1:1", username="user", password="pass")
+    with pytest.raises(exceptions.ProxyConnectionError):
+        await guarded.get("http://localhost:8080/get", proxy=proxy)
+
+    with pytest.raises(ValueError):
+        wreq.Proxy.socks5("http://127.0.0.1:1")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_base_exception():
+    for name in exceptions.__all__:
+        assert issubclass(getattr(exceptions, name), exceptions.RnetError)
+
+    with pytest.raises(exceptions.RnetError):
+        await wreq.get("http://127.0.0.1:1")
+    with pytest.raises(exceptions.ConnectionError):
+        await wreq.get("http://127.0.0.1:1")
+
+    # Errors once raised as a bare `RuntimeError` are still caught as one.
+    client = wreq.Client()
+    client.close()
+    try:
+        await client.get("http://localhost:8080/get")
+    except exceptions.RnetError as err:
+        assert isinstance(err, exceptions.ClientClosedError)
+        assert isinstance(err, RuntimeError)
+    else:
+        pytest.fail("a closed client sent a request")
+    assert issubclass(exceptions.WebSocketClosedError, exceptions.WebSocketError)
+    assert issubclass(exceptions.WebSocketClosedError, RuntimeError)
@@ -57,3 +57,129 @@ async def test_client_redirec_history():
     assert len(history) == 1
     assert history[0].url == "https://www.google.com/"
     assert history[0].previous == url
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_redirect_error_history():
+    client = wreq.Client(redirect=redirect.Policy.limited(2))
+    with pytest.raises(wreq.exceptions.RedirectError) as exc:
+        await client.get("http://localhost:8080/redirect/5")
+
+    history = exc.value.history
+    assert len(history) == 3
+    assert history[0].previous == "http://localhost:8080/redirect/5"
+    assert [hop.method for hop in history] == ["GET"] * 3
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_redirect_history_method():
+    url = "http://localhost:8080/redirect-to"
+    response = await client.post(url, query={"url": "/get", "status_code": "303"})
+    assert response.status.is_success()
+    assert [(hop.status, hop.method) for hop in response.history] == [(303, "POST")]
+
+    response = await client.post(url, query={"url": "/post", "status_code": "307"})
+    assert response.status.is_success()
+    assert [(hop.status, hop.method) for hop in response.history] == [(307, "POST")]
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_max_redirects_per_host():
+    client = wreq.Client(max_redirects_per_host=2)
+    with pytest.raises(wreq.exceptions.RedirectError):
+        await client.get("http://localhost:8080/redirect/5")
+
+    response = await client.get("http://localhost:8080/redirect/2")
+    assert response.status.is_success()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_redirect_retries():
+    client = wreq.Client(redirect=redirect.Policy.limited(), redirect_retries=2)
+    response = await client.get("http://localhost:8080/redirect/2")
+    assert response.status.is_success()
+
+    # The hop keeps failing, so the connection error surfaces after the retries.
+    with pytest.raises(wreq.exceptions.ConnectionError):
+        await client.get(
+            "http://localhost:8080/redirect-to", query={"url": "http://127.0.0.1:1/"}
+        )
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_redirect_callable():
+    seen = []
+
+    def follow_once(url, previous, status):
+        seen.append((url, previous, status))
+        return len(seen) < 2
+
+    response = await client.get(
+        "http://localhost:8080/redirect/3", redirect=follow_once
+    )
+    assert response.status.is_redirection()
+    assert len(seen) == 2
+    assert seen[0] == (
+        "http://localhost:8080/relative-redirect/2",
+        "http://localhost:8080/redirect/3",
+        302,
+    )
+
+    response = await client.get(
+        "http://localhost:8080/redirect/3", redirect=lambda *_: True
+    )
+    assert response.status.is_success()
+    assert response.url == "http://localhost:8080/get"
+
+    def refuse(url, previous, status):
+        raise LookupError(url)
+
+    with pytest.raises(wreq.exceptions.RedirectError) as exc:
+        await client.get("http://localhost:8080/redirect/3", redirect=refuse)
+    assert isinstance(exc.value.__cause__, LookupError)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_redirect_strips_sensitive_headers():
+    headers = {"Authorization": "Bearer secret", "Cookie": "session=secret"}
+
+    response = await client.get(
+        "http://localhost:8080/redirect-to",
+        query={"url": "http://127.0.0.1:8080/headers"},
+        headers=headers,
+    )
+    assert response.url == "http://127.0.0.1:8080/headers"
+    sent = (await response.json())["headers"]
+    assert "Authorization" not in sent
+    assert "Cookie" not in sent
+
+    response = await client.get(
+        "http://localhost:8080/redirect-to",
+        query={"url": "/headers"},
+        headers=headers,
+    )
+    sent = (await response.json())["headers"]
+    assert sent["Authorization"] == "Bearer secret"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_client_redirect_callable():
+    def same_host(url, previous, status):
+        return url.startswith("http://localhost:8080/")
+
+    client = wreq.Client(redirect=same_host)
+    response = await client.get("http://localhost:8080/redirect/2")
+    assert response.status.is_success()
+
+    response = await client.get(
+        "http://localhost:8080/redirect-to",
+        query={"url": "http://127.0.0.1:8080/get"},
+    )
+    assert response.status.is_redirection()
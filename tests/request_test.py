@@ -1,7 +1,15 @@
+import asyncio
+import json
+import signal
+import time
+from datetime import date, timedelta
+
 import pytest
 import wreq
 from wreq import Version
+from wreq.emulation import Emulation
 from wreq.header import HeaderMap
+from wreq.http2 import StreamDependency, StreamId
 
 client = wreq.Client(tls_info=True)
 
@@ -154,6 +162,59 @@ async def test_send_json():
         assert json["json"] == {"foo": "bar"}
 
 
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_send_json_with_explicit_content_type():
+    url = "http://localhost:8080/post"
+    resp = await client.post(
+        url,
+        json={"foo": "bar"},
+        headers={"Content-Type": "application/vnd.api+json"},
+    )
+    async with resp:
+        json = await resp.json()
+        assert json["headers"]["Content-Type"] == "application/vnd.api+json"
+        assert json["data"] == '{"foo":"bar"}'
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_send_json_dumps():
+    url = "http://localhost:8080/post"
+    resp = await client.post(url, json={"b": 1, "a": 2})
+    async with resp:
+        assert (await resp.json())["data"] == '{"b":1,"a":2}'
+
+    def dumps(value):
+        return json.dumps(value, default=str, separators=(",", ":")).encode()
+
+    resp = await client.post(url, json={"at": date(2024, 1, 2)}, json_dumps=dumps)
+    async with resp:
+        body = await resp.json()
+        assert body["headers"]["Content-Type"] == "application/json"
+        assert body["data"] == '{"at":"2024-01-02"}'
+
+    with pytest.raises(ValueError):
+        await client.post(url, json={}, body=b"{}", json_dumps=dumps)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_decode_fallback():
+    # The body is plain JSON, but the response claims to be gzip encoded.
+    url = "http://localhost:8080/response-headers?Content-Encoding=gzip"
+    resp = await client.get(url, decode_fallback=True)
+    async with resp:
+        assert resp.decode_fallback
+        assert b'"Content-Encoding"' in await resp.bytes()
+
+    # A body that does decode is decompressed as usual.
+    resp = await client.get("http://localhost:8080/gzip", decode_fallback=True)
+    async with resp:
+        assert not resp.decode_fallback
+        assert (await resp.json())["gzipped"]
+
+
 @pytest.mark.asyncio
 @pytest.mark.flaky(reruns=3, reruns_delay=2)
 async def test_send_text():
@@ -205,3 +266,661 @@ async def test_send_sync_bytes_stream():
     async with resp:
         json = await resp.json()
         assert json["data"] in open("README.md").read()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_stream_priority():
+    resp = await client.get(
+        "https://tls.peet.ws/api/all",
+        version=Version.HTTP_2,
+        stream_priority=StreamDependency(StreamId(0), 219, True),
+    )
+    async with resp:
+        json = await resp.json()
+        headers_frame = next(
+            frame
+            for frame in json["http2"]["sent_frames"]
+            if frame["frame_type"] == "HEADERS"
+        )
+        priority = headers_frame["priority"]
+        assert priority["depends_on"] == 0
+        assert priority["weight"] == 220
+        assert priority["exclusive"] == 1
+
+    # The dependency alone cannot be changed on top of an emulated HTTP/2 fingerprint.
+    emulated = wreq.Client(emulation=Emulation.Chrome133)
+    with pytest.raises(ValueError):
+        await emulated.get(
+            "https://tls.peet.ws/api/all",
+            stream_priority=StreamDependency(StreamId(0), 219, True),
+        )
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_shortcut_with_shared_client():
+    shared = wreq.Client(emulation=Emulation.Firefox139)
+    resp = await wreq.get("http://localhost:8080/headers", client=shared)
+    async with resp:
+        json = await resp.json()
+        assert "Firefox" in json["headers"]["User-Agent"]
+
+    resp = await wreq.get("http://localhost:8080/headers")
+    async with resp:
+        json = await resp.json()
+        assert "Firefox" not in json["headers"].get("User-Agent", "")
+
+
+def test_conflicting_protocol_options():
+    with pytest.raises(ValueError):
+        wreq.Client(http1_only=True, http2_only=True)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_response_validator():
+    def validator(json):
+        if "slideshow" not in json:
+            raise ValueError("missing slideshow")
+
+    resp = await client.get("http://localhost:8080/json", validator=validator)
+    async with resp:
+        assert "slideshow" in await resp.json()
+
+    resp = await client.get("http://localhost:8080/get", validator=validator)
+    async with resp:
+        with pytest.raises(wreq.exceptions.ValidationError) as exc:
+            await resp.json()
+        assert isinstance(exc.value.__cause__, ValueError)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_send_async_stream_buffered():
+    async def body():
+        for chunk in (b"hello", b" ", b"world"):
+            yield chunk
+
+    resp = await client.post(
+        "http://localhost:8080/post", body=body(), stream_mode="buffered"
+    )
+    async with resp:
+        json = await resp.json()
+        assert json["data"] == "hello world"
+        assert json["headers"]["Content-Length"] == "11"
+        assert "Transfer-Encoding" not in json["headers"]
+
+
+@pytest.mark.asyncio
+async def test_allowed_hosts():
+    guarded = wreq.Client(allowed_hosts=["*.example.com", "127.0.0.0/8"])
+    resp = await guarded.get("http://127.0.0.1:8080/get")
+    async with resp:
+        assert resp.status.is_success()
+
+    with pytest.raises(wreq.exceptions.HostNotAllowed):
+        await guarded.get("http://localhost:8080/get")
+
+    # A redirect may not leave the allowlist either.
+    with pytest.raises(wreq.exceptions.HostNotAllowed):
+        await guarded.get(
+            "http://127.0.0.1:8080/redirect-to",
+            query={"url": "http://localhost:8080/get"},
+        )
+
+
+@pytest.mark.asyncio
+async def test_block_private_networks():
+    guarded = wreq.Client(block_private_networks=True)
+    with pytest.raises(wreq.exceptions.HostNotAllowed):
+        await guarded.get("http://127.0.0.1:8080/get")
+    with pytest.raises(wreq.exceptions.HostNotAllowed):
+        await guarded.get("http://0.1.2.3/")
+    # NAT64 and 6to4 addresses reach the IPv4 address they embed.
+    with pytest.raises(wreq.exceptions.HostNotAllowed):
+        await guarded.get("http://[64:ff9b::a9fe:a9fe]/")
+    with pytest.raises(wreq.exceptions.HostNotAllowed):
+        await guarded.get("http://[2002:7f00:1::]/")
+
+    # A proxy would resolve the target past the guard.
+    proxy = wreq.Proxy.all("http://proxy.example.com:8080")
+    with pytest.raises(ValueError):
+        await guarded.get("http://example.com/", proxy=proxy)
+    with pytest.raises(ValueError):
+        wreq.Client(block_private_networks=True, proxies=[proxy])
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_timeout_tuple():
+    resp = await client.get(
+        "http://localhost:8080/get",
+        timeout=(timedelta(seconds=5), timedelta(seconds=5)),
+    )
+    async with resp:
+        assert resp.status.is_success()
+
+    with pytest.raises(wreq.exceptions.TimeoutError):
+        await client.get(
+            "http://localhost:8080/delay/3",
+            timeout=(timedelta(seconds=5), timedelta(seconds=1)),
+        )
+
+    # The connect phase is bounded on its own, well before the read timeout.
+    start = time.monotonic()
+    with pytest.raises(
+        (wreq.exceptions.ConnectionError, wreq.exceptions.TimeoutError)
+    ):
+        await client.get(
+            "http://10.255.255.1:8080/get",
+            timeout=(timedelta(seconds=1), timedelta(seconds=30)),
+        )
+    assert time.monotonic() - start < 5
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_per_request_alpn():
+    resp = await wreq.Client().get(
+        "https://tls.peet.ws/api/all", alpn=[wreq.AlpnProtocol.HTTP1]
+    )
+    async with resp:
+        assert resp.version == Version.HTTP_11
+
+    # Offering other protocols would drop the rest of the emulated TLS fingerprint.
+    emulated = wreq.Client(emulation=Emulation.Chrome133)
+    with pytest.raises(ValueError):
+        await emulated.get("https://tls.peet.ws/api/all", alpn=[wreq.AlpnProtocol.HTTP1])
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_default_accept():
+    accepting = wreq.Client(default_accept="json")
+
+    async def accept(**kwargs):
+        resp = await accepting.get("http://localhost:8080/headers", **kwargs)
+        async with resp:
+            return (await resp.json())["headers"].get("Accept")
+
+    assert await accept() == "application/json"
+    assert await accept(accept="xml") == "application/xml"
+    assert await accept(accept="image/webp") == "image/webp"
+    # Headers set on the request win over both.
+    assert await accept(accept="html", headers={"Accept": "text/csv"}) == "text/csv"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_host_emulation():
+    hosts = ["example.test", "api.example.test", "www.example.test"]
+    per_host = wreq.Client(
+        resolve={host: "127.0.0.1" for host in hosts},
+        host_emulation={
+            "*.example.test": Emulation.Chrome133,
+            "api.example.test": Emulation.Firefox139,
+        },
+    )
+
+    async def user_agent(host, **kwargs):
+        resp = await per_host.get(f"http://{host}:8080/headers", **kwargs)
+        async with resp:
+            return (await resp.json())["headers"].get("User-Agent", "")
+
+    # Exact hosts win over wildcards, which only match subdomains.
+    assert "Firefox" in await user_agent("api.example.test")
+    assert "Chrome" in await user_agent("www.example.test")
+    agent = await user_agent("example.test")
+    assert "Chrome" not in agent and "Firefox" not in agent
+
+    # A per-request emulation takes precedence.
+    agent = await user_agent("api.example.test", emulation=Emulation.Chrome133)
+    assert "Chrome" in agent and "Firefox" not in agent
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_emulation_without_tls():
+    url = "https://tls.peet.ws/api/all"
+    resp = await wreq.Client(emulation=Emulation.Chrome133).get(url)
+    async with resp:
+        chrome = (await resp.json())["tls"]["ja4"]
+    resp = await wreq.Client().get(url)
+    async with resp:
+        plain = (await resp.json())["tls"]["ja4"]
+
+    headers_only = wreq.Client(emulation=Emulation(profile=Emulation.Chrome133, tls=False))
+    resp = await headers_only.get(url)
+    async with resp:
+        json = await resp.json()
+        assert json["tls"]["ja4"] == plain != chrome
+        assert "Chrome" in json["user_agent"]
+
+    # Without the emulated TLS options there is no fingerprint for ALPN to break.
+    resp = await headers_only.get(url, alpn=[wreq.AlpnProtocol.HTTP1])
+    async with resp:
+        assert resp.version == Version.HTTP_11
+
+
+def test_to_curl():
+    command = wreq.Client(tls_verify=False).to_curl(
+        wreq.Method.POST,
+        "https://example.com/post",
+        headers={"x-test": "it's"},
+        body=b"hello",
+    )
+    assert command.startswith("curl -X POST")
+    assert "-H 'x-test: it'\\''s'" in command
+    assert "--data-binary 'hello'" in command
+    assert "--insecure" in command
+    assert command.endswith("'https://example.com/post'")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_persist_cookies():
+    url = "http://localhost:8080/cookies"
+    store = wreq.Client(cookie_store=True)
+    resp = await store.get(url, cookies={"foo": "bar"}, persist_cookies=True)
+    async with resp:
+        assert (await resp.json())["cookies"] == {"foo": "bar"}
+
+    resp = await store.get(url)
+    async with resp:
+        assert (await resp.json())["cookies"] == {"foo": "bar"}
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_raw_request():
+    raw = wreq.Client(raw_request=True)
+    with pytest.raises(ValueError):
+        await raw.get("http://localhost:8080/headers")
+
+    resp = await raw.get(
+        "http://localhost:8080/headers",
+        headers={"Host": "localhost:8080", "X-Test": "raw"},
+    )
+    async with resp:
+        headers = (await resp.json())["headers"]
+        assert headers["X-Test"] == "raw"
+        assert "Accept" not in headers
+        assert "Accept-Encoding" not in headers
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_per_request_grease():
+    grease = wreq.Client()
+    resp = await grease.get("https://tls.peet.ws/api/all", grease_enabled=True)
+    async with resp:
+        assert "GREASE" in await resp.text()
+
+    resp = await grease.get("https://tls.peet.ws/api/all", grease_enabled=False)
+    async with resp:
+        assert "GREASE" not in await resp.text()
+
+    # Toggling GREASE alone cannot be done on top of an emulated fingerprint.
+    emulated = wreq.Client(emulation=Emulation.Chrome133)
+    with pytest.raises(ValueError):
+        await emulated.get("https://tls.peet.ws/api/all", grease_enabled=False)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_auto_compress_request():
+    compressing = wreq.Client(auto_compress_request=True, auto_compress_threshold=64)
+    resp = await compressing.post("http://localhost:8080/anything", body=b"a" * 1024)
+    async with resp:
+        assert (await resp.json())["headers"]["Content-Encoding"] == "gzip"
+
+    resp = await compressing.post("http://localhost:8080/anything", body=b"small")
+    async with resp:
+        json = await resp.json()
+        assert "Content-Encoding" not in json["headers"]
+        assert json["data"] == "small"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_pipe():
+    src = await client.get("http://localhost:8080/base64/cGlwZWQgYm9keQ==")
+    resp = await client.pipe(src, wreq.Method.POST, "http://localhost:8080/post")
+    async with resp:
+        assert (await resp.json())["data"] == "piped body"
+
+    src = await client.get("http://localhost:8080/bytes/16")
+    with pytest.raises(ValueError):
+        await client.pipe(src, wreq.Method.POST, "http://localhost:8080/post", body=b"x")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_retry():
+    retry = wreq.Retry(
+        max_attempts=3, backoff=timedelta(milliseconds=100), jitter=False, statuses=[503]
+    )
+    start = time.monotonic()
+    resp = await client.get("http://localhost:8080/status/503", retry=retry)
+    async with resp:
+        assert resp.status.as_int() == 503
+    # Two retries, waiting 100ms and then 200ms.
+    assert time.monotonic() - start >= 0.3
+
+    with pytest.raises(ValueError):
+        wreq.Retry(max_attempts=0)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_retry_within_timeout():
+    retry = wreq.Retry(
+        max_attempts=3, backoff=timedelta(milliseconds=100), jitter=False, statuses=[200]
+    )
+    # The third attempt only gets what is left of the timeout, not the whole of it.
+    start = time.monotonic()
+    with pytest.raises(wreq.exceptions.TimeoutError):
+        await client.get(
+            "http://localhost:8080/delay/1", retry=retry, timeout=timedelta(seconds=2.5)
+        )
+    assert time.monotonic() - start < 3
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_retry_after_capped():
+    retry = wreq.Retry(
+        max_attempts=2, backoff=timedelta(milliseconds=100), jitter=False, statuses=[200]
+    )
+    # Without a timeout, a long `Retry-After` is capped at the longest backoff.
+    start = time.monotonic()
+    resp = await client.get(
+        "http://localhost:8080/response-headers?Retry-After=60", retry=retry
+    )
+    async with resp:
+        assert resp.status.as_int() == 200
+    assert time.monotonic() - start < 5
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_connection_stats():
+    assert wreq.Client().connection_stats() is None
+
+    client = wreq.Client(track_connections=True)
+    for _ in range(3):
+        resp = await client.get("http://localhost:8080/get")
+        async with resp:
+            await resp.bytes()
+    stats = client.connection_stats()
+    assert stats.requests == 3
+    assert stats.new_connections == 1
+    assert stats.reused == 2
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_single_connection():
+    client = wreq.Client(single_connection=True, track_connections=True)
+
+    # Requests sent one after another reuse the one pooled connection.
+    for _ in range(3):
+        resp = await client.get("http://localhost:8080/get")
+        async with resp:
+            await resp.bytes()
+    stats = client.connection_stats()
+    assert stats.requests == 3
+    assert stats.new_connections == 1
+
+    # An unread response does not hold back the next request.
+    held = await client.get("http://localhost:8080/stream-bytes/1024")
+    async with held:
+        resp = await asyncio.wait_for(client.get("http://localhost:8080/get"), 5)
+        async with resp:
+            assert resp.status.is_success()
+
+    with pytest.raises(ValueError):
+        wreq.Client(single_connection=True, pool_max_size=2)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_emulation_fallback():
+    seen = []
+
+    def blocked(status, headers):
+        seen.append(status.as_int())
+        return status.as_int() == 403
+
+    client = wreq.Client(
+        emulation_fallback=[Emulation.Chrome133, Emulation.Firefox136],
+        block_detector=blocked,
+    )
+    resp = await client.get("http://localhost:8080/status/403")
+    async with resp:
+        assert resp.status.as_int() == 403
+    # One check per fallback profile, after which the last response is returned.
+    assert seen == [403, 403]
+
+    seen.clear()
+    resp = await client.get("http://localhost:8080/status/200")
+    async with resp:
+        assert resp.status.as_int() == 200
+    assert seen == [200]
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_client_retry():
+    client = wreq.Client(
+        max_retries=2,
+        retry_backoff=timedelta(milliseconds=100),
+        retry_on_status=[503],
+    )
+    start = time.monotonic()
+    resp = await client.get("http://localhost:8080/status/503")
+    async with resp:
+        assert resp.status.as_int() == 503
+    # Two retries, waiting at least half of 100ms and 200ms with jitter.
+    assert time.monotonic() - start >= 0.15
+
+    # POST is not idempotent and is sent once.
+    start = time.monotonic()
+    resp = await client.post("http://localhost:8080/status/503")
+    async with resp:
+        assert resp.status.as_int() == 503
+    assert time.monotonic() - start < 0.15
+
+    # The request's own retry options cover POST too.
+    start = time.monotonic()
+    resp = await client.post(
+        "http://localhost:8080/status/429",
+        max_retries=1,
+        retry_on_status=[429],
+    )
+    async with resp:
+        assert resp.status.as_int() == 429
+    assert time.monotonic() - start >= 0.05
+
+    # Tuning only the statuses keeps the client's method filter.
+    start = time.monotonic()
+    resp = await client.post("http://localhost:8080/status/429", retry_on_status=[429])
+    async with resp:
+        assert resp.status.as_int() == 429
+    assert time.monotonic() - start < 0.15
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_request_retry_shorthands():
+    # The shorthands override the fields of the request's own policy.
+    retry = wreq.Retry(
+        max_attempts=1, backoff=timedelta(seconds=5), jitter=False, statuses=[503]
+    )
+    start = time.monotonic()
+    resp = await client.post(
+        "http://localhost:8080/status/503",
+        retry=retry,
+        max_retries=2,
+        retry_backoff=timedelta(milliseconds=100),
+    )
+    async with resp:
+        assert resp.status.as_int() == 503
+    # Two retries, waiting 100ms and then 200ms.
+    assert 0.3 <= time.monotonic() - start < 5
+
+
+def test_proxy_pool():
+    pool = [wreq.Proxy.all(f"http://proxy{i}.example.com:8080") for i in range(4)]
+
+    def picks(client):
+        return [
+            client.to_curl(wreq.Method.GET, "http://example.com", proxy=pool)
+            for _ in range(8)
+        ]
+
+    first = picks(wreq.Client(proxy_seed=7))
+    assert first == picks(wreq.Client(proxy_seed=7))
+    assert all(".example.com:8080" in command for command in first)
+
+    with pytest.raises(ValueError):
+        wreq.Client().to_curl(wreq.Method.GET, "http://example.com", proxy=[])
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_base_url():
+    client = wreq.Client(base_url="http://localhost:8080/anything/")
+    resp = await client.get("users?id=1")
+    async with resp:
+        json = await resp.json()
+        assert json["url"].endswith("/anything/users?id=1")
+
+    resp = await client.get("/get")
+    async with resp:
+        assert str(resp.url) == "http://localhost:8080/get"
+
+    resp = await client.get("http://127.0.0.1:8080/get")
+    async with resp:
+        assert str(resp.url) == "http://127.0.0.1:8080/get"
+
+    with pytest.raises(wreq.BuilderError):
+        wreq.Client(base_url="not a url")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_default_query():
+    client = wreq.Client(
+        default_query=[("api_key", "secret"), ("tag", "a"), ("tag", "b"), ("v", 1)]
+    )
+    resp = await client.get("http://localhost:8080/get", query={"v": 2})
+    async with resp:
+        args = (await resp.json())["args"]
+        assert args["api_key"] == "secret"
+        assert args["tag"] == ["a", "b"]
+        assert args["v"] == "2"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_client_close():
+    async with wreq.Client() as client:
+        resp = await client.get("http://localhost:8080/get")
+        async with resp:
+            assert resp.status.is_success()
+
+    with pytest.raises(RuntimeError, match="closed"):
+        await client.get("http://localhost:8080/get")
+
+    with wreq.blocking.Client() as blocking:
+        with blocking.get("http://localhost:8080/get") as resp:
+            assert resp.status.is_success()
+
+    with pytest.raises(RuntimeError, match="closed"):
+        blocking.get("http://localhost:8080/get")
+
+
+@pytest.mark.skipif(not hasattr(signal, "setitimer"), reason="needs POSIX timers")
+def test_blocking_interruptible():
+    def interrupt(signum, frame):
+        raise KeyboardInterrupt
+
+    previous = signal.signal(signal.SIGALRM, interrupt)
+    try:
+        signal.setitimer(signal.ITIMER_REAL, 0.5)
+        start = time.monotonic()
+        with pytest.raises(KeyboardInterrupt):
+            wreq.blocking.Client().get("http://localhost:8080/delay/10")
+        assert time.monotonic() - start < 5
+    finally:
+        signal.setitimer(signal.ITIMER_REAL, 0)
+        signal.signal(signal.SIGALRM, previous)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_sse():
+    url = (
+        "http://localhost:8080/base64/"
+        "cmV0cnk6IDEwCjoga2VlcC1hbGl2ZQppZDogMQpldmVudDogZ3JlZXRpbmcKZGF0YTogaGVsbG8KZGF0YTog"
+        "d29ybGQKCmRhdGE6IHNlY29uZA0KDQo="
+    )
+    events = []
+    async for event in client.sse(url):
+        events.append(event)
+        if len(events) == 3:
+            break
+
+    assert events[0].event == "greeting"
+    assert events[0].data == "hello\nworld"
+    assert events[0].id == "1"
+    assert events[0].retry == timedelta(milliseconds=10)
+    assert events[1].event == "message"
+    assert events[1].data == "second"
+    assert events[1].id == "1"
+    # The stream ended and was reconnected after the server's 10ms delay.
+    assert events[2].data == "hello\nworld"
+
+    # An ID only counts once its event is dispatched, data or not, so the one of an event
+    # cut off by the end of the stream is dropped.
+    url = (
+        "http://localhost:8080/base64/"
+        "cmV0cnk6IDEwCmRhdGE6IGEKCmlkOiAxCgpkYXRhOiBiCgppZDogMgpkYXRhOiBjdXQ="
+    )
+    events = []
+    async for event in client.sse(url):
+        events.append(event)
+        if len(events) == 3:
+            break
+
+    assert [event.data for event in events] == ["a", "b", "a"]
+    assert [event.id for event in events] == ["", "1", "1"]
+
+    with pytest.raises(wreq.StatusError):
+        next(wreq.blocking.Client().sse("http://localhost:8080/status/500"))
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_sent_request():
+    client = wreq.Client(headers={"X-Client": "default"})
+    resp = await client.get(
+        "http://localhost:8080/headers",
+        headers={"X-Request": "own"},
+        return_sent=True,
+    )
+    async with resp:
+        sent = resp.sent_request
+        assert sent.method == "GET"
+        assert sent.url == "http://localhost:8080/headers"
+        # Only the request's own headers are captured, with lowercased names.
+        assert sent.headers["x-request"] == b"own"
+        assert "x-client" not in sent.headers
+        # The client's default headers are still sent.
+        assert (await resp.json())["headers"]["X-Client"] == "default"
+
+    resp = await client.get("http://localhost:8080/get")
+    async with resp:
+        assert resp.sent_request is None
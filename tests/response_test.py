@@ -1,5 +1,12 @@
+import asyncio
+import base64
+import gzip
+import hashlib
+import json
 import pytest
 import wreq
+from datetime import timedelta
+from decimal import Decimal
 from pathlib import Path
 from wreq import Version, Multipart, Part
 
@@ -111,6 +118,510 @@ async def test_get_stream():
                 assert bytes is not None
 
 
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_get_stream_max_bytes():
+    url = "http://localhost:8080/stream-bytes/4096?chunk_size=512"
+    resp = await client.get(url)
+    async with resp:
+        total = 0
+        async with resp.stream(max_bytes=1000) as streamer:
+            async for chunk in streamer:
+                total += len(chunk)
+        assert total == 1000
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_iter_content_default():
+    url = "http://localhost:8080/stream-bytes/4096?chunk_size=512&seed=1"
+    resp = await client.get(url)
+    async with resp:
+        chunks = [chunk async for chunk in resp.iter_content()]
+        assert all(isinstance(chunk, bytes) for chunk in chunks)
+        assert sum(len(chunk) for chunk in chunks) == 4096
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_iter_content_chunk_size():
+    url = "http://localhost:8080/stream-bytes/4096?chunk_size=500&seed=1"
+    resp = await client.get(url)
+    async with resp:
+        sizes = [len(chunk) async for chunk in resp.iter_content(chunk_size=1000)]
+        assert sizes == [1000, 1000, 1000, 1000, 96]
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_iter_content_decode_unicode():
+    url = "http://localhost:8080/encoding/utf8"
+    resp = await client.get(url)
+    async with resp:
+        chunks = [chunk async for chunk in resp.iter_content(7, decode_unicode=True)]
+        assert all(isinstance(chunk, str) for chunk in chunks)
+
+    expected = await (await client.get(url)).text()
+    assert "".join(chunks) == expected
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_extract_links():
+    resp = await client.get("http://localhost:8080/links/3/0")
+    async with resp:
+        links = await resp.extract_links()
+        assert links == [
+            "http://localhost:8080/links/3/1",
+            "http://localhost:8080/links/3/2",
+        ]
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_bytes_and_str_of_buffered_body():
+    resp = await client.get("http://localhost:8080/robots.txt")
+    async with resp:
+        with pytest.raises(RuntimeError):
+            bytes(resp)
+
+        text = await resp.text()
+        assert str(resp) == text
+        assert bytes(resp) == text.encode()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_set_cookies():
+    resp = await client.get("http://localhost:8080/cookies/set?foo=bar")
+    async with resp:
+        cookies = resp.set_cookies
+        assert len(cookies) == 1
+        assert cookies[0].name == "foo"
+        assert cookies[0].value == "bar"
+        assert cookies[0].domain == "localhost"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_auto_buffer_limit():
+    buffered = wreq.Client(auto_buffer_limit=1024)
+
+    resp = await buffered.get("http://localhost:8080/bytes/100")
+    async with resp:
+        assert len(bytes(resp)) == 100
+        assert len(await resp.bytes()) == 100
+
+    resp = await buffered.get("http://localhost:8080/bytes/2048")
+    async with resp:
+        assert len(await resp.bytes()) == 2048
+        with pytest.raises(RuntimeError):
+            await resp.bytes()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_json_loads():
+    calls = []
+
+    def loads(data):
+        calls.append(data)
+        return json.loads(data, parse_float=Decimal)
+
+    resp = await client.post("http://localhost:8080/post", json={"price": 1.5})
+    async with resp:
+        body = await resp.json(loads=loads)
+        assert body["json"]["price"] == Decimal("1.5")
+        assert isinstance(calls[0], bytes)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_links():
+    link = (
+        '<https://api.example.com/items?page=2>; rel="next", '
+        '<https://api.example.com/items?page=9>; rel="last"; title="a, b", '
+        "not a link"
+    )
+    resp = await client.get(
+        "http://localhost:8080/response-headers",
+        query=[("Link", link), ("Link", "</help>; rel=help")],
+    )
+    async with resp:
+        links = resp.links
+        assert links["next"] == {"url": "https://api.example.com/items?page=2", "rel": "next"}
+        assert links["last"]["title"] == "a, b"
+        assert links["help"]["url"] == "/help"
+        assert len(links) == 3
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_raw_bytes():
+    resp = await client.get(
+        "http://localhost:8080/gzip",
+        headers={"Accept-Encoding": "gzip"},
+        decompress=False,
+    )
+    async with resp:
+        assert (await resp.raw_bytes()).startswith(b"\x1f\x8b")
+
+    resp = await client.get("http://localhost:8080/gzip")
+    async with resp:
+        with pytest.raises(RuntimeError):
+            await resp.raw_bytes()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_max_body_size():
+    limited = wreq.Client(max_body_size=1024)
+
+    resp = await limited.get("http://localhost:8080/bytes/100")
+    async with resp:
+        assert len(await resp.bytes()) == 100
+
+    # Rejected up front from the `Content-Length`.
+    with pytest.raises(wreq.exceptions.BodyTooLarge):
+        await limited.get("http://localhost:8080/bytes/2048")
+
+    # Counted while streaming when the length is not known.
+    resp = await limited.get("http://localhost:8080/stream-bytes/4096?chunk_size=256")
+    async with resp:
+        with pytest.raises(wreq.exceptions.BodyTooLarge):
+            async with resp.stream() as streamer:
+                async for _ in streamer:
+                    pass
+
+    resp = await limited.get("http://localhost:8080/bytes/2048", max_body_size=4096)
+    async with resp:
+        assert len(await resp.bytes()) == 2048
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_offload_decode():
+    offloaded = wreq.Client(offload_decode=True)
+
+    resp = await offloaded.get("http://localhost:8080/json")
+    async with resp:
+        assert "slideshow" in await resp.json()
+        assert "slideshow" in await resp.text()
+
+    resp = await offloaded.get("http://localhost:8080/html")
+    async with resp:
+        with pytest.raises(wreq.exceptions.DecodingError):
+            await resp.json()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_stream_chunk_timeout():
+    resp = await client.get("http://localhost:8080/drip?duration=4&numbytes=2&delay=0")
+    async with resp:
+        async with resp.stream(chunk_timeout=timedelta(milliseconds=500)) as streamer:
+            with pytest.raises(wreq.exceptions.TimeoutError):
+                async for _ in streamer:
+                    pass
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_iter_lines():
+    resp = await client.get("http://localhost:8080/stream/3")
+    async with resp:
+        lines = [line async for line in resp.iter_lines()]
+        assert len(lines) == 3
+        assert all(isinstance(line, str) and line.startswith("{") for line in lines)
+
+    resp = await client.get("http://localhost:8080/stream/2")
+    async with resp:
+        lines = [line async for line in resp.iter_lines(b"}\n", decode_unicode=False)]
+        assert len(lines) == 2
+        assert all(isinstance(line, bytes) for line in lines)
+
+    resp = await client.get("http://localhost:8080/base64/Y2Fm6QpuYe92ZQ==")
+    async with resp:
+        lines = [line async for line in resp.iter_lines(encoding="latin-1")]
+        assert lines == ["café", "naïve"]
+
+    resp = await client.get("http://localhost:8080/stream/1")
+    async with resp:
+        with pytest.raises(ValueError):
+            resp.iter_lines(encoding="no-such-encoding")
+
+    resp = await client.get("http://localhost:8080/stream/3")
+    async with resp:
+        lines = [line async for line in resp.iter_lines(max_line_length=4096)]
+        assert len(lines) == 3
+
+    resp = await client.get("http://localhost:8080/range/4096")
+    async with resp:
+        with pytest.raises(wreq.exceptions.BodyTooLarge):
+            async for _ in resp.iter_lines(max_line_length=1024):
+                pass
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_stream_chunk_size():
+    url = "http://localhost:8080/stream-bytes/4096?chunk_size=500&seed=1"
+    resp = await client.get(url)
+    async with resp:
+        async with resp.stream(chunk_size=1000, max_bytes=2500) as streamer:
+            sizes = [len(chunk) async for chunk in streamer]
+            assert sizes == [1000, 1000, 500]
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_stream_autodetect_compression():
+    # A zstd frame holding `hello` in a single raw block.
+    zstd = bytes.fromhex("28b52ffd2005290000") + b"hello"
+    for body in [gzip.compress(b"hello"), zstd, b"hello"]:
+        url = "http://localhost:8080/base64/" + base64.urlsafe_b64encode(body).decode()
+        resp = await client.get(url)
+        async with resp:
+            assert "Content-Encoding" not in resp.headers
+            async with resp.stream(autodetect_compression=True) as streamer:
+                assert b"".join([bytes(chunk) async for chunk in streamer]) == b"hello"
+
+    # Without opting in, the compressed body is passed through as is.
+    url = "http://localhost:8080/base64/" + base64.urlsafe_b64encode(zstd).decode()
+    resp = await client.get(url)
+    async with resp:
+        async with resp.stream() as streamer:
+            assert b"".join([bytes(chunk) async for chunk in streamer]) == zstd
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_iter_multipart_requires_boundary():
+    resp = await client.get("http://localhost:8080/get")
+    async with resp:
+        with pytest.raises(ValueError):
+            resp.iter_multipart()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_ok_or_and_json_or():
+    resp = await client.get("http://localhost:8080/status/404")
+    async with resp:
+        assert resp.ok_or(None) is None
+        assert await resp.json_or({}) == {}
+
+    resp = await client.get("http://localhost:8080/html")
+    async with resp:
+        assert resp.ok_or(None) is resp
+        assert await resp.json_or("fallback") == "fallback"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_iter_chunks():
+    resp = await client.get("http://localhost:8080/bytes/4096?seed=1")
+    async with resp:
+        received = bytearray()
+        async with resp.iter_chunks() as chunks:
+            async for chunk in chunks:
+                assert isinstance(chunk, memoryview)
+                assert chunk.readonly
+                received += chunk
+        assert len(received) == 4096
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_raise_for_status():
+    resp = await client.get("http://localhost:8080/get")
+    async with resp:
+        assert resp.raise_for_status() is resp
+
+    resp = await client.get("http://localhost:8080/status/404")
+    async with resp:
+        with pytest.raises(wreq.StatusError) as exc:
+            resp.raise_for_status()
+        assert exc.value.status == 404
+        assert exc.value.reason == "Not Found"
+        assert exc.value.url == "http://localhost:8080/status/404"
+        assert exc.value.response is resp
+        assert await resp.text() is not None
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_ok():
+    resp = await client.get("http://localhost:8080/status/204")
+    async with resp:
+        assert resp.ok is True
+
+    resp = await client.get("http://localhost:8080/status/500")
+    async with resp:
+        assert resp.ok is False
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_paginate_json():
+    def next_url(page):
+        if int(page) < 3:
+            return f"http://localhost:8080/anything?page={int(page) + 1}"
+        return None
+
+    resp = await client.get("http://localhost:8080/anything?page=1")
+    pages = [page async for page in resp.paginate_json("args.page", next_url)]
+    assert [page["args"]["page"] for page in pages] == ["1", "2", "3"]
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_elapsed():
+    resp = await client.get("http://localhost:8080/delay/1")
+    async with resp:
+        elapsed = resp.elapsed
+        assert elapsed >= timedelta(seconds=1)
+        await resp.bytes()
+        assert resp.elapsed == elapsed
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_timings():
+    resp = await client.get("http://localhost:8080/delay/1")
+    async with resp:
+        timings = resp.timings
+        assert timedelta(seconds=1) <= timings.ttfb <= resp.elapsed
+        assert timings.dns is None or timings.dns <= timings.ttfb
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_download(tmp_path):
+    path = tmp_path / "body.bin"
+    resp = await client.get("http://localhost:8080/stream-bytes/100000?seed=1")
+    async with resp:
+        written = await resp.download(path, chunk_size=8192)
+        assert written == 100000
+        assert path.stat().st_size == written
+
+        with pytest.raises(RuntimeError):
+            await resp.download(path)
+
+    resp = await client.get("http://localhost:8080/bytes/100")
+    async with resp:
+        assert await resp.save(path, chunk_size=64, append=True) == 100
+        assert path.stat().st_size == 100100
+
+
+@pytest.mark.asyncio
+async def test_trailers():
+    async def handle(reader, writer):
+        await reader.readuntil(b"\r\n\r\n")
+        writer.write(
+            b"HTTP/1.1 200 OK\r\n"
+            b"Transfer-Encoding: chunked\r\n"
+            b"Trailer: x-checksum\r\n"
+            b"Connection: close\r\n\r\n"
+            b"5\r\nhello\r\n0\r\nx-checksum: abc\r\n\r\n"
+        )
+        await writer.drain()
+        writer.close()
+
+    server = await asyncio.start_server(handle, "127.0.0.1", 0)
+    port = server.sockets[0].getsockname()[1]
+    async with server:
+        resp = await client.get(f"http://127.0.0.1:{port}/")
+        async with resp:
+            assert resp.trailers is None
+            assert await resp.bytes() == b"hello"
+            assert resp.trailers["x-checksum"] == b"abc"
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_save_digest(tmp_path):
+    url = "http://localhost:8080/base64/aGVsbG8="
+    path = tmp_path / "hello.txt"
+
+    resp = await client.get(url)
+    async with resp:
+        digest = hashlib.sha512(b"hello").hexdigest()
+        assert await resp.save(path, digest=digest, algorithm="sha512") == 5
+        assert path.read_bytes() == b"hello"
+
+    # A mismatch on an appended body restores the file to its previous length.
+    resp = await client.get(url)
+    async with resp:
+        with pytest.raises(wreq.exceptions.DigestMismatch):
+            await resp.save(path, digest=hashlib.sha256(b"other").hexdigest(), append=True)
+        assert path.read_bytes() == b"hello"
+
+    # A mismatch on a new file removes it.
+    path = tmp_path / "mismatch.txt"
+    resp = await client.get(url)
+    async with resp:
+        with pytest.raises(wreq.exceptions.DigestMismatch):
+            await resp.save(path, digest=hashlib.sha256(b"other").hexdigest())
+        assert not path.exists()
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_follow_refresh():
+    resp = await client.get(
+        "http://localhost:8080/response-headers",
+        query={"Refresh": "0; url=/get?refreshed=1"},
+    )
+    async with resp:
+        refreshed = await resp.follow_refresh()
+        assert refreshed is not None
+        async with refreshed:
+            assert str(refreshed.url) == "http://localhost:8080/get?refreshed=1"
+
+    resp = await client.get("http://localhost:8080/get")
+    async with resp:
+        assert await resp.follow_refresh() is None
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_content():
+    resp = await client.get("http://localhost:8080/json")
+    async with resp:
+        with pytest.raises(RuntimeError):
+            resp.content
+        json = await resp.json()
+        assert resp.content == await resp.bytes()
+        assert len(resp.content) > 0
+        assert json
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_body_sizes():
+    resp = await client.get("http://localhost:8080/bytes/64")
+    async with resp:
+        assert resp.decoded_bytes is None
+        await resp.bytes()
+        assert resp.decoded_bytes == 64
+        assert resp.downloaded_bytes == 64
+
+    resp = await client.get("http://localhost:8080/gzip")
+    async with resp:
+        body = await resp.bytes()
+        assert "content-encoding" not in resp.headers
+        assert json.loads(body)["gzipped"] is True
+        assert resp.downloaded_bytes < resp.decoded_bytes == len(body)
+
+    raw = wreq.Client(gzip=False, brotli=False, deflate=False, zstd=False)
+    resp = await raw.get("http://localhost:8080/gzip")
+    async with resp:
+        body = await resp.bytes()
+        assert resp.headers["content-encoding"] == b"gzip"
+        assert resp.downloaded_bytes == resp.decoded_bytes == len(body)
+
+
 @pytest.mark.asyncio
 @pytest.mark.flaky(reruns=3, reruns_delay=2)
 async def test_peer_certificate():
@@ -118,3 +629,45 @@ async def test_peer_certificate():
     async with resp:
         assert resp.tls_info is not None
         assert resp.tls_info.peer_certificate() is not None
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_cache_info():
+    resp = await client.get(
+        "http://localhost:8080/response-headers",
+        query=[
+            ("Cache-Control", 'public, max-age=300, s-maxage="600", no-transform'),
+            ("Age", "42"),
+            ("X-Cache", "Hit from cloudfront"),
+        ],
+    )
+    async with resp:
+        info = resp.cache_info()
+        assert info.age == 42
+        assert info.hit is True
+        assert info.cache_control.public
+        assert info.cache_control.no_transform
+        assert not info.cache_control.no_store
+        assert info.cache_control.max_age == 300
+        assert info.cache_control.s_maxage == 600
+        assert info.cdn_cache_control is None
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_cache_info_quoted_directives():
+    resp = await client.get(
+        "http://localhost:8080/response-headers",
+        query=[
+            ("Cache-Control", 'private="Set-Cookie, Authorization", No-Store, max-age=0'),
+        ],
+    )
+    async with resp:
+        cache_control = resp.cache_info().cache_control
+        assert cache_control.private
+        assert cache_control.no_store
+        assert cache_control.max_age == 0
+        assert cache_control.directives["private"] == "Set-Cookie, Authorization"
+        assert "no-store" in cache_control
+        assert resp.cache_info().age is None
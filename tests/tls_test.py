@@ -1,7 +1,10 @@
+import os
+import ssl
+
 import pytest
 import wreq
 from wreq.emulation import Emulation
-from wreq.tls import CertStore
+from wreq.tls import CertStore, Identity, KeyLog
 
 
 @pytest.mark.asyncio
@@ -39,6 +42,23 @@ async def test_badssl_invalid_cert():
             assert resp.status.is_success()
 
 
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_badssl_per_request_verify():
+    url = "https://self-signed.badssl.com/"
+    client = wreq.Client()
+    resp = await client.get(url, tls_verify=False)
+    async with resp:
+        assert resp.status.is_success()
+
+    with pytest.raises((wreq.TlsError, wreq.ConnectionError)):
+        await client.get(url)
+
+    client = wreq.Client(tls_verify=False)
+    with pytest.raises((wreq.TlsError, wreq.ConnectionError)):
+        await client.get(url, tls_verify=True)
+
+
 @pytest.mark.asyncio
 @pytest.mark.flaky(reruns=3, reruns_delay=2)
 async def test_alps_new_endpoint():
@@ -48,3 +68,81 @@ async def test_alps_new_endpoint():
     async with resp:
         text = await resp.text()
         assert text is not None
+
+
+def test_identity_constructors():
+    with pytest.raises(wreq.exceptions.RnetError):
+        Identity.from_pem(b"not a certificate", b"not a key")
+    with pytest.raises(wreq.exceptions.RnetError):
+        Identity.from_pkcs12(b"not an archive", "password")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_client_certificate():
+    resp = await wreq.get("https://badssl.com/certs/badssl.com-client.p12")
+    identity = Identity.from_pkcs12(await resp.bytes(), "badssl.com")
+
+    resp = await wreq.Client().get("https://client.badssl.com/")
+    async with resp:
+        assert resp.status.as_int() == 400
+
+    resp = await wreq.Client(identity=identity).get("https://client.badssl.com/")
+    async with resp:
+        assert resp.status.as_int() == 200
+
+    with pytest.raises(ValueError):
+        wreq.Client(identity=identity, tls_identity=identity)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_tls_verify_bytes():
+    cafile = ssl.get_default_verify_paths().openssl_cafile
+    if not os.path.exists(cafile):
+        pytest.skip("no system CA bundle")
+    with open(cafile, "rb") as f:
+        client = wreq.Client(tls_verify=f.read())
+    resp = await client.get("https://www.example.com")
+    async with resp:
+        assert resp.status.is_success()
+
+    with pytest.raises(wreq.exceptions.RnetError):
+        wreq.Client(tls_verify=b"-----BEGIN CERTIFICATE-----\nnot a certificate")
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_keylog(tmp_path):
+    path = tmp_path / "keys.log"
+    resp = await wreq.Client(tls_keylog=path).get("https://www.example.com")
+    async with resp:
+        assert resp.status.is_success()
+    assert "CLIENT_" in path.read_text()
+
+    wreq.Client(tls_keylog=True)
+    wreq.Client(tls_keylog=KeyLog.environment())
+
+
+def test_emulation_info():
+    info = wreq.Client(emulation=Emulation.Chrome133).emulation_info()
+    assert info.profile == Emulation.Chrome133
+    assert info.alpn_protocols is not None
+
+    info = wreq.Client().emulation_info()
+    assert info.profile is None
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_tls_cipher_list():
+    client = wreq.Client(
+        tls_cipher_list="ECDHE-ECDSA-AES128-GCM-SHA256:ECDHE-RSA-AES128-GCM-SHA256",
+        tls_curves="X25519:P-256",
+    )
+    resp = await client.get("https://www.google.com")
+    async with resp:
+        assert resp.status.is_success()
+
+    with pytest.raises(ValueError):
+        wreq.Client(emulation=Emulation.Chrome133, tls_cipher_list="ECDHE-RSA-AES128-GCM-SHA256")
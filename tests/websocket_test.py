@@ -0,0 +1,91 @@
+from datetime import timedelta
+
+import pytest
+import wreq
+from wreq import Message
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_websocket_records():
+    ws = await wreq.websocket("wss://echo.websocket.org")
+    async with ws:
+        await ws.send_all([Message.from_text(f"Message {i}") for i in range(5)])
+
+        records = []
+        async for record in ws.records():
+            records.append(record)
+            if len(records) == 5:
+                break
+
+        seqs = [seq for seq, _, _ in records]
+        assert seqs == list(range(seqs[0], seqs[0] + 5))
+
+        times = [recv_time for _, recv_time, _ in records]
+        assert times == sorted(times)
+        assert all(isinstance(recv_time, timedelta) for recv_time in times)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_websocket_run():
+    texts, binaries, closes = [], [], []
+
+    async def on_text(text):
+        texts.append(text)
+
+    ws = await wreq.websocket("wss://echo.websocket.org")
+    async with ws:
+        await ws.send_all(
+            [
+                Message.from_text("hello"),
+                Message.from_binary(b"world"),
+                Message.from_close(1000, "bye"),
+            ]
+        )
+        await ws.run(
+            on_text=on_text,
+            on_binary=binaries.append,
+            on_close=lambda code, reason: closes.append(code),
+        )
+
+    assert "hello" in texts
+    assert binaries == [b"world"]
+    assert closes == [1000]
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_websocket_run_callback_raises():
+    def on_binary(data):
+        raise LookupError(data)
+
+    ws = await wreq.websocket("wss://echo.websocket.org")
+    async with ws:
+        await ws.send(Message.from_binary(b"stop"))
+        with pytest.raises(LookupError):
+            await ws.run(on_binary=on_binary)
+
+
+@pytest.mark.asyncio
+@pytest.mark.flaky(reruns=3, reruns_delay=2)
+async def test_websocket_fragment():
+    ws = await wreq.websocket("wss://echo.websocket.org")
+    async with ws:
+        await ws.send_all(
+            [
+                Message.fragment("Hello, ", opcode="text"),
+                Message.fragment("wor"),
+                Message.fragment("ld", fin=True),
+                Message.fragment(b"\x00\x01", opcode="binary"),
+                Message.fragment(b"\x02", fin=True),
+            ]
+        )
+        # The fragments are reassembled into whole messages on the way back.
+        received = []
+        while len(received) < 2:
+            message = await ws.recv(timeout=timedelta(seconds=10))
+            if message.text == "Hello, world" or message.binary is not None:
+                received.append(message)
+        assert received[0].text == "Hello, world"
+        assert received[1].binary == b"\x00\x01\x02"